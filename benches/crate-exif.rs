@@ -1,4 +1,5 @@
 use criterion::{Criterion, criterion_group, criterion_main};
+use gufo_exif::Lookup;
 
 fn thumbnailer(c: &mut Criterion) {
     let mut group = c.benchmark_group("apple-iphone6.exif.le.tiff");
@@ -25,9 +26,126 @@ fn thumbnailer(c: &mut Criterion) {
     });
 }
 
+fn getters(c: &mut Criterion) {
+    let mut group = c.benchmark_group("apple-iphone6.exif.le.tiff getters");
+    let data = std::fs::read("../apple-iphone6.exif.le.tiff").unwrap();
+
+    let gufo = gufo_exif::Exif::for_vec(data.clone()).unwrap();
+    group.bench_function("gufo", |b| {
+        b.iter(|| {
+            gufo.model().unwrap();
+            gufo.orientation().unwrap();
+            gufo.gps_location().unwrap();
+        })
+    });
+
+    let kamadak = exif::Reader::new().read_raw(data.clone()).unwrap();
+    group.bench_function("kamadak-exif", |b| {
+        b.iter(|| {
+            kamadak
+                .get_field(exif::Tag::Model, exif::In::PRIMARY)
+                .unwrap();
+            kamadak
+                .get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+                .unwrap();
+            kamadak
+                .get_field(exif::Tag::GPSLatitude, exif::In::PRIMARY)
+                .unwrap();
+            kamadak
+                .get_field(exif::Tag::GPSLongitude, exif::In::PRIMARY)
+                .unwrap();
+        })
+    });
+
+    group.bench_function("gufo clone", |b| b.iter(|| gufo.clone()));
+}
+
+fn example_exif() -> Vec<u8> {
+    let data = std::fs::read("../gufo-exif/example.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    jpeg.exif_data().next().unwrap().to_vec()
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("example.jpg parse");
+    let data = example_exif();
+
+    group.bench_function("gufo Exif", |b| {
+        b.iter(|| gufo_exif::Exif::for_vec(data.clone()).unwrap())
+    });
+
+    group.bench_function("gufo ExifRef", |b| {
+        b.iter(|| gufo_exif::ExifRef::for_slice(&data).unwrap())
+    });
+
+    group.bench_function("gufo Document", |b| {
+        b.iter(|| gufo_exif::structure::Document::for_slice(&data).unwrap())
+    });
+
+    group.bench_function("kamadak-exif", |b| {
+        b.iter(|| exif::Reader::new().read_raw(data.clone()).unwrap())
+    });
+}
+
+fn lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("example.jpg lookup");
+    let data = example_exif();
+
+    let gufo = gufo_exif::Exif::for_vec(data.clone()).unwrap();
+    group.bench_function("gufo Exif", |b| {
+        b.iter(|| {
+            gufo.model().unwrap();
+            gufo.orientation().unwrap();
+            gufo.exposure_time().unwrap();
+            gufo.f_number().unwrap();
+        })
+    });
+
+    let mut gufo_ref = gufo_exif::ExifRef::for_slice(&data).unwrap();
+    group.bench_function("gufo ExifRef", |b| {
+        b.iter(|| {
+            gufo_ref.model().unwrap();
+            gufo_ref.orientation().unwrap();
+            gufo_ref.exposure_time().unwrap();
+            gufo_ref.f_number().unwrap();
+        })
+    });
+
+    let mut document = gufo_exif::structure::Document::for_slice(&data).unwrap();
+    group.bench_function("gufo Document", |b| {
+        b.iter(|| {
+            document.model().unwrap();
+            document.orientation().unwrap();
+            document.exposure_time().unwrap();
+            document.f_number().unwrap();
+        })
+    });
+
+    let kamadak = exif::Reader::new().read_raw(data.clone()).unwrap();
+    group.bench_function("kamadak-exif", |b| {
+        b.iter(|| {
+            for tag in [
+                exif::Tag::Model,
+                exif::Tag::Orientation,
+                exif::Tag::ExposureTime,
+                exif::Tag::FNumber,
+            ] {
+                kamadak.get_field(tag, exif::In::PRIMARY).unwrap();
+            }
+        })
+    });
+
+    group.bench_function("gufo serialize big endian", |b| {
+        b.iter(|| {
+            gufo.serialize_with(gufo_exif::structure::Endieness::Big)
+                .unwrap()
+        })
+    });
+}
+
 criterion_main!(benches);
 criterion_group!(
     name = benches;
     config = Criterion::default().with_plots();
-    targets = thumbnailer
+    targets = thumbnailer, getters, parse, lookup
 );
@@ -0,0 +1,356 @@
+//! Enumerated values describing how an image was captured
+//!
+//! All types can be created from the raw value via [`From`]. Values that are
+//! not defined in the Exif specification are kept as `Unknown`.
+//!
+//! ```
+//! # use gufo_common::capture::{ExposureProgram, MeteringMode};
+//! assert_eq!(ExposureProgram::from(3), ExposureProgram::AperturePriority);
+//! assert_eq!(
+//!     ExposureProgram::AperturePriority.label(),
+//!     Some("Aperture priority")
+//! );
+//!
+//! assert_eq!(MeteringMode::from(42).label(), None);
+//! assert_eq!(MeteringMode::from(42).to_string(), "Unknown (42)");
+//! ```
+
+use std::fmt::Display;
+
+/// Enum with a human-readable label for each variant
+macro_rules! labeled_enum {
+    ($(#[$meta:meta])* pub enum $enum_name:ident {
+        $($(#[$variant_meta:meta])* $variant_name:ident = $variant_value:expr => $label:literal,)*
+    }) => {
+        crate::utils::convertible_enum!(
+            #[repr(u16)]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+            $(#[$meta])*
+            pub enum $enum_name {
+                $($(#[$variant_meta])* $variant_name = $variant_value,)*
+            }
+        );
+
+        impl $enum_name {
+            /// Human-readable label
+            ///
+            /// Returns `None` for values that are not defined.
+            pub fn label(self) -> Option<&'static str> {
+                match self {
+                    $(Self::$variant_name => Some($label),)*
+                    Self::Unknown(_) => None,
+                }
+            }
+        }
+
+        impl Display for $enum_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self.label() {
+                    Some(label) => f.write_str(label),
+                    None => write!(f, "Unknown ({})", u16::from(*self)),
+                }
+            }
+        }
+    };
+}
+
+labeled_enum!(
+    /// Program used by the camera to set the exposure
+    ///
+    /// Stored in [`ExposureProgram`](crate::field::ExposureProgram).
+    pub enum ExposureProgram {
+        NotDefined = 0 => "Not defined",
+        Manual = 1 => "Manual",
+        Normal = 2 => "Normal program",
+        AperturePriority = 3 => "Aperture priority",
+        ShutterPriority = 4 => "Shutter priority",
+        /// Biased toward depth of field
+        Creative = 5 => "Creative program",
+        /// Biased toward fast shutter speed
+        Action = 6 => "Action program",
+        /// For closeup photos with the background out of focus
+        Portrait = 7 => "Portrait mode",
+        /// For landscape photos with the background in focus
+        Landscape = 8 => "Landscape mode",
+    }
+);
+
+labeled_enum!(
+    /// Metering mode
+    ///
+    /// Stored in [`MeteringMode`](crate::field::MeteringMode).
+    pub enum MeteringMode {
+        NotDefined = 0 => "Unknown",
+        Average = 1 => "Average",
+        CenterWeightedAverage = 2 => "Center-weighted average",
+        Spot = 3 => "Spot",
+        MultiSpot = 4 => "Multi-spot",
+        Pattern = 5 => "Pattern",
+        Partial = 6 => "Partial",
+        Other = 255 => "Other",
+    }
+);
+
+labeled_enum!(
+    /// Kind of light source
+    ///
+    /// Stored in [`LightSource`](crate::field::LightSource).
+    pub enum LightSource {
+        NotDefined = 0 => "Unknown",
+        Daylight = 1 => "Daylight",
+        Fluorescent = 2 => "Fluorescent",
+        /// Incandescent light
+        Tungsten = 3 => "Tungsten",
+        Flash = 4 => "Flash",
+        FineWeather = 9 => "Fine weather",
+        CloudyWeather = 10 => "Cloudy weather",
+        Shade = 11 => "Shade",
+        /// Daylight fluorescent (D 5700–7100 K)
+        DaylightFluorescent = 12 => "Daylight fluorescent",
+        /// Day white fluorescent (N 4600–5500 K)
+        DayWhiteFluorescent = 13 => "Day white fluorescent",
+        /// Cool white fluorescent (W 3800–4500 K)
+        CoolWhiteFluorescent = 14 => "Cool white fluorescent",
+        /// White fluorescent (WW 3250–3800 K)
+        WhiteFluorescent = 15 => "White fluorescent",
+        /// Warm white fluorescent (L 2600–3250 K)
+        WarmWhiteFluorescent = 16 => "Warm white fluorescent",
+        StandardLightA = 17 => "Standard light A",
+        StandardLightB = 18 => "Standard light B",
+        StandardLightC = 19 => "Standard light C",
+        D55 = 20 => "D55",
+        D65 = 21 => "D65",
+        D75 = 22 => "D75",
+        D50 = 23 => "D50",
+        IsoStudioTungsten = 24 => "ISO studio tungsten",
+        Other = 255 => "Other",
+    }
+);
+
+labeled_enum!(
+    /// White balance mode
+    ///
+    /// Stored in [`WhiteBalance`](crate::field::WhiteBalance).
+    pub enum WhiteBalance {
+        Auto = 0 => "Auto",
+        Manual = 1 => "Manual",
+    }
+);
+
+labeled_enum!(
+    /// Type of scene that was shot
+    ///
+    /// Stored in [`SceneCaptureType`](crate::field::SceneCaptureType).
+    pub enum SceneCaptureType {
+        Standard = 0 => "Standard",
+        Landscape = 1 => "Landscape",
+        Portrait = 2 => "Portrait",
+        NightScene = 3 => "Night scene",
+    }
+);
+
+labeled_enum!(
+    /// Type of image sensor
+    ///
+    /// Stored in [`SensingMethod`](crate::field::SensingMethod).
+    pub enum SensingMethod {
+        NotDefined = 1 => "Not defined",
+        OneChipColorArea = 2 => "One-chip color area sensor",
+        TwoChipColorArea = 3 => "Two-chip color area sensor",
+        ThreeChipColorArea = 4 => "Three-chip color area sensor",
+        ColorSequentialArea = 5 => "Color sequential area sensor",
+        Trilinear = 7 => "Trilinear sensor",
+        ColorSequentialLinear = 8 => "Color sequential linear sensor",
+    }
+);
+
+labeled_enum!(
+    /// Color space of the image data
+    ///
+    /// Stored in [`ColorSpace`](crate::field::ColorSpace).
+    pub enum ColorSpace {
+        /// Color space other than sRGB
+        Uncalibrated = 0xFFFF => "Uncalibrated",
+        Srgb = 1 => "sRGB",
+    }
+);
+
+labeled_enum!(
+    /// Direction of contrast processing applied by the camera
+    ///
+    /// Stored in [`Contrast`](crate::field::Contrast).
+    pub enum Contrast {
+        Normal = 0 => "Normal",
+        Soft = 1 => "Soft",
+        Hard = 2 => "Hard",
+    }
+);
+
+labeled_enum!(
+    /// Direction of saturation processing applied by the camera
+    ///
+    /// Stored in [`Saturation`](crate::field::Saturation).
+    pub enum Saturation {
+        Normal = 0 => "Normal",
+        Low = 1 => "Low",
+        High = 2 => "High",
+    }
+);
+
+labeled_enum!(
+    /// Direction of sharpness processing applied by the camera
+    ///
+    /// Stored in [`Sharpness`](crate::field::Sharpness).
+    pub enum Sharpness {
+        Normal = 0 => "Normal",
+        Soft = 1 => "Soft",
+        Hard = 2 => "Hard",
+    }
+);
+
+labeled_enum!(
+    /// Exposure mode
+    ///
+    /// Stored in [`ExposureMode`](crate::field::ExposureMode).
+    pub enum ExposureMode {
+        Auto = 0 => "Auto",
+        Manual = 1 => "Manual",
+        AutoBracket = 2 => "Auto bracket",
+    }
+);
+
+labeled_enum!(
+    /// Degree of overall image gain adjustment
+    ///
+    /// Stored in [`GainControl`](crate::field::GainControl).
+    pub enum GainControl {
+        None = 0 => "None",
+        LowGainUp = 1 => "Low gain up",
+        HighGainUp = 2 => "High gain up",
+        LowGainDown = 3 => "Low gain down",
+        HighGainDown = 4 => "High gain down",
+    }
+);
+
+labeled_enum!(
+    /// Distance to the subject
+    ///
+    /// Stored in [`SubjectDistanceRange`](crate::field::SubjectDistanceRange).
+    pub enum SubjectDistanceRange {
+        NotDefined = 0 => "Unknown",
+        Macro = 1 => "Macro",
+        CloseView = 2 => "Close view",
+        DistantView = 3 => "Distant view",
+    }
+);
+
+labeled_enum!(
+    /// Use of special processing on the image data
+    ///
+    /// Stored in [`CustomRendered`](crate::field::CustomRendered).
+    pub enum CustomRendered {
+        Normal = 0 => "Normal process",
+        Custom = 1 => "Custom process",
+    }
+);
+
+labeled_enum!(
+    /// Status of the returned light of the flash
+    pub enum FlashReturn {
+        NoDetectionFunction = 0 => "No strobe return detection function",
+        NotDetected = 2 => "Strobe return light not detected",
+        Detected = 3 => "Strobe return light detected",
+    }
+);
+
+labeled_enum!(
+    /// Flash mode
+    pub enum FlashMode {
+        NotDefined = 0 => "Unknown",
+        /// Compulsory flash firing
+        On = 1 => "On",
+        /// Compulsory flash suppression
+        Off = 2 => "Off",
+        Auto = 3 => "Auto",
+    }
+);
+
+/// Status of the flash
+///
+/// Decoded from the bit field stored in [`Flash`](crate::field::Flash).
+///
+/// ```
+/// # use gufo_common::capture::{Flash, FlashMode};
+/// let flash = Flash::from(0x19);
+/// assert!(flash.fired);
+/// assert_eq!(flash.mode, FlashMode::Auto);
+/// assert_eq!(flash.to_string(), "Fired, auto mode");
+/// assert_eq!(u16::from(flash), 0x19);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Flash {
+    /// Flash fired
+    pub fired: bool,
+    pub return_light: FlashReturn,
+    pub mode: FlashMode,
+    /// Camera has a flash
+    pub function_present: bool,
+    /// Red-eye reduction supported
+    pub red_eye_reduction: bool,
+}
+
+impl From<u16> for Flash {
+    fn from(value: u16) -> Self {
+        let bit = |n: u16| value & (1 << n) != 0;
+
+        Self {
+            fired: bit(0),
+            return_light: FlashReturn::from((value >> 1) & 0b11),
+            mode: FlashMode::from((value >> 3) & 0b11),
+            // The bit is set if there is *no* flash function
+            function_present: !bit(5),
+            red_eye_reduction: bit(6),
+        }
+    }
+}
+
+impl From<Flash> for u16 {
+    fn from(flash: Flash) -> Self {
+        u16::from(flash.fired)
+            | (u16::from(flash.return_light) & 0b11) << 1
+            | (u16::from(flash.mode) & 0b11) << 3
+            | u16::from(!flash.function_present) << 5
+            | u16::from(flash.red_eye_reduction) << 6
+    }
+}
+
+impl Display for Flash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.function_present {
+            return f.write_str("No flash function");
+        }
+
+        f.write_str(if self.fired { "Fired" } else { "Did not fire" })?;
+
+        match self.mode {
+            FlashMode::On => f.write_str(", compulsory")?,
+            FlashMode::Off => f.write_str(", suppressed")?,
+            FlashMode::Auto => f.write_str(", auto mode")?,
+            FlashMode::NotDefined | FlashMode::Unknown(_) => {}
+        }
+
+        match self.return_light {
+            FlashReturn::NotDetected => f.write_str(", return not detected")?,
+            FlashReturn::Detected => f.write_str(", return detected")?,
+            FlashReturn::NoDetectionFunction | FlashReturn::Unknown(_) => {}
+        }
+
+        if self.red_eye_reduction {
+            f.write_str(", red-eye reduction")?;
+        }
+
+        Ok(())
+    }
+}
@@ -28,10 +28,89 @@ pub trait Field {
     const IFD_ENTRY: Option<IfdId> = None;
 }
 
+/// Definition of a tag in the Exif, TIFF, TIFF/EP, or DNG standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TagInfo {
+    pub name: &'static str,
+    pub tag: Tag,
+    pub ifd: IfdId,
+    /// Types the value can be stored as
+    ///
+    /// The first type is the preferred one.
+    pub types: &'static [Type],
+    pub count: Count,
+    /// Short human-readable description
+    pub description: &'static str,
+}
+
+/// Expected number of values in an entry
+///
+/// For [`Type::Ascii`] the count includes the terminating NUL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Count {
+    Fixed(usize),
+    Any,
+}
+
+impl Count {
+    pub fn matches(self, count: usize) -> bool {
+        match self {
+            Self::Fixed(n) => n == count,
+            Self::Any => true,
+        }
+    }
+}
+
+/// Returns the definition of a known tag
+///
+/// Tags in the [`IfdId::Thumbnail`], [`IfdId::Chain`], and [`IfdId::SubIfd`]
+/// that are not explicitly defined for these IFDs fall back to the definition
+/// in the [`IfdId::Primary`].
+///
+/// ```
+/// # use gufo_common::exif::{lookup_tag_info, Count, Type};
+/// let info = lookup_tag_info(gufo_common::field::GPSLatitude.into()).unwrap();
+/// assert_eq!(info.types, &[Type::Rational]);
+/// assert_eq!(info.count, Count::Fixed(3));
+/// ```
+pub fn lookup_tag_info(tagifd: TagIfd) -> Option<&'static TagInfo> {
+    crate::field::TAGS
+        .get(&(tagifd.tag, tagifd.ifd))
+        .or_else(|| {
+            tagifd
+                .ifd
+                .is_image()
+                .then(|| crate::field::TAGS.get(&(tagifd.tag, IfdId::Primary)))
+                .flatten()
+        })
+}
+
 pub fn lookup_tag_name(tagifd: TagIfd) -> Option<&'static str> {
-    crate::field::TAG_NAMES
-        .get(&(tagifd.tag.0, tagifd.ifd))
-        .copied()
+    lookup_tag_info(tagifd).map(|x| x.name)
+}
+
+/// Namespace of the XMP property that corresponds to the tag
+///
+/// The XMP property has the same name as the tag. Tags with an XMP equivalent
+/// of a different name, like [`Artist`](crate::field::Artist), are not
+/// included.
+///
+/// ```
+/// # use gufo_common::exif::lookup_xmp_namespace;
+/// # use gufo_common::field;
+/// # use gufo_common::xmp::Namespace;
+/// assert_eq!(
+///     lookup_xmp_namespace(field::Orientation.into()),
+///     Some(&Namespace::Tiff)
+/// );
+/// ```
+pub fn lookup_xmp_namespace(tagifd: TagIfd) -> Option<&'static crate::xmp::Namespace> {
+    crate::field::XMP_NAMESPACES.get(&(tagifd.tag, tagifd.ifd))
+}
+
+/// All known tag definitions in no particular order
+pub fn tag_infos() -> impl Iterator<Item = &'static TagInfo> {
+    crate::field::TAGS.values()
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
@@ -43,6 +122,14 @@ impl Tag {
     pub const EXIF_IFD_POINTER: Self = Self(0x8769);
     pub const GPS_INFO_IFD_POINTER: Self = Self(0x8825);
     pub const INTEROPERABILITY_IFD_POINTER: Self = Self(0xA005);
+    pub const SUB_IFDS: Self = Self(0x14A);
+
+    pub const STRIP_OFFSETS: Self = Self(0x111);
+    pub const STRIP_BYTE_COUNTS: Self = Self(0x117);
+    pub const TILE_OFFSETS: Self = Self(0x144);
+    pub const TILE_BYTE_COUNTS: Self = Self(0x145);
+    pub const JPEG_INTERCHANGE_FORMAT: Self = Self(0x201);
+    pub const JPEG_INTERCHANGE_FORMAT_LENGTH: Self = Self(0x202);
 
     /// Returns the IFD if the tag stores the location of that IFD
     ///
@@ -59,6 +146,24 @@ impl Tag {
     pub fn is_exif_specific_ifd(&self) -> bool {
         self.exif_specific_ifd().is_some()
     }
+
+    /// Returns true if the values of the tag are offsets to image data
+    ///
+    /// These are the strip, tile, and JPEG thumbnail offsets defined in TIFF
+    /// 6.0 and Exif 3.0.
+    pub fn is_data_offset(&self) -> bool {
+        self.data_length_tag().is_some()
+    }
+
+    /// Returns the tag that stores the byte counts for data offsets
+    pub fn data_length_tag(&self) -> Option<Tag> {
+        match *self {
+            Self::STRIP_OFFSETS => Some(Self::STRIP_BYTE_COUNTS),
+            Self::TILE_OFFSETS => Some(Self::TILE_BYTE_COUNTS),
+            Self::JPEG_INTERCHANGE_FORMAT => Some(Self::JPEG_INTERCHANGE_FORMAT_LENGTH),
+            _ => None,
+        }
+    }
 }
 
 impl Deref for Tag {
@@ -68,13 +173,324 @@ impl Deref for Tag {
     }
 }
 
+crate::utils::convertible_enum!(
+    #[repr(u16)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    /// Exif datatype
+    ///
+    /// Specifies which type an entry has
+    pub enum Type {
+        Byte = 1,
+        Ascii = 2,
+        Short = 3,
+        Long = 4,
+        Rational = 5,
+        /// Signed 8-bit integer, defined in TIFF 6.0
+        SByte = 6,
+        Undefined = 7,
+        /// Signed 16-bit integer, defined in TIFF 6.0
+        SShort = 8,
+        SLong = 9,
+        SRational = 10,
+        /// IEEE single precision float, defined in TIFF 6.0
+        Float = 11,
+        /// IEEE double precision float, defined in TIFF 6.0
+        Double = 12,
+        /// Offset to an IFD, defined in TIFF Technical Note 1
+        Ifd = 13,
+        /// Unsigned 64-bit integer, defined in BigTIFF
+        Long8 = 16,
+        /// Signed 64-bit integer, defined in BigTIFF
+        SLong8 = 17,
+        /// 64-bit offset to an IFD, defined in BigTIFF
+        Ifd8 = 18,
+        Utf8 = 129,
+    }
+);
+
+impl Type {
+    /// Size of an entry per count
+    pub const fn size(self) -> usize {
+        match self {
+            Self::Byte
+            | Self::Ascii
+            | Self::SByte
+            | Self::Undefined
+            | Self::Utf8
+            | Self::Unknown(_) => 1,
+            Self::Short | Self::SShort => 2,
+            Self::Long | Self::SLong | Self::Float | Self::Ifd => 4,
+            Self::Rational
+            | Self::SRational
+            | Self::Double
+            | Self::Long8
+            | Self::SLong8
+            | Self::Ifd8 => 8,
+        }
+    }
+
+    pub fn u16(self) -> u16 {
+        self.into()
+    }
+}
+
 /// Image file directory
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IfdId {
     Primary,
     Thumbnail,
     Exif,
     Gps,
     Interoperability,
-    MakerNote,
+    /// Manufacturer specific data
+    ///
+    /// The structure of the MakerNote depends on the manufacturer. Each vendor
+    /// has its own namespace of tags.
+    MakerNote(Vendor),
+    /// IFD in the chain after the thumbnail IFD, like further pages in TIFFs
+    ///
+    /// Use [`IfdId::chain`] to create IDs by their position.
+    Chain(ChainId),
+    /// IFD referenced from a [`SubIFDs`](crate::field::SubIFDs) entry
+    ///
+    /// Used for full-resolution images and previews in DNG and raw formats.
+    SubIfd(SubIfdId),
+}
+
+/// Manufacturer that defines the structure of a MakerNote
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Vendor {
+    Apple,
+    Canon,
+    Nikon,
+    /// MakerNote from an unsupported manufacturer that is read as plain IFD
+    Unknown,
+}
+
+impl IfdId {
+    /// IFD number `n` in the chain that starts with the primary IFD
+    ///
+    /// IFD0 is the [`Primary`](Self::Primary) and IFD1 the
+    /// [`Thumbnail`](Self::Thumbnail) IFD.
+    ///
+    /// ```
+    /// # use gufo_common::exif::IfdId;
+    /// assert_eq!(IfdId::chain(1), IfdId::Thumbnail);
+    /// assert_eq!(IfdId::chain(2).chain_index(), Some(2));
+    /// ```
+    pub fn chain(n: u16) -> Self {
+        match n {
+            0 => Self::Primary,
+            1 => Self::Thumbnail,
+            n => Self::Chain(ChainId(n)),
+        }
+    }
+
+    /// Position in the chain of IFDs that starts with the primary IFD
+    pub fn chain_index(self) -> Option<u16> {
+        match self {
+            Self::Primary => Some(0),
+            Self::Thumbnail => Some(1),
+            Self::Chain(chain) => Some(chain.index()),
+            _ => None,
+        }
+    }
+
+    /// IFD at `index` in the [`SubIFDs`](crate::field::SubIFDs) entry of this
+    /// IFD
+    ///
+    /// Returns `None` if this IFD can't contain SubIFDs or the maximum nesting
+    /// depth is reached.
+    ///
+    /// ```
+    /// # use gufo_common::exif::IfdId;
+    /// let preview = IfdId::Primary.sub_ifd(1).unwrap();
+    /// let nested = preview.sub_ifd(0).unwrap();
+    /// assert_eq!(nested.parent(), Some(preview));
+    /// assert_eq!(preview.parent(), Some(IfdId::Primary));
+    /// ```
+    pub fn sub_ifd(self, index: u16) -> Option<Self> {
+        match self {
+            Self::SubIfd(sub_ifd) => sub_ifd.child(index).map(Self::SubIfd),
+            ifd => Some(Self::SubIfd(SubIfdId::new(ifd.chain_index()?, index))),
+        }
+    }
+
+    /// IFD that contains the pointer to this SubIFD
+    pub fn parent(self) -> Option<Self> {
+        match self {
+            Self::SubIfd(sub_ifd) => Some(sub_ifd.parent()),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the IFD describes an image
+    ///
+    /// These are all IFDs in the main chain and all SubIFDs. They can contain
+    /// the tags of the primary IFD.
+    pub fn is_image(self) -> bool {
+        matches!(
+            self,
+            Self::Primary | Self::Thumbnail | Self::Chain(_) | Self::SubIfd(_)
+        )
+    }
+}
+
+/// Position of an IFD in the chain after the thumbnail IFD
+///
+/// The first two IFDs of the chain are [`IfdId::Primary`] and
+/// [`IfdId::Thumbnail`]. Therefore, the position is always at least two.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u16", into = "u16")
+)]
+pub struct ChainId(u16);
+
+impl ChainId {
+    /// Position in the chain of IFDs that starts with the primary IFD
+    pub fn index(self) -> u16 {
+        self.0
+    }
+}
+
+impl TryFrom<u16> for ChainId {
+    type Error = InvalidIfdId;
+
+    fn try_from(index: u16) -> Result<Self, Self::Error> {
+        match IfdId::chain(index) {
+            IfdId::Chain(chain) => Ok(chain),
+            _ => Err(InvalidIfdId::ChainIndex(index)),
+        }
+    }
+}
+
+impl From<ChainId> for u16 {
+    fn from(chain: ChainId) -> Self {
+        chain.0
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum InvalidIfdId {
+    #[error("Invalid chain index: {0}. The first two IFDs are the primary and thumbnail IFD.")]
+    ChainIndex(u16),
+    #[error(
+        "Invalid SubIFD path length: {0}. The path must contain between one and {max} indices.",
+        max = SubIfdId::MAX_DEPTH
+    )]
+    SubIfdDepth(usize),
+}
+
+/// Location of an IFD referenced from a [`SubIFDs`](crate::field::SubIFDs)
+/// entry
+///
+/// SubIFDs can contain SubIFDs themselves. The path contains the index within
+/// each of the nested SubIFDs entries.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SubIfdPath", into = "SubIfdPath")
+)]
+pub struct SubIfdId {
+    chain: u16,
+    path: [u16; SubIfdId::MAX_DEPTH],
+    depth: u8,
+}
+
+impl SubIfdId {
+    /// Maximum nesting level of SubIFDs
+    pub const MAX_DEPTH: usize = 4;
+
+    fn new(chain: u16, index: u16) -> Self {
+        let mut path = [0; Self::MAX_DEPTH];
+        path[0] = index;
+
+        Self {
+            chain,
+            path,
+            depth: 1,
+        }
+    }
+
+    /// Index of the IFD in the main chain that the SubIFDs originate from
+    pub fn chain(&self) -> u16 {
+        self.chain
+    }
+
+    /// Index in the SubIFDs entry on each nesting level
+    pub fn path(&self) -> &[u16] {
+        &self.path[..usize::from(self.depth)]
+    }
+
+    /// Index in the SubIFDs entry of the parent IFD
+    pub fn index(&self) -> u16 {
+        self.path().last().copied().unwrap_or_default()
+    }
+
+    /// IFD that contains the pointer to this SubIFD
+    pub fn parent(&self) -> IfdId {
+        match self.depth.checked_sub(1) {
+            Some(depth) if depth > 0 => {
+                let mut parent = *self;
+                parent.depth = depth;
+                parent.path[usize::from(depth)] = 0;
+                IfdId::SubIfd(parent)
+            }
+            _ => IfdId::chain(self.chain),
+        }
+    }
+
+    fn child(&self, index: u16) -> Option<Self> {
+        let mut child = *self;
+        *child.path.get_mut(usize::from(self.depth))? = index;
+        child.depth = self.depth.checked_add(1)?;
+        Some(child)
+    }
+}
+
+/// Serialized form of [`SubIfdId`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SubIfdPath {
+    chain: u16,
+    path: Vec<u16>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SubIfdPath> for SubIfdId {
+    type Error = InvalidIfdId;
+
+    fn try_from(value: SubIfdPath) -> Result<Self, Self::Error> {
+        let depth = value.path.len();
+        let (first, rest) = value
+            .path
+            .split_first()
+            .ok_or(InvalidIfdId::SubIfdDepth(depth))?;
+
+        let mut sub_ifd = Self::new(value.chain, *first);
+        for index in rest {
+            sub_ifd = sub_ifd
+                .child(*index)
+                .ok_or(InvalidIfdId::SubIfdDepth(depth))?;
+        }
+
+        Ok(sub_ifd)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<SubIfdId> for SubIfdPath {
+    fn from(sub_ifd: SubIfdId) -> Self {
+        Self {
+            chain: sub_ifd.chain,
+            path: sub_ifd.path().to_vec(),
+        }
+    }
 }
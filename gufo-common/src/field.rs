@@ -2,151 +2,401 @@
 //!
 //! Definition of metadata fields that can be looked up from various formats.
 //! Currently supported are Exif and XMP.
+//!
+//! The expected types, count, and a description of the Exif fields are
+//! available via [`lookup_tag_info`](crate::exif::lookup_tag_info).
 
 mod macros;
 
-use crate::exif::IfdId;
+use crate::exif::{IfdId, Vendor};
 
 // Exif
 macros::make_tags![
     // Interoperability
-    (0x1, InteroperabilityIndex, IfdId::Interoperability, xmp = ExifEX),
-    (0x2, InteroperabilityVersion, IfdId::Interoperability),
+    (0x1, InteroperabilityIndex, IfdId::Interoperability, [Ascii], 4, "Interoperability rule set", xmp = ExifEX),
+    (0x2, InteroperabilityVersion, IfdId::Interoperability, [Undefined], 4, "Version of the interoperability rule set"),
+    (0x1000, RelatedImageFileFormat, IfdId::Interoperability, [Ascii], Any, "File format of the related image file"),
+    (0x1001, RelatedImageWidth, IfdId::Interoperability, [Short, Long], 1, "Width of the related image"),
+    (0x1002, RelatedImageLength, IfdId::Interoperability, [Short, Long], 1, "Height of the related image"),
 
     // GPS
-    (0x0, GPSVersionID, IfdId::Gps, xmp = Exif),
-    (0x1, GPSLatitudeRef, IfdId::Gps),
-    (0x2, GPSLatitude, IfdId::Gps, xmp = Exif),
-    (0x3, GPSLongitudeRef, IfdId::Gps),
-    (0x4, GPSLongitude, IfdId::Gps, xmp = Exif),
+    (0x0, GPSVersionID, IfdId::Gps, [Byte], 4, "Version of the GPS IFD", xmp = Exif),
+    (0x1, GPSLatitudeRef, IfdId::Gps, [Ascii], 2, "North or south latitude"),
+    (0x2, GPSLatitude, IfdId::Gps, [Rational], 3, "Latitude in degrees, minutes, and seconds", xmp = Exif),
+    (0x3, GPSLongitudeRef, IfdId::Gps, [Ascii], 2, "East or west longitude"),
+    (0x4, GPSLongitude, IfdId::Gps, [Rational], 3, "Longitude in degrees, minutes, and seconds", xmp = Exif),
     /// TODO: Xmp mapping unclear
-    (0x5, GPSAltitudeRef, IfdId::Gps),
-    (0x6, GPSAltitude, IfdId::Gps, xmp = Exif),
-    (0x7, GPSTimeStamp, IfdId::Gps, xmp = Exif),
-    (0x8, GPSSatellites, IfdId::Gps, xmp = Exif),
-    (0x9, GPSStatus, IfdId::Gps, xmp = Exif),
-    (0xA, GPSMeasureMode, IfdId::Gps, xmp = Exif),
-    (0xB, GPSDOP, IfdId::Gps, xmp = Exif),
-    (0xC, GPSSpeedRef, IfdId::Gps, xmp = Exif),
-    (0xD, GPSSpeed, IfdId::Gps, xmp = Exif),
-    (0x10, GPSImgDirectionRef, IfdId::Gps, xmp = Exif),
-    (0x11, GPSImgDirection, IfdId::Gps, xmp = Exif),
-    (0x12, GPSMapDatum, IfdId::Gps, xmp = Exif),
-    (0x17, GPSDestBearingRef, IfdId::Gps, xmp = Exif),
-    (0x18, GPSDestBearing, IfdId::Gps, xmp = Exif),
-    (0x1D, GPSDateStamp, IfdId::Gps),
-    (0x1F, GPSHPositioningError, IfdId::Gps, xmp = Exif),
+    (0x5, GPSAltitudeRef, IfdId::Gps, [Byte], 1, "Altitude above or below sea level or ellipsoid"),
+    (0x6, GPSAltitude, IfdId::Gps, [Rational], 1, "Altitude in meters", xmp = Exif),
+    (0x7, GPSTimeStamp, IfdId::Gps, [Rational], 3, "Time as UTC in hours, minutes, and seconds", xmp = Exif),
+    (0x8, GPSSatellites, IfdId::Gps, [Ascii], Any, "Satellites used for measurement", xmp = Exif),
+    (0x9, GPSStatus, IfdId::Gps, [Ascii], 2, "Status of the GPS receiver", xmp = Exif),
+    (0xA, GPSMeasureMode, IfdId::Gps, [Ascii], 2, "Two- or three-dimensional measurement", xmp = Exif),
+    (0xB, GPSDOP, IfdId::Gps, [Rational], 1, "Dilution of precision of the measurement", xmp = Exif),
+    (0xC, GPSSpeedRef, IfdId::Gps, [Ascii], 2, "Unit of the speed", xmp = Exif),
+    (0xD, GPSSpeed, IfdId::Gps, [Rational], 1, "Speed of the GPS receiver", xmp = Exif),
+    (0xE, GPSTrackRef, IfdId::Gps, [Ascii], 2, "Reference for the direction of movement", xmp = Exif),
+    (0xF, GPSTrack, IfdId::Gps, [Rational], 1, "Direction of movement in degrees", xmp = Exif),
+    (0x10, GPSImgDirectionRef, IfdId::Gps, [Ascii], 2, "Reference for the image direction", xmp = Exif),
+    (0x11, GPSImgDirection, IfdId::Gps, [Rational], 1, "Direction of the image in degrees", xmp = Exif),
+    (0x12, GPSMapDatum, IfdId::Gps, [Ascii], Any, "Geodetic survey data", xmp = Exif),
+    (0x13, GPSDestLatitudeRef, IfdId::Gps, [Ascii], 2, "North or south latitude of the destination"),
+    (0x14, GPSDestLatitude, IfdId::Gps, [Rational], 3, "Latitude of the destination", xmp = Exif),
+    (0x15, GPSDestLongitudeRef, IfdId::Gps, [Ascii], 2, "East or west longitude of the destination"),
+    (0x16, GPSDestLongitude, IfdId::Gps, [Rational], 3, "Longitude of the destination", xmp = Exif),
+    (0x17, GPSDestBearingRef, IfdId::Gps, [Ascii], 2, "Reference for the bearing to the destination", xmp = Exif),
+    (0x18, GPSDestBearing, IfdId::Gps, [Rational], 1, "Bearing to the destination in degrees", xmp = Exif),
+    (0x19, GPSDestDistanceRef, IfdId::Gps, [Ascii], 2, "Unit of the distance to the destination", xmp = Exif),
+    (0x1A, GPSDestDistance, IfdId::Gps, [Rational], 1, "Distance to the destination", xmp = Exif),
+    (0x1B, GPSProcessingMethod, IfdId::Gps, [Undefined], Any, "Name of the method used for location finding", xmp = Exif),
+    (0x1C, GPSAreaInformation, IfdId::Gps, [Undefined], Any, "Name of the GPS area", xmp = Exif),
+    (0x1D, GPSDateStamp, IfdId::Gps, [Ascii], 11, "Date as UTC"),
+    (0x1E, GPSDifferential, IfdId::Gps, [Short], 1, "Differential correction applied", xmp = Exif),
+    (0x1F, GPSHPositioningError, IfdId::Gps, [Rational], 1, "Horizontal positioning error in meters", xmp = Exif),
 
     // Primary/Thumbnail
-    (0x100, ImageWidth, IfdId::Primary, xmp = Exif),
-    (0x100, ThumbnailImageWidth, IfdId::Thumbnail),
-    (0x101, ImageHeight, IfdId::Primary, xmp = Exif),
-    (0x102, BitsPerSample, IfdId::Primary, xmp = Tiff),
-    (0x103, Compression, IfdId::Primary, xmp = Tiff),
-    (0x106, PhotometricInterpretation, IfdId::Primary, xmp = Tiff),
-    (0x10A, FillOrder, IfdId::Primary),
-    (0x10E, ImageDescription, IfdId::Primary, xmp = Dc),
-    (0x10F, Make, IfdId::Primary, xmp = Tiff),
-    (0x110, Model, IfdId::Primary, xmp = Tiff),
-    (0x111, StripOffsets, IfdId::Primary),
-    /// Image orientation and mirroring
-    (0x112, Orientation, IfdId::Primary, xmp = Tiff),
-    (0x112, ThumbnailOrientation, IfdId::Thumbnail),
-    (0x115, SamplesPerPixel, IfdId::Primary, xmp = Tiff),
-    (0x116, RowsPerStrip, IfdId::Primary),
-    (0x117, StripByteCounts, IfdId::Primary),
-    (0x11A, XResolution, IfdId::Primary, xmp = Tiff),
-    (0x11B, YResolution, IfdId::Primary, xmp = Tiff),
-    (0x11C, PlanarConfiguration, IfdId::Primary, xmp = Tiff),
-    (0x11E, XPosition, IfdId::Primary),
-    (0x11F, YPosition, IfdId::Primary),
-    (0x128, ResolutionUnit, IfdId::Primary, xmp = Tiff),
-    (0x129, PageNumber, IfdId::Primary),
+    (0xFE, NewSubfileType, IfdId::Primary, [Long], 1, "Kind of data in the IFD"),
+    (0xFF, SubfileType, IfdId::Primary, [Short], 1, "Kind of data in the IFD (deprecated)"),
+    (0x100, ImageWidth, IfdId::Primary, [Short, Long], 1, "Number of columns of image data", xmp = Exif),
+    (0x100, ThumbnailImageWidth, IfdId::Thumbnail, [Short, Long], 1, "Number of columns of the thumbnail"),
+    (0x101, ImageHeight, IfdId::Primary, [Short, Long], 1, "Number of rows of image data", xmp = Exif),
+    (0x101, ThumbnailImageHeight, IfdId::Thumbnail, [Short, Long], 1, "Number of rows of the thumbnail"),
+    (0x102, BitsPerSample, IfdId::Primary, [Short], Any, "Number of bits per component", xmp = Tiff),
+    (0x103, Compression, IfdId::Primary, [Short], 1, "Compression scheme of the image data", xmp = Tiff),
+    (0x103, ThumbnailCompression, IfdId::Thumbnail, [Short], 1, "Compression scheme of the thumbnail"),
+    (0x106, PhotometricInterpretation, IfdId::Primary, [Short], 1, "Pixel composition", xmp = Tiff),
+    (0x107, Threshholding, IfdId::Primary, [Short], 1, "Conversion from gray to black and white"),
+    (0x108, CellWidth, IfdId::Primary, [Short], 1, "Width of the dithering or halftoning matrix"),
+    (0x109, CellLength, IfdId::Primary, [Short], 1, "Height of the dithering or halftoning matrix"),
+    (0x10A, FillOrder, IfdId::Primary, [Short], 1, "Logical order of bits within a byte"),
+    (0x10D, DocumentName, IfdId::Primary, [Ascii], Any, "Name of the document the image was scanned from"),
+    (0x10E, ImageDescription, IfdId::Primary, [Ascii, Utf8], Any, "Title of the image", xmp = Dc),
+    (0x10F, Make, IfdId::Primary, [Ascii, Utf8], Any, "Manufacturer of the camera", xmp = Tiff),
+    (0x110, Model, IfdId::Primary, [Ascii, Utf8], Any, "Model name of the camera", xmp = Tiff),
+    (0x111, StripOffsets, IfdId::Primary, [Short, Long], Any, "Offsets of the image data strips"),
+    (0x112, Orientation, IfdId::Primary, [Short], 1, "Image orientation and mirroring", xmp = Tiff),
+    (0x112, ThumbnailOrientation, IfdId::Thumbnail, [Short], 1, "Orientation and mirroring of the thumbnail"),
+    (0x115, SamplesPerPixel, IfdId::Primary, [Short], 1, "Number of components per pixel", xmp = Tiff),
+    (0x116, RowsPerStrip, IfdId::Primary, [Short, Long], 1, "Number of rows per strip"),
+    (0x117, StripByteCounts, IfdId::Primary, [Short, Long], Any, "Byte sizes of the image data strips"),
+    (0x118, MinSampleValue, IfdId::Primary, [Short], Any, "Minimum component value"),
+    (0x119, MaxSampleValue, IfdId::Primary, [Short], Any, "Maximum component value"),
+    (0x11A, XResolution, IfdId::Primary, [Rational], 1, "Horizontal resolution", xmp = Tiff),
+    (0x11B, YResolution, IfdId::Primary, [Rational], 1, "Vertical resolution", xmp = Tiff),
+    (0x11C, PlanarConfiguration, IfdId::Primary, [Short], 1, "Chunky or planar storage of components", xmp = Tiff),
+    (0x11D, PageName, IfdId::Primary, [Ascii], Any, "Name of the page the image was scanned from"),
+    (0x11E, XPosition, IfdId::Primary, [Rational], 1, "Horizontal offset of the image"),
+    (0x11F, YPosition, IfdId::Primary, [Rational], 1, "Vertical offset of the image"),
+    (0x120, FreeOffsets, IfdId::Primary, [Long], Any, "Offsets of unused bytes in the file"),
+    (0x121, FreeByteCounts, IfdId::Primary, [Long], Any, "Byte sizes of unused bytes in the file"),
+    (0x122, GrayResponseUnit, IfdId::Primary, [Short], 1, "Precision of the gray response curve"),
+    (0x123, GrayResponseCurve, IfdId::Primary, [Short], Any, "Optical density of each gray value"),
+    (0x124, T4Options, IfdId::Primary, [Long], 1, "Options for CCITT Group 3 compression"),
+    (0x125, T6Options, IfdId::Primary, [Long], 1, "Options for CCITT Group 4 compression"),
+    (0x128, ResolutionUnit, IfdId::Primary, [Short], 1, "Unit of the resolution", xmp = Tiff),
+    (0x129, PageNumber, IfdId::Primary, [Short], 2, "Page number and total number of pages"),
+    (0x12D, TransferFunction, IfdId::Primary, [Short], 768, "Transfer function of the image", xmp = Tiff),
     /// The XMP equivalent is [`CreatorTool`]
-    (0x131, Software, IfdId::Primary),
+    (0x131, Software, IfdId::Primary, [Ascii, Utf8], Any, "Software used to create the image"),
     /// The XMP equivalent is [`ModifyDate`]
-    (0x132, DateTime, IfdId::Primary),
+    (0x132, DateTime, IfdId::Primary, [Ascii], 20, "Date and time of the last file change"),
     /// The XMP equivalent is [`Creator`]
-    (0x13B, Artist, IfdId::Primary),
-    (0x13E, WhitePoint, IfdId::Primary, xmp = Tiff),
-    (0x13F, PrimaryChromaticities, IfdId::Primary, xmp = Tiff),
-    (0x213, YCbCrPositioning, IfdId::Primary),
-    (0x258, Xmp, IfdId::Primary),
+    (0x13B, Artist, IfdId::Primary, [Ascii, Utf8], Any, "Person who created the image"),
+    (0x13C, HostComputer, IfdId::Primary, [Ascii], Any, "Computer used to create the image"),
+    (0x13D, Predictor, IfdId::Primary, [Short], 1, "Predictor applied before compression"),
+    (0x13E, WhitePoint, IfdId::Primary, [Rational], 2, "Chromaticity of the white point", xmp = Tiff),
+    (0x13F, PrimaryChromaticities, IfdId::Primary, [Rational], 6, "Chromaticities of the primaries", xmp = Tiff),
+    (0x140, ColorMap, IfdId::Primary, [Short], Any, "Color map for palette color images"),
+    (0x142, TileWidth, IfdId::Primary, [Short, Long], 1, "Number of columns per tile"),
+    (0x143, TileLength, IfdId::Primary, [Short, Long], 1, "Number of rows per tile"),
+    (0x144, TileOffsets, IfdId::Primary, [Short, Long], Any, "Offsets of the image data tiles"),
+    (0x145, TileByteCounts, IfdId::Primary, [Short, Long], Any, "Byte sizes of the image data tiles"),
+    (0x14A, SubIFDs, IfdId::Primary, [Long, Ifd], Any, "Offsets of child IFDs"),
+    (0x14C, InkSet, IfdId::Primary, [Short], 1, "Set of inks used in a separated image"),
+    (0x14D, InkNames, IfdId::Primary, [Ascii], Any, "Names of the inks used in a separated image"),
+    (0x14E, NumberOfInks, IfdId::Primary, [Short], 1, "Number of inks used in a separated image"),
+    (0x150, DotRange, IfdId::Primary, [Byte, Short], Any, "Component values for 0% and 100% dot"),
+    (0x151, TargetPrinter, IfdId::Primary, [Ascii], Any, "Printing environment of a separated image"),
+    (0x152, ExtraSamples, IfdId::Primary, [Short], Any, "Meaning of extra components"),
+    (0x153, SampleFormat, IfdId::Primary, [Short], Any, "Interpretation of the samples"),
+    (0x154, SMinSampleValue, IfdId::Primary, [Byte, Short, Long, SByte, SShort, SLong, Float, Double], Any, "Minimum sample value"),
+    (0x155, SMaxSampleValue, IfdId::Primary, [Byte, Short, Long, SByte, SShort, SLong, Float, Double], Any, "Maximum sample value"),
+    (0x156, TransferRange, IfdId::Primary, [Short], 6, "Expansion of the transfer function range"),
+    (0x15B, JPEGTables, IfdId::Primary, [Undefined], Any, "Shared JPEG quantization and Huffman tables"),
+    (0x201, JPEGInterchangeFormat, IfdId::Thumbnail, [Long], 1, "Offset of the JPEG thumbnail"),
+    (0x202, JPEGInterchangeFormatLength, IfdId::Thumbnail, [Long], 1, "Byte size of the JPEG thumbnail"),
+    (0x211, YCbCrCoefficients, IfdId::Primary, [Rational], 3, "Matrix coefficients for the RGB to YCbCr transformation", xmp = Tiff),
+    (0x212, YCbCrSubSampling, IfdId::Primary, [Short], 2, "Subsampling ratio of chrominance components", xmp = Tiff),
+    (0x213, YCbCrPositioning, IfdId::Primary, [Short], 1, "Position of chrominance components relative to luminance", xmp = Tiff),
+    (0x214, ReferenceBlackWhite, IfdId::Primary, [Rational], 6, "Reference black and white point values", xmp = Tiff),
+    /// Also called XMLPacket
+    (0x2BC, Xmp, IfdId::Primary, [Byte, Undefined], Any, "Embedded XMP data"),
+    (0x828D, CFARepeatPatternDim, IfdId::Primary, [Short], 2, "Dimensions of the color filter array pattern"),
+    /// TIFF/EP variant of [`CFAPattern`]
+    (0x828E, CFAPattern2, IfdId::Primary, [Byte], Any, "Color filter array pattern"),
+    (0x828F, BatteryLevel, IfdId::Primary, [Rational, Ascii], Any, "Battery level"),
     /// The XMP equivalent is [`Rights`]
-    (0x8298, Copyright, IfdId::Primary),
-
+    (0x8298, Copyright, IfdId::Primary, [Ascii, Utf8], Any, "Copyright holder"),
+    (0x83BB, IptcNaa, IfdId::Primary, [Long, Undefined], Any, "Embedded IPTC data"),
+    (0x8773, InterColorProfile, IfdId::Primary, [Undefined], Any, "Embedded ICC profile"),
+    (0x8829, Interlace, IfdId::Primary, [Short], 1, "Field number of multifield images"),
+    (0x882A, TimeZoneOffset, IfdId::Primary, [SShort], Any, "Time zone offset of DateTimeOriginal and DateTime in hours"),
+    (0x882B, SelfTimerMode, IfdId::Primary, [Short], 1, "Self-timer delay in seconds"),
+    /// TIFF/EP variant of [`FlashEnergy`]
+    (0x920B, FlashEnergy2, IfdId::Primary, [Rational], Any, "Strobe energy in BCPS"),
+    /// TIFF/EP variant of [`SpatialFrequencyResponse`]
+    (0x920C, SpatialFrequencyResponse2, IfdId::Primary, [Undefined], Any, "Spatial frequency table and SFR values"),
+    (0x920D, Noise, IfdId::Primary, [Undefined], Any, "Noise measurement values"),
+    /// TIFF/EP variant of [`FocalPlaneXResolution`]
+    (0x920E, FocalPlaneXResolution2, IfdId::Primary, [Rational], 1, "Horizontal pixels per focal plane resolution unit"),
+    /// TIFF/EP variant of [`FocalPlaneYResolution`]
+    (0x920F, FocalPlaneYResolution2, IfdId::Primary, [Rational], 1, "Vertical pixels per focal plane resolution unit"),
+    /// TIFF/EP variant of [`FocalPlaneResolutionUnit`]
+    (0x9210, FocalPlaneResolutionUnit2, IfdId::Primary, [Short], 1, "Unit of the focal plane resolution"),
+    (0x9211, ImageNumber, IfdId::Primary, [Long], 1, "Number of the image in a sequence"),
+    (0x9212, SecurityClassification, IfdId::Primary, [Ascii], Any, "Security classification of the image"),
+    (0x9213, ImageHistory, IfdId::Primary, [Ascii], Any, "Record of changes to the image"),
+    /// TIFF/EP variant of [`ExposureIndex`]
+    (0x9215, ExposureIndex2, IfdId::Primary, [Rational], Any, "Exposure index selected on the camera"),
+    (0x9216, TIFFEPStandardID, IfdId::Primary, [Byte], 4, "Version of the TIFF/EP standard"),
+    /// TIFF/EP variant of [`SensingMethod`]
+    (0x9217, SensingMethod2, IfdId::Primary, [Short], 1, "Type of image sensor"),
 
     // Exif
-    (0x829A, ExposureTime, IfdId::Exif, xmp = Exif),
-    (0x829D, FNumber, IfdId::Exif, xmp = Exif),
+    (0x829A, ExposureTime, IfdId::Exif, [Rational], 1, "Exposure time in seconds", xmp = Exif),
+    (0x829D, FNumber, IfdId::Exif, [Rational], 1, "F number", xmp = Exif),
     /// Points to the start of the [`IfdId::Exif`] entries list
-    (0x8769,  ExifIFDPointer, IfdId::Primary),
-    (0x8822, ExposureProgram, IfdId::Exif, xmp = Exif),
+    (0x8769, ExifIFDPointer, IfdId::Primary, [Long], 1, "Offset of the Exif IFD"),
+    (0x8822, ExposureProgram, IfdId::Exif, [Short], 1, "Program used to set the exposure", xmp = Exif),
+    (0x8824, SpectralSensitivity, IfdId::Exif, [Ascii], Any, "Spectral sensitivity of each channel", xmp = Exif),
     /// Points to the start of the [`IfdId::Gps`] entries list
-    (0x8825, GPSInfoIFDPointer, IfdId::Primary),
+    (0x8825, GPSInfoIFDPointer, IfdId::Primary, [Long], 1, "Offset of the GPS IFD"),
     /// Also called ISOSpeedRatings (new xmp value since Exif 2.3 or later)
-    (0x8827, PhotographicSensitivity, IfdId::Exif, xmp = ExifEX),
-    (0x8830, SensitivityType, IfdId::Exif, xmp = ExifEX),
-    (0x8832, RecommendedExposureIndex, IfdId::Exif, xmp = ExifEX),
-    (0x9000, ExifVersion, IfdId::Exif, xmp = Exif),
-    (0x9003, DateTimeOriginal, IfdId::Exif, xmp = Exif),
-    (0x9004, DateTimeDigitized, IfdId::Exif, xmp = Exif),
-    (0x9010, OffsetTime, IfdId::Exif),
-    (0x9012, OffsetTimeDigitized, IfdId::Exif),
-    (0x9011, OffsetTimeOriginal, IfdId::Exif),
-    (0x9101, ComponentsConfiguration, IfdId::Exif, xmp = Exif),
-    (0x9201, ShutterSpeedValue, IfdId::Exif, xmp = Exif),
+    (0x8827, PhotographicSensitivity, IfdId::Exif, [Short], Any, "Sensitivity of the camera", xmp = ExifEX),
+    (0x8828, OECF, IfdId::Exif, [Undefined], Any, "Opto-electric conversion function", xmp = Exif),
+    (0x8830, SensitivityType, IfdId::Exif, [Short], 1, "Parameter used for PhotographicSensitivity", xmp = ExifEX),
+    (0x8831, StandardOutputSensitivity, IfdId::Exif, [Long], 1, "Standard output sensitivity", xmp = ExifEX),
+    (0x8832, RecommendedExposureIndex, IfdId::Exif, [Long], 1, "Recommended exposure index", xmp = ExifEX),
+    (0x8833, ISOSpeed, IfdId::Exif, [Long], 1, "ISO speed", xmp = ExifEX),
+    (0x8834, ISOSpeedLatitudeyyy, IfdId::Exif, [Long], 1, "ISO speed latitude yyy", xmp = ExifEX),
+    (0x8835, ISOSpeedLatitudezzz, IfdId::Exif, [Long], 1, "ISO speed latitude zzz", xmp = ExifEX),
+    (0x9000, ExifVersion, IfdId::Exif, [Undefined], 4, "Version of the Exif standard", xmp = Exif),
+    (0x9003, DateTimeOriginal, IfdId::Exif, [Ascii], 20, "Date and time the original image was generated", xmp = Exif),
+    (0x9004, DateTimeDigitized, IfdId::Exif, [Ascii], 20, "Date and time the image was stored as digital data", xmp = Exif),
+    (0x9010, OffsetTime, IfdId::Exif, [Ascii], 7, "Time zone offset of DateTime"),
+    (0x9011, OffsetTimeOriginal, IfdId::Exif, [Ascii], 7, "Time zone offset of DateTimeOriginal"),
+    (0x9012, OffsetTimeDigitized, IfdId::Exif, [Ascii], 7, "Time zone offset of DateTimeDigitized"),
+    (0x9101, ComponentsConfiguration, IfdId::Exif, [Undefined], 4, "Order of components", xmp = Exif),
+    (0x9102, CompressedBitsPerPixel, IfdId::Exif, [Rational], 1, "Compression mode in bits per pixel", xmp = Exif),
+    (0x9201, ShutterSpeedValue, IfdId::Exif, [SRational], 1, "Shutter speed in APEX", xmp = Exif),
     /// Lens aperture with unit APEX
-    (0x9202, Aperture, IfdId::Exif, xmp = Exif),
-    (0x9203, BrightnessValue, IfdId::Exif, xmp = Exif),
-    (0x9204, ExposureBiasValue, IfdId::Exif, xmp = Exif),
-    (0x9205, MaxApertureValue, IfdId::Exif, xmp = Exif),
-    (0x9206, SubjectDistance, IfdId::Exif, xmp = Exif),
-    (0x9207, MeteringMode, IfdId::Exif, xmp = Exif),
-    (0x9208, LightSource, IfdId::Exif, xmp = Exif),
-    (0x9209, Flash, IfdId::Exif, xmp = Exif),
-    (0x920A, FocalLength, IfdId::Exif, xmp = Exif),
-    (0x927C, MakerNote, IfdId::Exif),
-    (0x9286, UserComment, IfdId::Exif, xmp = Exif),
-    (0x9290, SubSecTime, IfdId::Exif),
-    (0x9291, SubSecTimeOriginal, IfdId::Exif),
-    (0x9292, SubsecTimeDigitized, IfdId::Exif, xmp = Exif),
-    (0xA000, FlashpixVersion, IfdId::Exif, xmp = Exif),
-    (0xA001, ColorSpace, IfdId::Exif, xmp = Exif),
-    (0xA002, PixelXDimension, IfdId::Exif, xmp = Exif),
-    (0xA003, PixelYDimension, IfdId::Exif, xmp = Exif),
-    (0xA005, InteroperabilityIfd, IfdId::Exif),
-    (0xA20E, FocalPlaneXResolution, IfdId::Exif, xmp = Exif),
-    (0xA20F, FocalPlaneYResolution, IfdId::Exif, xmp = Exif),
-    (0xA210, FocalPlaneResolutionUnit, IfdId::Exif, xmp = Exif),
+    (0x9202, Aperture, IfdId::Exif, [Rational], 1, "Lens aperture in APEX", xmp = Exif),
+    (0x9203, BrightnessValue, IfdId::Exif, [SRational], 1, "Brightness in APEX", xmp = Exif),
+    (0x9204, ExposureBiasValue, IfdId::Exif, [SRational], 1, "Exposure bias in APEX", xmp = Exif),
+    (0x9205, MaxApertureValue, IfdId::Exif, [Rational], 1, "Smallest F number of the lens in APEX", xmp = Exif),
+    (0x9206, SubjectDistance, IfdId::Exif, [Rational], 1, "Distance to the subject in meters", xmp = Exif),
+    (0x9207, MeteringMode, IfdId::Exif, [Short], 1, "Metering mode", xmp = Exif),
+    (0x9208, LightSource, IfdId::Exif, [Short], 1, "Kind of light source", xmp = Exif),
+    (0x9209, Flash, IfdId::Exif, [Short], 1, "Status of the flash", xmp = Exif),
+    (0x920A, FocalLength, IfdId::Exif, [Rational], 1, "Focal length of the lens in millimeters", xmp = Exif),
+    (0x9214, SubjectArea, IfdId::Exif, [Short], Any, "Location and area of the main subject", xmp = Exif),
+    (0x927C, MakerNote, IfdId::Exif, [Undefined], Any, "Manufacturer specific data"),
+    (0x9286, UserComment, IfdId::Exif, [Undefined], Any, "Comments by the user", xmp = Exif),
+    (0x9290, SubSecTime, IfdId::Exif, [Ascii], Any, "Fractions of seconds for DateTime"),
+    (0x9291, SubSecTimeOriginal, IfdId::Exif, [Ascii], Any, "Fractions of seconds for DateTimeOriginal"),
+    (0x9292, SubsecTimeDigitized, IfdId::Exif, [Ascii], Any, "Fractions of seconds for DateTimeDigitized", xmp = Exif),
+    (0x9400, Temperature, IfdId::Exif, [SRational], 1, "Ambient temperature in degrees Celsius", xmp = ExifEX),
+    (0x9401, Humidity, IfdId::Exif, [Rational], 1, "Ambient relative humidity in percent", xmp = ExifEX),
+    (0x9402, Pressure, IfdId::Exif, [Rational], 1, "Ambient air pressure in hPa", xmp = ExifEX),
+    (0x9403, WaterDepth, IfdId::Exif, [SRational], 1, "Water depth in meters", xmp = ExifEX),
+    (0x9404, Acceleration, IfdId::Exif, [Rational], 1, "Acceleration of the camera in mGal", xmp = ExifEX),
+    (0x9405, CameraElevationAngle, IfdId::Exif, [SRational], 1, "Elevation angle of the camera in degrees", xmp = ExifEX),
+    (0xA000, FlashpixVersion, IfdId::Exif, [Undefined], 4, "Supported Flashpix version", xmp = Exif),
+    (0xA001, ColorSpace, IfdId::Exif, [Short], 1, "Color space of the image data", xmp = Exif),
+    (0xA002, PixelXDimension, IfdId::Exif, [Short, Long], 1, "Width of the compressed image", xmp = Exif),
+    (0xA003, PixelYDimension, IfdId::Exif, [Short, Long], 1, "Height of the compressed image", xmp = Exif),
+    (0xA004, RelatedSoundFile, IfdId::Exif, [Ascii], 13, "Name of an audio file related to the image", xmp = Exif),
+    (0xA005, InteroperabilityIfd, IfdId::Exif, [Long], 1, "Offset of the interoperability IFD"),
+    (0xA20B, FlashEnergy, IfdId::Exif, [Rational], 1, "Strobe energy in BCPS", xmp = Exif),
+    (0xA20C, SpatialFrequencyResponse, IfdId::Exif, [Undefined], Any, "Spatial frequency table and SFR values", xmp = Exif),
+    (0xA20E, FocalPlaneXResolution, IfdId::Exif, [Rational], 1, "Horizontal pixels per focal plane resolution unit", xmp = Exif),
+    (0xA20F, FocalPlaneYResolution, IfdId::Exif, [Rational], 1, "Vertical pixels per focal plane resolution unit", xmp = Exif),
+    (0xA210, FocalPlaneResolutionUnit, IfdId::Exif, [Short], 1, "Unit of the focal plane resolution", xmp = Exif),
+    (0xA214, SubjectLocation, IfdId::Exif, [Short], 2, "Location of the main subject", xmp = Exif),
+    (0xA215, ExposureIndex, IfdId::Exif, [Rational], 1, "Exposure index selected on the camera", xmp = Exif),
+    (0xA217, SensingMethod, IfdId::Exif, [Short], 1, "Type of image sensor", xmp = Exif),
+    (0xA300, FileSource, IfdId::Exif, [Undefined], 1, "Source of the image", xmp = Exif),
+    (0xA301, SceneType, IfdId::Exif, [Undefined], 1, "Type of scene", xmp = Exif),
+    (0xA302, CFAPattern, IfdId::Exif, [Undefined], Any, "Color filter array pattern", xmp = Exif),
+    (0xA401, CustomRendered, IfdId::Exif, [Short], 1, "Use of special processing on the image data", xmp = Exif),
+    (0xA402, ExposureMode, IfdId::Exif, [Short], 1, "Exposure mode", xmp = Exif),
+    (0xA403, WhiteBalance, IfdId::Exif, [Short], 1, "White balance mode", xmp = Exif),
+    (0xA404, DigitalZoomRatio, IfdId::Exif, [Rational], 1, "Digital zoom ratio", xmp = Exif),
+    (0xA405, FocalLengthIn35mmFilm, IfdId::Exif, [Short], 1, "Equivalent focal length for 35 mm film", xmp = Exif),
+    (0xA406, SceneCaptureType, IfdId::Exif, [Short], 1, "Type of scene that was shot", xmp = Exif),
+    (0xA407, GainControl, IfdId::Exif, [Short], 1, "Degree of overall image gain adjustment", xmp = Exif),
+    (0xA408, Contrast, IfdId::Exif, [Short], 1, "Contrast processing applied by the camera", xmp = Exif),
+    (0xA409, Saturation, IfdId::Exif, [Short], 1, "Saturation processing applied by the camera", xmp = Exif),
+    (0xA40A, Sharpness, IfdId::Exif, [Short], 1, "Sharpness processing applied by the camera", xmp = Exif),
+    (0xA40B, DeviceSettingDescription, IfdId::Exif, [Undefined], Any, "Picture-taking conditions of the camera model", xmp = Exif),
+    (0xA40C, SubjectDistanceRange, IfdId::Exif, [Short], 1, "Distance to the subject", xmp = Exif),
+    (0xA420, ImageUniqueID, IfdId::Exif, [Ascii], 33, "Unique identifier of the image", xmp = Exif),
+    (0xA430, CameraOwnerName, IfdId::Exif, [Ascii, Utf8], Any, "Owner of the camera", xmp = ExifEX),
+    (0xA431, BodySerialNumber, IfdId::Exif, [Ascii], Any, "Serial number of the camera body", xmp = ExifEX),
+    (0xA432, LensSpecification, IfdId::Exif, [Rational], 4, "Focal length and F number ranges of the lens", xmp = ExifEX),
+    (0xA433, LensMake, IfdId::Exif, [Ascii, Utf8], Any, "Manufacturer of the lens", xmp = ExifEX),
+    (0xA434, LensModel, IfdId::Exif, [Ascii, Utf8], Any, "Model name of the lens", xmp = ExifEX),
+    (0xA435, LensSerialNumber, IfdId::Exif, [Ascii], Any, "Serial number of the lens", xmp = ExifEX),
+    (0xA436, ImageTitle, IfdId::Exif, [Ascii, Utf8], Any, "Title of the image"),
+    (0xA437, Photographer, IfdId::Exif, [Ascii, Utf8], Any, "Person who took the photo"),
+    (0xA438, ImageEditor, IfdId::Exif, [Ascii, Utf8], Any, "Person who edited the image"),
+    (0xA439, CameraFirmware, IfdId::Exif, [Ascii], Any, "Firmware of the camera"),
+    (0xA43A, RAWDevelopingSoftware, IfdId::Exif, [Ascii, Utf8], Any, "Software used to develop the RAW image"),
+    (0xA43B, ImageEditingSoftware, IfdId::Exif, [Ascii, Utf8], Any, "Software used to edit the image"),
+    (0xA43C, MetadataEditingSoftware, IfdId::Exif, [Ascii, Utf8], Any, "Software used to edit the metadata"),
+    (0xA460, CompositeImage, IfdId::Exif, [Short], 1, "Whether the image is a composite image", xmp = ExifEX),
+    (0xA461, SourceImageNumberOfCompositeImage, IfdId::Exif, [Short], 2, "Number of source images of the composite image", xmp = ExifEX),
+    (0xA462, SourceExposureTimesOfCompositeImage, IfdId::Exif, [Undefined], Any, "Exposure times of the source images of the composite image", xmp = ExifEX),
+    (0xA500, Gamma, IfdId::Exif, [Rational], 1, "Gamma coefficient", xmp = ExifEX),
 
-    (0xA217, SensingMethod, IfdId::Exif, xmp = Exif),
-    (0xA300, FileSource, IfdId::Exif, xmp = Exif),
+    // DNG
+    (0xC612, DNGVersion, IfdId::Primary, [Byte], 4, "Version of the DNG specification"),
+    (0xC613, DNGBackwardVersion, IfdId::Primary, [Byte], 4, "Oldest DNG version the file is compatible with"),
+    (0xC614, UniqueCameraModel, IfdId::Primary, [Ascii], Any, "Unique name of the camera model"),
+    (0xC615, LocalizedCameraModel, IfdId::Primary, [Ascii, Byte], Any, "Localized name of the camera model"),
+    (0xC616, CFAPlaneColor, IfdId::Primary, [Byte], Any, "Color filter array colors of the planes"),
+    (0xC617, CFALayout, IfdId::Primary, [Short], 1, "Spatial layout of the color filter array"),
+    (0xC618, LinearizationTable, IfdId::Primary, [Short], Any, "Lookup table to linearize the raw values"),
+    (0xC619, BlackLevelRepeatDim, IfdId::Primary, [Short], 2, "Dimensions of the black level pattern"),
+    (0xC61A, BlackLevel, IfdId::Primary, [Rational, Short, Long], Any, "Zero light encoding level"),
+    (0xC61B, BlackLevelDeltaH, IfdId::Primary, [SRational], Any, "Black level differences per column"),
+    (0xC61C, BlackLevelDeltaV, IfdId::Primary, [SRational], Any, "Black level differences per row"),
+    (0xC61D, WhiteLevel, IfdId::Primary, [Short, Long], Any, "Fully saturated encoding level"),
+    (0xC61E, DefaultScale, IfdId::Primary, [Rational], 2, "Default scale factors for square pixels"),
+    (0xC61F, DefaultCropOrigin, IfdId::Primary, [Short, Long, Rational], 2, "Origin of the final image area"),
+    (0xC620, DefaultCropSize, IfdId::Primary, [Short, Long, Rational], 2, "Size of the final image area"),
+    (0xC621, ColorMatrix1, IfdId::Primary, [SRational], Any, "Transformation from XYZ to camera color space for the first illuminant"),
+    (0xC622, ColorMatrix2, IfdId::Primary, [SRational], Any, "Transformation from XYZ to camera color space for the second illuminant"),
+    (0xC623, CameraCalibration1, IfdId::Primary, [SRational], Any, "Calibration matrix for the first illuminant"),
+    (0xC624, CameraCalibration2, IfdId::Primary, [SRational], Any, "Calibration matrix for the second illuminant"),
+    (0xC625, ReductionMatrix1, IfdId::Primary, [SRational], Any, "Dimensionality reduction matrix for the first illuminant"),
+    (0xC626, ReductionMatrix2, IfdId::Primary, [SRational], Any, "Dimensionality reduction matrix for the second illuminant"),
+    (0xC627, AnalogBalance, IfdId::Primary, [Rational], Any, "Gain applied to the stored raw values"),
+    (0xC628, AsShotNeutral, IfdId::Primary, [Rational, Short], Any, "Selected white balance as camera neutral coordinates"),
+    (0xC629, AsShotWhiteXY, IfdId::Primary, [Rational], 2, "Selected white balance as xy chromaticity coordinates"),
+    (0xC62A, BaselineExposure, IfdId::Primary, [SRational], 1, "Baseline exposure compensation in EV"),
+    (0xC62B, BaselineNoise, IfdId::Primary, [Rational], 1, "Relative noise level of the camera model"),
+    (0xC62C, BaselineSharpness, IfdId::Primary, [Rational], 1, "Relative sharpening of the camera model"),
+    (0xC62D, BayerGreenSplit, IfdId::Primary, [Long], 1, "Differences between the green pixels of a Bayer sensor"),
+    (0xC62E, LinearResponseLimit, IfdId::Primary, [Rational], 1, "Fraction of the encoding range with linear response"),
+    (0xC62F, CameraSerialNumber, IfdId::Primary, [Ascii], Any, "Serial number of the camera"),
+    (0xC630, LensInfo, IfdId::Primary, [Rational], 4, "Focal length and F number ranges of the lens"),
+    (0xC631, ChromaBlurRadius, IfdId::Primary, [Rational], 1, "Chroma blur radius for demosaicing"),
+    (0xC632, AntiAliasStrength, IfdId::Primary, [Rational], 1, "Strength of the anti-alias filter"),
+    (0xC633, ShadowScale, IfdId::Primary, [Rational], 1, "Scale for the shadows slider"),
+    (0xC634, DNGPrivateData, IfdId::Primary, [Byte], Any, "Private data of the DNG writer"),
+    (0xC635, MakerNoteSafety, IfdId::Primary, [Short], 1, "Whether the MakerNote is safe to preserve"),
+    (0xC65A, CalibrationIlluminant1, IfdId::Primary, [Short], 1, "First illuminant for calibration"),
+    (0xC65B, CalibrationIlluminant2, IfdId::Primary, [Short], 1, "Second illuminant for calibration"),
+    (0xC65C, BestQualityScale, IfdId::Primary, [Rational], 1, "Additional scale factor for best quality"),
+    (0xC65D, RawDataUniqueID, IfdId::Primary, [Byte], 16, "Unique identifier of the raw image data"),
+    (0xC68B, OriginalRawFileName, IfdId::Primary, [Ascii, Byte], Any, "File name of the original raw file"),
+    (0xC68C, OriginalRawFileData, IfdId::Primary, [Undefined], Any, "Contents of the original raw file"),
+    (0xC68D, ActiveArea, IfdId::Primary, [Short, Long], 4, "Area of the sensor with valid image data"),
+    (0xC68E, MaskedAreas, IfdId::Primary, [Short, Long], Any, "Masked areas of the sensor"),
+    (0xC68F, AsShotICCProfile, IfdId::Primary, [Undefined], Any, "ICC profile for the as-shot rendering"),
+    (0xC690, AsShotPreProfileMatrix, IfdId::Primary, [SRational], Any, "Matrix applied before AsShotICCProfile"),
+    (0xC691, CurrentICCProfile, IfdId::Primary, [Undefined], Any, "ICC profile for the current rendering"),
+    (0xC692, CurrentPreProfileMatrix, IfdId::Primary, [SRational], Any, "Matrix applied before CurrentICCProfile"),
+    (0xC6BF, ColorimetricReference, IfdId::Primary, [Short], 1, "Colorimetric reference of the rendered image"),
+    (0xC6F3, CameraCalibrationSignature, IfdId::Primary, [Ascii, Byte], Any, "Signature of the camera calibration matrices"),
+    (0xC6F4, ProfileCalibrationSignature, IfdId::Primary, [Ascii, Byte], Any, "Signature of the camera profile calibration"),
+    (0xC6F5, ExtraCameraProfiles, IfdId::Primary, [Long, Ifd], Any, "Offsets of additional camera profile IFDs"),
+    (0xC6F6, AsShotProfileName, IfdId::Primary, [Ascii, Byte], Any, "Name of the camera profile for the as-shot rendering"),
+    (0xC6F7, NoiseReductionApplied, IfdId::Primary, [Rational], 1, "Amount of noise reduction applied to the raw data"),
+    (0xC6F8, ProfileName, IfdId::Primary, [Ascii, Byte], Any, "Name of the camera profile"),
+    (0xC6F9, ProfileHueSatMapDims, IfdId::Primary, [Long], 3, "Dimensions of the hue/saturation/value mapping tables"),
+    (0xC6FA, ProfileHueSatMapData1, IfdId::Primary, [Float], Any, "Hue/saturation/value mapping for the first illuminant"),
+    (0xC6FB, ProfileHueSatMapData2, IfdId::Primary, [Float], Any, "Hue/saturation/value mapping for the second illuminant"),
+    (0xC6FC, ProfileToneCurve, IfdId::Primary, [Float], Any, "Default tone curve of the camera profile"),
+    (0xC6FD, ProfileEmbedPolicy, IfdId::Primary, [Long], 1, "Usage restrictions of the camera profile"),
+    (0xC6FE, ProfileCopyright, IfdId::Primary, [Ascii, Byte], Any, "Copyright of the camera profile"),
+    (0xC714, ForwardMatrix1, IfdId::Primary, [SRational], Any, "Transformation from white balanced camera colors to XYZ for the first illuminant"),
+    (0xC715, ForwardMatrix2, IfdId::Primary, [SRational], Any, "Transformation from white balanced camera colors to XYZ for the second illuminant"),
+    (0xC716, PreviewApplicationName, IfdId::Primary, [Ascii, Byte], Any, "Name of the application that rendered the preview"),
+    (0xC717, PreviewApplicationVersion, IfdId::Primary, [Ascii, Byte], Any, "Version of the application that rendered the preview"),
+    (0xC718, PreviewSettingsName, IfdId::Primary, [Ascii, Byte], Any, "Name of the settings used to render the preview"),
+    (0xC719, PreviewSettingsDigest, IfdId::Primary, [Byte], 16, "Digest of the settings used to render the preview"),
+    (0xC71A, PreviewColorSpace, IfdId::Primary, [Long], 1, "Color space of the preview"),
+    (0xC71B, PreviewDateTime, IfdId::Primary, [Ascii], Any, "Date and time the preview was rendered"),
+    (0xC71C, RawImageDigest, IfdId::Primary, [Byte], 16, "MD5 digest of the raw image data"),
+    (0xC71D, OriginalRawFileDigest, IfdId::Primary, [Byte], 16, "MD5 digest of the original raw file data"),
+    (0xC71E, SubTileBlockSize, IfdId::Primary, [Short, Long], 2, "Size of the blocks within a tile"),
+    (0xC71F, RowInterleaveFactor, IfdId::Primary, [Short, Long], 1, "Number of interleaved fields"),
+    (0xC725, ProfileLookTableDims, IfdId::Primary, [Long], 3, "Dimensions of the profile look table"),
+    (0xC726, ProfileLookTableData, IfdId::Primary, [Float], Any, "Hue/saturation/value mapping applied after the tone curve"),
+    (0xC740, OpcodeList1, IfdId::Primary, [Undefined], Any, "Opcodes applied to the raw image as read from the file"),
+    (0xC741, OpcodeList2, IfdId::Primary, [Undefined], Any, "Opcodes applied after mapping to linear reference values"),
+    (0xC74E, OpcodeList3, IfdId::Primary, [Undefined], Any, "Opcodes applied after demosaicing"),
+    (0xC761, NoiseProfile, IfdId::Primary, [Double], Any, "Noise model of the raw image data"),
+    (0xC763, TimeCodes, IfdId::Primary, [Byte], Any, "SMPTE time codes of a video frame"),
+    (0xC764, FrameRate, IfdId::Primary, [SRational], 1, "Frame rate of a video"),
+    (0xC772, TStop, IfdId::Primary, [SRational], Any, "T-stop of the lens"),
+    (0xC789, ReelName, IfdId::Primary, [Ascii], Any, "Name of the reel of a video"),
+    (0xC791, OriginalDefaultFinalSize, IfdId::Primary, [Short, Long], 2, "Default final size of the original raw image"),
+    (0xC792, OriginalBestQualityFinalSize, IfdId::Primary, [Short, Long], 2, "Best quality final size of the original raw image"),
+    (0xC793, OriginalDefaultCropSize, IfdId::Primary, [Short, Long, Rational], 2, "Default crop size of the original raw image"),
+    (0xC7A1, CameraLabel, IfdId::Primary, [Ascii], Any, "Label of the camera in a multi-camera setup"),
+    (0xC7A3, ProfileHueSatMapEncoding, IfdId::Primary, [Long], 1, "Encoding of the hue/saturation/value mapping tables"),
+    (0xC7A4, ProfileLookTableEncoding, IfdId::Primary, [Long], 1, "Encoding of the profile look table"),
+    (0xC7A5, BaselineExposureOffset, IfdId::Primary, [SRational], 1, "Offset to the baseline exposure of the camera profile"),
+    (0xC7A6, DefaultBlackRender, IfdId::Primary, [Long], 1, "Preferred rendering of black levels"),
+    (0xC7A7, NewRawImageDigest, IfdId::Primary, [Byte], 16, "MD5 digest of the raw image data, including floating point data"),
+    (0xC7A8, RawToPreviewGain, IfdId::Primary, [Double], 1, "Gain between the raw image data and the preview"),
+    (0xC7B5, DefaultUserCrop, IfdId::Primary, [Rational], 4, "Default crop in relative coordinates"),
+    (0xC7E9, DepthFormat, IfdId::Primary, [Short], 1, "Encoding of the depth map"),
+    (0xC7EA, DepthNear, IfdId::Primary, [Rational], 1, "Distance of the nearest depth value"),
+    (0xC7EB, DepthFar, IfdId::Primary, [Rational], 1, "Distance of the farthest depth value"),
+    (0xC7EC, DepthUnits, IfdId::Primary, [Short], 1, "Unit of the depth distances"),
+    (0xC7ED, DepthMeasureType, IfdId::Primary, [Short], 1, "Measurement geometry of the depth map"),
+    (0xC7EE, EnhanceParams, IfdId::Primary, [Ascii], Any, "Parameters of the enhancement applied to the image"),
 
-    (0xA301, SceneType, IfdId::Exif, xmp = Exif),
-    (0xA401, CustomRendered, IfdId::Exif, xmp = Exif),
-    (0xA402, ExposureMode, IfdId::Exif, xmp = Exif),
-    (0xA403, WhiteBalance, IfdId::Exif, xmp = Exif),
-    (0xA404, DigitalZoomRatio, IfdId::Exif, xmp = Exif),
-    (0xA405, FocalLengthIn35mmFilm, IfdId::Exif, xmp = Exif),
-    (0xA406, SceneCaptureType, IfdId::Exif, xmp = Exif),
-    (0xA407, GainControl, IfdId::Exif, xmp = Exif),
-    (0xA408, Contrast, IfdId::Exif, xmp = Exif),
-    (0xA409, Saturation, IfdId::Exif, xmp = Exif),
-    (0xA40A, Sharpness, IfdId::Exif, xmp = Exif),
-    (0xA40C, SubjectDistanceRange, IfdId::Exif),
-    (0xA420, ImageUniqueID, IfdId::Exif, xmp = Exif),
-    (0xA430, CameraOwnerName, IfdId::Exif, xmp = ExifEX),
-    (0xA431, BodySerialNumber, IfdId::Exif, xmp = ExifEX),
-    (0xA432, LensSpecification, IfdId::Exif, xmp = ExifEX),
-    (0xA433, LensMake, IfdId::Exif, xmp = ExifEX),
-    (0xA434, LensModel, IfdId::Exif, xmp = ExifEX),
-    (0xA435, LensSerialNumber, IfdId::Exif, xmp = ExifEX),
+    // Apple
+    (0x1, AppleMakerNoteVersion, IfdId::MakerNote(Vendor::Apple), [SLong], 1, "Version of the MakerNote"),
+    (0x8, AppleAccelerationVector, IfdId::MakerNote(Vendor::Apple), [SRational], 3, "Acceleration in units of g along the X, Y, and Z axes of the phone"),
+    (0xA, AppleHDRImageType, IfdId::MakerNote(Vendor::Apple), [SLong], 1, "Type of HDR image"),
+    (0xB, AppleBurstUUID, IfdId::MakerNote(Vendor::Apple), [Ascii], Any, "Identifier shared by all photos of a burst"),
+    (0x11, AppleContentIdentifier, IfdId::MakerNote(Vendor::Apple), [Ascii], Any, "Identifier that links a photo to the video of its Live Photo"),
+    (0x15, AppleImageUniqueID, IfdId::MakerNote(Vendor::Apple), [Ascii], Any, "Unique identifier of the image"),
+    (0x21, AppleHDRHeadroom, IfdId::MakerNote(Vendor::Apple), [SRational], 1, "HDR headroom"),
+    (0x30, AppleHDRGain, IfdId::MakerNote(Vendor::Apple), [SRational], 1, "HDR gain"),
 
     // Canon
-    (0x7, CanonFirmwareVersion, IfdId::MakerNote),
-    (0x9, CanonCameraOwnerName, IfdId::MakerNote),
-    (0x95, CanonLensModel, IfdId::MakerNote),
+    (0x1, CanonCameraSettings, IfdId::MakerNote(Vendor::Canon), [Short], Any, "Array of camera settings like focus mode and lens data"),
+    (0x2, CanonFocalLength, IfdId::MakerNote(Vendor::Canon), [Short], 4, "Focal length and focal plane size"),
+    (0x4, CanonShotInfo, IfdId::MakerNote(Vendor::Canon), [Short], Any, "Array of values describing the exposure of the shot"),
+    (0x6, CanonImageType, IfdId::MakerNote(Vendor::Canon), [Ascii], Any, "Type of the image"),
+    (0x7, CanonFirmwareVersion, IfdId::MakerNote(Vendor::Canon), [Ascii], Any, "Firmware version of the camera"),
+    (0x8, CanonFileNumber, IfdId::MakerNote(Vendor::Canon), [Long], 1, "File number"),
+    (0x9, CanonCameraOwnerName, IfdId::MakerNote(Vendor::Canon), [Ascii], Any, "Owner of the camera"),
+    (0xC, CanonSerialNumber, IfdId::MakerNote(Vendor::Canon), [Long], 1, "Serial number of the camera body"),
+    (0x10, CanonModelID, IfdId::MakerNote(Vendor::Canon), [Long], 1, "Identifier of the camera model"),
+    (0x93, CanonFileInfo, IfdId::MakerNote(Vendor::Canon), [Short], Any, "Array of values like the file number or shutter count"),
+    (0x95, CanonLensModel, IfdId::MakerNote(Vendor::Canon), [Ascii], Any, "Model name of the lens"),
+    (0x96, CanonInternalSerialNumber, IfdId::MakerNote(Vendor::Canon), [Ascii], Any, "Internal serial number of the camera"),
+
+    // Nikon
+    (0x1, NikonMakerNoteVersion, IfdId::MakerNote(Vendor::Nikon), [Undefined], 4, "Version of the MakerNote"),
+    (0x2, NikonISO, IfdId::MakerNote(Vendor::Nikon), [Short], 2, "ISO speed"),
+    (0x4, NikonQuality, IfdId::MakerNote(Vendor::Nikon), [Ascii], Any, "Image quality"),
+    (0x5, NikonWhiteBalance, IfdId::MakerNote(Vendor::Nikon), [Ascii], Any, "White balance mode"),
+    (0x7, NikonFocusMode, IfdId::MakerNote(Vendor::Nikon), [Ascii], Any, "Focus mode"),
+    (0x1D, NikonSerialNumber, IfdId::MakerNote(Vendor::Nikon), [Ascii], Any, "Serial number of the camera body"),
+    (0x83, NikonLensType, IfdId::MakerNote(Vendor::Nikon), [Byte], 1, "Flags describing the lens features"),
+    /// Like Exif's [`LensSpecification`]
+    (0x84, NikonLens, IfdId::MakerNote(Vendor::Nikon), [Rational], 4, "Focal length range and largest apertures"),
+    (0x98, NikonLensData, IfdId::MakerNote(Vendor::Nikon), [Undefined], Any, "Encrypted lens data for newer models"),
+    (0xA7, NikonShutterCount, IfdId::MakerNote(Vendor::Nikon), [Long], 1, "Number of shutter releases"),
 ];
 
 macros::make_xmp_tags![
@@ -162,3 +412,7 @@ macros::make_xmp_tags![(Rights, "rights", Dc)];
 macros::make_xmp_tags![(CreatorTool, Xmp)];
 macros::make_xmp_tags![(ModifyDate, Xmp)];
 macros::make_xmp_tags![(WebStatement, XmpRights)];
+
+// Digests of the Exif data at the time the XMP was synchronized
+macros::make_xmp_tags![(TiffNativeDigest, "NativeDigest", Tiff)];
+macros::make_xmp_tags![(ExifNativeDigest, "NativeDigest", Exif)];
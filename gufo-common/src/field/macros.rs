@@ -1,6 +1,8 @@
 macro_rules! make_tags {
-    ($($(#[$($attrss:tt)*])*($tag:literal, $id:ident, $ifd:expr $(,xmp = $xmp_ns:ident)?)),*$(,)?) => {
+    ($($(#[$($attrss:tt)*])*($tag:literal, $id:ident, $ifd:expr, [$($type_:ident),+], $count:tt, $description:literal $(,xmp = $xmp_ns:ident)?)),*$(,)?) => {
         $(
+            #[doc = $description]
+            #[doc = ""]
             $(#[$($attrss)*])*
             #[derive(Copy, Clone, Debug)]
             pub struct $id;
@@ -19,12 +21,40 @@ macro_rules! make_tags {
             )*
         )*
 
-        pub(crate) static TAG_NAMES: std::sync::LazyLock<std::collections::HashMap<(u16, IfdId), &'static str>> =
+        pub(crate) static TAGS: std::sync::LazyLock<std::collections::HashMap<(crate::exif::Tag, IfdId), crate::exif::TagInfo>> =
          std::sync::LazyLock::new(|| std::collections::HashMap::from([
             $(
-                (($tag, $ifd), stringify!($id)),
+                (
+                    (crate::exif::Tag($tag), $ifd),
+                    crate::exif::TagInfo {
+                        name: stringify!($id),
+                        tag: crate::exif::Tag($tag),
+                        ifd: $ifd,
+                        types: &[$(crate::exif::Type::$type_),+],
+                        count: macros::count!($count),
+                        description: $description,
+                    },
+                ),
             )*
         ]));
+
+        pub(crate) static XMP_NAMESPACES: std::sync::LazyLock<std::collections::HashMap<(crate::exif::Tag, IfdId), $crate::xmp::Namespace>> =
+         std::sync::LazyLock::new(|| std::collections::HashMap::from([
+            $(
+                $(
+                    ((crate::exif::Tag($tag), $ifd), $crate::xmp::Namespace::$xmp_ns),
+                )?
+            )*
+        ]));
+    };
+}
+
+macro_rules! count {
+    (Any) => {
+        crate::exif::Count::Any
+    };
+    ($count:literal) => {
+        crate::exif::Count::Fixed($count)
     };
 }
 
@@ -58,5 +88,6 @@ macro_rules! make_xmp_tags {
     };
 }
 
+pub(crate) use count;
 pub(crate) use make_tags;
 pub(crate) use make_xmp_tags;
@@ -1,3 +1,4 @@
+pub mod capture;
 pub mod cicp;
 #[cfg(feature = "chrono")]
 pub mod datetime;
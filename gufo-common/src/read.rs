@@ -1,4 +1,4 @@
-use std::io::{Cursor, Seek};
+use std::io::{Cursor, Read, Seek};
 use std::slice::SliceIndex;
 use std::sync::Arc;
 
@@ -98,6 +98,30 @@ impl<'a> SliceExt<'a> for Cursor<&'a [u8]> {
     }
 }
 
+/// Append exactly `len` bytes from `reader` to `buf`
+///
+/// In contrast to [`Read::read_exact`], the memory
+/// is not allocated in advance. Therefore, lengths that exceed the data don't
+/// lead to huge allocations.
+///
+/// ```
+/// # use gufo_common::read::append_exact;
+/// let mut buf = b"abc".to_vec();
+/// append_exact(&mut b"defgh".as_slice(), &mut buf, 3).unwrap();
+/// assert_eq!(buf, b"abcdef");
+///
+/// assert!(append_exact(&mut b"defgh".as_slice(), &mut buf, u64::MAX).is_err());
+/// ```
+pub fn append_exact(reader: &mut impl Read, buf: &mut Vec<u8>, len: u64) -> std::io::Result<()> {
+    let read = reader.take(len).read_to_end(buf)?;
+
+    if u64::try_from(read).ok() == Some(len) {
+        Ok(())
+    } else {
+        Err(std::io::ErrorKind::UnexpectedEof.into())
+    }
+}
+
 #[derive(Debug, thiserror::Error, Clone)]
 pub enum ReadError {
     #[error("Math: {0}")]
@@ -23,12 +23,40 @@ impl Rational<u32> {
     pub fn as_f64(&self) -> f64 {
         self.numerator as f64 / self.denominator as f64
     }
+
+    /// Approximate a value with a fixed denominator
+    ///
+    /// ```
+    /// # use gufo_common::types::Rational;
+    /// assert_eq!(
+    ///     Rational::from_f64(6.704, 100),
+    ///     Some(Rational::new(670, 100))
+    /// );
+    /// assert_eq!(Rational::from_f64(-1., 1), None);
+    /// ```
+    ///
+    /// Returns `None` if the value is negative or too large.
+    pub fn from_f64(value: f64, denominator: u32) -> Option<Self> {
+        let numerator = (value * f64::from(denominator)).round();
+
+        if !(0. ..=f64::from(u32::MAX)).contains(&numerator) {
+            return None;
+        }
+
+        // Range is checked above
+        #[allow(clippy::cast_possible_truncation)]
+        Some(Self::new(numerator as u32, denominator))
+    }
 }
 
 impl Rational<i32> {
     pub fn as_f32(&self) -> f32 {
         self.numerator as f32 / self.denominator as f32
     }
+
+    pub fn as_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
 }
 
 impl<T: Display> Rational<T> {
@@ -51,6 +51,20 @@ impl Namespace {
         }
     }
 
+    /// Commonly used prefix for the namespace
+    pub fn prefix(&self) -> Option<&'static str> {
+        match self {
+            Namespace::Tiff => Some("tiff"),
+            Namespace::Exif => Some("exif"),
+            Namespace::ExifEX => Some("exifEX"),
+            Namespace::Xmp => Some("xmp"),
+            Namespace::XmpRights => Some("xmpRights"),
+            Namespace::Ps => Some("photoshop"),
+            Namespace::Dc => Some("dc"),
+            Namespace::Unknown(_) => None,
+        }
+    }
+
     pub fn to_url(&self) -> &str {
         match self {
             Namespace::Tiff => XML_NS_TIFF,
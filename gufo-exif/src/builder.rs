@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use gufo_common::exif::{IfdId, TagIfd};
+
+use crate::Error;
+use crate::structure::tree::{Tree, TreeValue};
+use crate::structure::{Endieness, Typed};
+
+/// Create new Exif data
+///
+/// The IFDs are created as needed, including the pointers to the Exif, GPS,
+/// and interoperability IFDs. Entries for the thumbnail IFD put it behind the
+/// primary IFD.
+///
+/// The generated data start with the TIFF header. They can be used as is for
+/// PNG's `eXIf` chunk and WebP's `EXIF` chunk. JPEG's APP1 segment requires
+/// the `Exif\0\0` identifier in front of them.
+///
+/// ```
+/// use gufo_common::field;
+/// use gufo_exif::structure::Endieness;
+/// use gufo_exif::{ExifBuilder, Typed};
+///
+/// let raw_exif = ExifBuilder::new(Endieness::Big)
+///     .entry(field::Make, Typed::Ascii(b"Gufo".to_vec()))
+///     .entry(field::ExposureProgram, Typed::Short(vec![2]))
+///     .build()
+///     .unwrap();
+///
+/// let exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+/// assert_eq!(exif.make().as_deref(), Some("Gufo"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExifBuilder {
+    endieness: Endieness,
+    big_tiff: bool,
+    entries: BTreeMap<TagIfd, Typed>,
+}
+
+impl ExifBuilder {
+    pub fn new(endieness: Endieness) -> Self {
+        Self {
+            endieness,
+            big_tiff: false,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Use the BigTIFF layout with 64 bit offsets
+    pub fn big_tiff(mut self, big_tiff: bool) -> Self {
+        self.big_tiff = big_tiff;
+        self
+    }
+
+    /// Set the value for an entry
+    ///
+    /// Accepts a [`TagIfd`] or any [`Field`](gufo_common::exif::Field), like
+    /// [`gufo_common::field::Make`]. Existing values are replaced.
+    pub fn entry(mut self, tag_ifd: impl Into<TagIfd>, value: Typed) -> Self {
+        self.entries.insert(tag_ifd.into(), value);
+        self
+    }
+
+    /// Generate the raw Exif data
+    ///
+    /// Pointers to IFDs and offsets to image data can't be set as entries
+    /// since they are only known when laying out the data. Entries in a
+    /// [`IfdId::MakerNote`] are not supported either.
+    pub fn build(&self) -> Result<Vec<u8>, Error> {
+        let mut tree = Tree::new(self.endieness, self.big_tiff);
+
+        for (tag_ifd, value) in &self.entries {
+            if matches!(tag_ifd.ifd, IfdId::MakerNote(_)) {
+                return Err(Error::other(format!(
+                    "Can't create entries in {:?}",
+                    tag_ifd.ifd
+                )));
+            }
+
+            if tag_ifd.tag.is_exif_specific_ifd() || tag_ifd.tag.is_data_offset() {
+                return Err(Error::other(format!(
+                    "Entry {tag_ifd:?} contains offsets and can't be set"
+                )));
+            }
+
+            tree.ifds
+                .entry(tag_ifd.ifd)
+                .or_default()
+                .entries
+                .insert(tag_ifd.tag, TreeValue::from_typed(value, self.endieness)?);
+        }
+
+        tree.link_ifds();
+
+        tree.serialize()
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+use gufo_common::exif::{IfdId, Tag, TagIfd, Type};
+
+use crate::structure::util::Endieness;
+use crate::structure::{Bytes, Document, Typed};
+use crate::{Error, Exif, Storage};
+
+/// Compare two Exif structures entry by entry
+///
+/// ```
+/// # let data = std::fs::read("example.jpg").unwrap();
+/// # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+/// # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+/// use gufo_common::field;
+/// use gufo_exif::{EntryChange, Typed};
+///
+/// let old = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+/// let mut new = old.clone();
+/// new.update_entry(field::Artist.into(), Typed::Ascii(b"Gufo".to_vec()))
+///     .unwrap();
+///
+/// let diff = gufo_exif::diff(&old, &new).unwrap();
+/// assert!(matches!(
+///     diff.entry(field::Artist.into()),
+///     Some(EntryChange::Added(_))
+/// ));
+/// ```
+///
+/// Values of entries that point to IFDs are not compared since they depend on
+/// the layout. Changed positions of IFDs are reported as
+/// [`StructureChange::IfdMoved`] instead. For entries that point to image data,
+/// the referenced data are compared instead of the offsets.
+pub fn diff<'a, 'b, S: Storage<'a>, T: Storage<'b>>(
+    old: &Exif<'a, S>,
+    new: &Exif<'b, T>,
+) -> Result<ExifDiff, Error> {
+    let old = old.read_document(Snapshot::new)?;
+    let new = new.read_document(Snapshot::new)?;
+
+    let mut structure = Vec::new();
+
+    if old.endieness != new.endieness {
+        structure.push(StructureChange::Endieness {
+            old: old.endieness,
+            new: new.endieness,
+        });
+    }
+
+    let ifds = old
+        .ifds
+        .keys()
+        .chain(new.ifds.keys())
+        .collect::<BTreeSet<_>>();
+    for ifd in ifds {
+        match (old.ifds.get(ifd), new.ifds.get(ifd)) {
+            (Some(_), None) => structure.push(StructureChange::IfdRemoved(*ifd)),
+            (None, Some(_)) => structure.push(StructureChange::IfdAdded(*ifd)),
+            (Some(old_offset), Some(new_offset)) if old_offset != new_offset => {
+                structure.push(StructureChange::IfdMoved {
+                    ifd: *ifd,
+                    old_offset: *old_offset,
+                    new_offset: *new_offset,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let mut entries = Vec::new();
+
+    let tag_ifds = old
+        .entries
+        .keys()
+        .chain(new.entries.keys())
+        .collect::<BTreeSet<_>>();
+    for tag_ifd in tag_ifds {
+        let change = match (old.entries.get(tag_ifd), new.entries.get(tag_ifd)) {
+            (Some(old), None) => EntryChange::Removed(old.clone()),
+            (None, Some(new)) => EntryChange::Added(new.clone()),
+            (Some(old), Some(new)) if old.differs(new) => EntryChange::Changed {
+                old: old.clone(),
+                new: new.clone(),
+            },
+            _ => continue,
+        };
+
+        entries.push(EntryDiff {
+            tag_ifd: *tag_ifd,
+            name: gufo_common::exif::lookup_tag_name(*tag_ifd),
+            change,
+        });
+    }
+
+    Ok(ExifDiff { entries, structure })
+}
+
+/// Differences between two Exif structures
+///
+/// Returned by [`diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExifDiff {
+    /// Added, removed, and changed entries, sorted by IFD and tag
+    pub entries: Vec<EntryDiff>,
+    pub structure: Vec<StructureChange>,
+}
+
+impl ExifDiff {
+    /// Returns `true` if both structures are identical
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.structure.is_empty()
+    }
+
+    /// Change of a specific entry
+    pub fn entry(&self, tag_ifd: TagIfd) -> Option<&EntryChange> {
+        self.entries
+            .iter()
+            .find(|x| x.tag_ifd == tag_ifd)
+            .map(|x| &x.change)
+    }
+
+    /// Entries of `ifd` that differ
+    pub fn ifd(&self, ifd: IfdId) -> impl Iterator<Item = &EntryDiff> {
+        self.entries.iter().filter(move |x| x.tag_ifd.ifd == ifd)
+    }
+}
+
+impl Display for ExifDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.structure {
+            writeln!(f, "{change}")?;
+        }
+
+        for entry in &self.entries {
+            writeln!(f, "{entry}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Entry that differs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryDiff {
+    pub tag_ifd: TagIfd,
+    /// Name of the tag if it is known
+    pub name: Option<&'static str>,
+    pub change: EntryChange,
+}
+
+impl Display for EntryDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} ", self.tag_ifd.ifd)?;
+
+        match self.name {
+            Some(name) => f.write_str(name)?,
+            None => write!(f, "Tag {:#06x}", self.tag_ifd.tag.0)?,
+        }
+
+        match &self.change {
+            EntryChange::Added(new) => write!(f, ": added {new}"),
+            EntryChange::Removed(old) => write!(f, ": removed {old}"),
+            EntryChange::Changed { old, new } => write!(f, ": changed from {old} to {new}"),
+        }
+    }
+}
+
+/// How an entry differs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryChange {
+    Added(EntryValue),
+    Removed(EntryValue),
+    /// Type or value changed
+    Changed {
+        old: EntryValue,
+        new: EntryValue,
+    },
+}
+
+impl EntryChange {
+    /// Returns `true` if the entry exists in both structures with different
+    /// types
+    pub fn is_type_change(&self) -> bool {
+        matches!(self, Self::Changed { old, new } if old.type_ != new.type_)
+    }
+}
+
+/// Type and content of an entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryValue {
+    pub type_: Type,
+    pub count: usize,
+    pub content: EntryContent,
+}
+
+impl EntryValue {
+    /// Values imply the count. For pointers to IFDs, the count can depend on
+    /// the layout, like for MakerNotes.
+    ///
+    /// Entries that can't be read are always reported as changed since it is
+    /// unknown whether their values differ.
+    fn differs(&self, other: &Self) -> bool {
+        self.type_ != other.type_
+            || self.content != other.content
+            || matches!(self.content, EntryContent::Unreadable(_))
+            || matches!(other.content, EntryContent::Unreadable(_))
+    }
+}
+
+impl Display for EntryValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.content {
+            EntryContent::Value(value) => write!(f, "{:?} {}", self.type_, value.display()),
+            EntryContent::ReferencedData(chunks) => write!(
+                f,
+                "{} bytes of data in {} chunks",
+                chunks.iter().map(Vec::len).sum::<usize>(),
+                chunks.len()
+            ),
+            EntryContent::IfdPointer => {
+                write!(f, "{:?} with count {}", self.type_, self.count)
+            }
+            EntryContent::Unreadable(err) => write!(f, "unreadable {:?} ({err})", self.type_),
+        }
+    }
+}
+
+/// What is compared for an entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryContent {
+    Value(Typed),
+    /// Data the offsets of the entry point to, like strips or a JPEG
+    /// thumbnail
+    ReferencedData(Vec<Vec<u8>>),
+    /// Pointer to an IFD whose value depends on the layout
+    ///
+    /// The IFD's entries are compared instead.
+    IfdPointer,
+    /// The value or the referenced data can't be read
+    Unreadable(String),
+}
+
+/// Change in the layout of the structure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureChange {
+    /// Byte order changed
+    Endieness {
+        old: Endieness,
+        new: Endieness,
+    },
+    IfdAdded(IfdId),
+    IfdRemoved(IfdId),
+    /// IFD is stored at a different position
+    IfdMoved {
+        ifd: IfdId,
+        old_offset: usize,
+        new_offset: usize,
+    },
+}
+
+impl Display for StructureChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Endieness { old, new } => write!(f, "Byte order changed from {old:?} to {new:?}"),
+            Self::IfdAdded(ifd) => write!(f, "{ifd:?} IFD added"),
+            Self::IfdRemoved(ifd) => write!(f, "{ifd:?} IFD removed"),
+            Self::IfdMoved {
+                ifd,
+                old_offset,
+                new_offset,
+            } => write!(f, "{ifd:?} IFD moved from {old_offset} to {new_offset}"),
+        }
+    }
+}
+
+/// Entries and IFD positions of one document
+struct Snapshot {
+    endieness: Endieness,
+    ifds: BTreeMap<IfdId, usize>,
+    entries: BTreeMap<TagIfd, EntryValue>,
+}
+
+impl Snapshot {
+    fn new<'a, B: Bytes<'a>>(document: &mut Document<'a, B>) -> Result<Self, Error> {
+        let mut entries = BTreeMap::new();
+
+        for entry in document
+            .entries()?
+            .into_values()
+            .flat_map(|x| x.into_values())
+        {
+            let tag_ifd = entry.tag_ifd;
+            let content = if is_ifd_pointer(document, tag_ifd) {
+                EntryContent::IfdPointer
+            } else if tag_ifd == TagIfd::new(Tag::MAKER_NOTE, IfdId::Exif)
+                && let Some(data) = document.embedded_maker_note_data()?
+            {
+                // Stored apart from the other data, see `Document::tree`
+                EntryContent::Value(Typed::Undefined(data))
+            } else if let Some(length_tag) = data_length_tag(tag_ifd) {
+                match document.image_data(tag_ifd, length_tag) {
+                    Ok(chunks) => EntryContent::ReferencedData(chunks),
+                    Err(err) => EntryContent::Unreadable(err.to_string()),
+                }
+            } else {
+                match entry.data {
+                    Ok(value) => EntryContent::Value(value),
+                    Err(err) => EntryContent::Unreadable(err.to_string()),
+                }
+            };
+
+            entries.insert(
+                tag_ifd,
+                EntryValue {
+                    type_: entry.type_,
+                    count: entry.count,
+                    content,
+                },
+            );
+        }
+
+        Ok(Self {
+            endieness: document.endieness(),
+            ifds: document
+                .ifds()
+                .iter()
+                .map(|(ifd_id, (offset, _))| (*ifd_id, *offset))
+                .collect(),
+            entries,
+        })
+    }
+}
+
+/// Returns `true` for entries that contain offsets of IFDs
+fn is_ifd_pointer<'a, B: Bytes<'a>>(document: &Document<'a, B>, tag_ifd: TagIfd) -> bool {
+    let tag = tag_ifd.tag;
+
+    document.sub_ifd(tag_ifd).is_some()
+        || (!matches!(tag_ifd.ifd, IfdId::MakerNote(_))
+            && (tag.is_exif_specific_ifd() || tag == Tag::SUB_IFDS))
+}
+
+/// Tag with the lengths for entries that contain offsets of image data
+fn data_length_tag(tag_ifd: TagIfd) -> Option<Tag> {
+    if matches!(tag_ifd.ifd, IfdId::MakerNote(_)) {
+        return None;
+    }
+
+    tag_ifd.tag.data_length_tag()
+}
@@ -3,12 +3,13 @@ use std::fmt::Display;
 use std::num::TryFromIntError;
 use std::ops::Range;
 
+use gufo_common::exif::IfdId;
 use gufo_common::math::MathError;
 use zerocopy::ConvertError;
 
 use crate::structure::Type;
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum Error {
     #[error("TryFromSlice")]
     TryFromSlice,
@@ -32,6 +33,20 @@ pub enum Error {
     InputDataWrongLength(usize, usize),
     #[error("WouldIncreaseDataStore")]
     WouldIncreaseDataStore,
+    #[error("StorageNotResizable")]
+    StorageNotResizable,
+    #[error("IfdNotFound({0:?})")]
+    IfdNotFound(IfdId),
+    #[error("MakerNoteNotResizable({0:?})")]
+    MakerNoteNotResizable(IfdId),
+    #[error("MakerNoteNotMovable({0:?})")]
+    MakerNoteNotMovable(IfdId),
+    #[error("MakerNoteByteOrder({0:?})")]
+    MakerNoteByteOrder(IfdId),
+    #[error("ImageDataByteOrder({0:?})")]
+    ImageDataByteOrder(IfdId),
+    #[error("UnknownTypeByteOrder({0:?})")]
+    UnknownTypeByteOrder(Type),
     #[error("MathError: {0}")]
     MathError(#[from] MathError),
     #[error("Other: {0}")]
@@ -1,67 +1,148 @@
+#[cfg(feature = "chrono")]
+mod date_time;
+#[cfg(feature = "serde")]
+mod export;
+mod gps;
 mod high_level;
+mod maker_note;
+mod sanitize;
 
 use std::marker::PhantomData;
-use std::sync::Mutex;
-
-use gufo_common::exif::TagIfd;
+use std::sync::Arc;
+
+#[cfg(feature = "chrono")]
+pub use date_time::DateTimeField;
+#[cfg(feature = "serde")]
+pub use export::{EntryExport, ExifExport, IfdExport, ValueExport};
+use gufo_common::exif::{Field, IfdId, Tag, TagIfd};
+use gufo_common::field;
 use gufo_common::math::cheq;
+pub use sanitize::{SanitizeProfile, SanitizeReport};
 use zerocopy::FromZeros;
 
-use crate::Error;
-use crate::structure::{Document, Typed, ValueOrOffset};
+use crate::exif_ref::ExifIndex;
+use crate::structure::tree::{Tree, TreeValue};
+use crate::structure::{
+    Document, Finding, ParseDiagnostic, ParseMode, SerializeMode, Type, Typed, ValueOrOffset,
+};
+use crate::{Error, ExifRef};
+
+/// Value of the compression tag for JPEG compressed data
+const COMPRESSION_JPEG: u16 = 6;
 
 /// Exif file
+///
+/// Values are read from the stored data via an index of all IFDs and entries
+/// that is created while parsing. Therefore, reading takes `&self` and doesn't
+/// need to parse again, while all changes take `&mut self`.
 #[derive(Debug)]
 pub struct Exif<'a, S: Storage<'a>> {
-    document: S,
+    storage: S,
+    index: Arc<ExifIndex>,
+    /// Mode used again when the data are replaced
+    parse_mode: ParseMode,
+    mode: SerializeMode,
     lifetime: PhantomData<&'a ()>,
 }
 
+/// Cloning is cheap since the data are only copied once one of the clones
+/// changes
 impl<'a> Clone for Exif<'a, OwnedStore> {
     fn clone(&self) -> Self {
-        Self::for_vec(self.serialize().unwrap()).unwrap()
+        Self {
+            storage: self.storage.clone(),
+            index: self.index.clone(),
+            parse_mode: self.parse_mode,
+            mode: self.mode,
+            lifetime: PhantomData,
+        }
     }
 }
 
 /// Data type on which [`Exif`] can be based
 pub trait Storage<'a> {
-    fn access<T>(&self, f: impl FnOnce(&mut Document) -> T) -> T;
+    /// Raw Exif data
+    fn data(&self) -> &[u8];
+
+    /// Raw Exif data for changes that keep the size
+    fn data_mut(&mut self) -> &mut [u8];
+
+    /// Replace the stored data with new raw Exif data
+    ///
+    /// Returns [`Error::StorageNotResizable`] if the storage can't change its
+    /// size.
+    fn replace(&mut self, data: Vec<u8>) -> Result<(), Error>;
 }
 
-#[ouroboros::self_referencing]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OwnedStore {
-    data: Vec<u8>,
-    #[borrows(mut data)]
-    #[not_covariant]
-    document: Mutex<Document<'this>>,
+    /// Shared between clones until one of them changes
+    data: Arc<Vec<u8>>,
 }
 
 impl<'a> Storage<'a> for OwnedStore {
-    fn access<T>(&self, f: impl FnOnce(&mut Document) -> T) -> T {
-        self.with_document(|x| f(&mut x.lock().unwrap()))
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut [u8] {
+        Arc::make_mut(&mut self.data).as_mut_slice()
+    }
+
+    fn replace(&mut self, data: Vec<u8>) -> Result<(), Error> {
+        self.data = Arc::new(data);
+        Ok(())
     }
 }
 
+#[derive(Debug)]
 pub struct MutBorrowedStore<'a> {
-    document: Mutex<Document<'a>>,
+    data: &'a mut [u8],
 }
 
 impl<'a> Storage<'a> for MutBorrowedStore<'a> {
-    fn access<T>(&self, f: impl FnOnce(&mut Document) -> T) -> T {
-        f(&mut self.document.lock().unwrap())
+    fn data(&self) -> &[u8] {
+        self.data
+    }
+
+    fn data_mut(&mut self) -> &mut [u8] {
+        self.data
+    }
+
+    fn replace(&mut self, _data: Vec<u8>) -> Result<(), Error> {
+        Err(Error::StorageNotResizable)
     }
 }
 
 impl<'a> Exif<'a, OwnedStore> {
     /// Create from an owned vector
     pub fn for_vec(data: Vec<u8>) -> Result<Self, Error> {
-        Ok(Self {
-            document: OwnedStore::try_new(data, |x| {
-                Ok::<_, Error>(Mutex::new(Document::for_mut_slice(x)?))
-            })?,
-            lifetime: Default::default(),
-        })
+        Self::for_vec_with_mode(data, ParseMode::default())
+    }
+
+    /// Create from an owned vector with the given handling of unreadable IFDs
+    ///
+    /// With [`ParseMode::Lenient`], IFDs that can't be read are skipped and
+    /// reported via [`parse_diagnostics`](Self::parse_diagnostics).
+    ///
+    /// ```
+    /// # let data = std::fs::read("example.jpg").unwrap();
+    /// # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    /// # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+    /// use gufo_exif::structure::ParseMode;
+    ///
+    /// let exif = gufo_exif::Exif::for_vec_with_mode(raw_exif, ParseMode::Lenient).unwrap();
+    /// assert!(exif.parse_diagnostics().is_empty());
+    /// ```
+    pub fn for_vec_with_mode(mut data: Vec<u8>, mode: ParseMode) -> Result<Self, Error> {
+        let index = Self::index(&mut data, mode)?;
+        Ok(Self::new(
+            OwnedStore {
+                data: Arc::new(data),
+            },
+            index,
+            mode,
+        ))
     }
 }
 
@@ -69,15 +150,16 @@ impl<'a> Exif<'a, MutBorrowedStore<'a>> {
     /// Create for a mutable slice
     ///
     /// Functions that need to resize the underlying storage are not available
-    /// with this constructor. This includes the deletion of entries.
+    /// with this constructor. This includes the deletion of entries and
+    /// inserting new entries.
     pub fn for_mut_slice(data: &'a mut [u8]) -> Result<Self, Error> {
-        let document = Document::for_mut_slice(data)?;
-        Ok(Self {
-            document: MutBorrowedStore {
-                document: Mutex::new(document),
-            },
-            lifetime: PhantomData::<&'a ()>,
-        })
+        Self::for_mut_slice_with_mode(data, ParseMode::default())
+    }
+
+    /// Create for a mutable slice with the given handling of unreadable IFDs
+    pub fn for_mut_slice_with_mode(data: &'a mut [u8], mode: ParseMode) -> Result<Self, Error> {
+        let index = Self::index(data, mode)?;
+        Ok(Self::new(MutBorrowedStore { data }, index, mode))
     }
 }
 
@@ -85,8 +167,13 @@ impl<'a> Exif<'a, OwnedStore> {
     /// Delete entry
     ///
     /// The size of the raw exif data is not reduced. Deleted data is
-    /// overwritten with zeros instead.
+    /// overwritten with zeros instead. Use [`compact`](Self::compact) to
+    /// remove the deleted data.
+    ///
+    /// Entries of MakerNotes with their own TIFF structure can't be deleted.
     pub fn delete(&mut self, tag_ifd: TagIfd) -> Result<bool, Error> {
+        self.check_resizable(tag_ifd)?;
+
         let Some((pos_retain_begin, pos_retain_end, pos_retain_new, pos_obsolete_retain_start)) =
             self.document(|document| {
                 let entry_size = document.entry_size;
@@ -104,7 +191,9 @@ impl<'a> Exif<'a, OwnedStore> {
                 // Number at which the entry lives in the ifd entry list
                 let n_entry = entry_data.n_entry;
 
-                let (ifd_pos, ifd) = document.ifd_pos(tag_ifd.ifd).unwrap();
+                let (ifd_pos, ifd) = document
+                    .ifd_pos(tag_ifd.ifd)?
+                    .ok_or(Error::IfdNotFound(tag_ifd.ifd))?;
 
                 let ifd_n_entries = ifd.n_entries();
                 let ifd_n_entries_behind_deleted = (cheq(ifd_n_entries) - n_entry).check()?;
@@ -132,13 +221,13 @@ impl<'a> Exif<'a, OwnedStore> {
                     pos_retain_new,
                     pos_obsolete_retain_start,
                 )))
-            })?
+            })??
         else {
             return Ok(false);
         };
 
-        // Get a raw exif data to edit them
-        let mut raw = self.serialize()?;
+        // Get a raw exif data to edit them, with the positions from above
+        let mut raw = self.read_document(|x| x.serialize_with_mode(SerializeMode::InPlace))?;
 
         // Overwrite old list entry with remaining data
         raw.copy_within(pos_retain_begin..pos_retain_end, pos_retain_new);
@@ -150,28 +239,204 @@ impl<'a> Exif<'a, OwnedStore> {
             .zero();
 
         // Parse exif again from raw data
-        let exif = Exif::for_vec(raw)?;
-
-        // Replace internal parsed data with new data
-        self.document = exif.document;
+        self.replace(raw)?;
 
         Ok(true)
     }
+
+    /// Remove all data that are not referenced anymore
+    ///
+    /// Lays out the complete structure anew, removing unused space like data
+    /// left behind by [`delete`](Self::delete). All offsets are rewritten.
+    /// IFDs that have not been parsed are not carried over. A MakerNote with
+    /// an unknown structure stays at its offset since it might contain
+    /// absolute offsets. All other data are laid out around it.
+    pub fn compact(&mut self) -> Result<(), Error> {
+        let raw = self.serialize_compact()?;
+        self.replace(raw)
+    }
+
+    /// Generate raw Exif data without unused space
+    ///
+    /// Like [`compact`](Self::compact) without modifying the stored data.
+    pub fn serialize_compact(&self) -> Result<Vec<u8>, Error> {
+        self.read_document(|x| x.serialize_with_mode(SerializeMode::Rebuild))
+    }
+
+    /// Check the structure for problems
+    ///
+    /// See [`Document::validate`] for details.
+    ///
+    /// ```
+    /// # let data = std::fs::read("example.jpg").unwrap();
+    /// # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    /// # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+    /// let exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+    /// let findings = exif.validate().unwrap();
+    /// assert!(
+    ///     findings
+    ///         .iter()
+    ///         .all(|x| x.severity == gufo_exif::structure::Severity::Warning)
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<Vec<Finding>, Error> {
+        self.read_document(|x| x.validate())
+    }
+
+    /// Fix all problems that can be fixed without guessing
+    ///
+    /// Returns the findings that have been fixed. The complete structure is
+    /// laid out anew. See [`Document::repair`] for details.
+    pub fn repair(&mut self) -> Result<Vec<Finding>, Error> {
+        let (raw, repaired) = self.read_document(|x| x.repair())?;
+        self.replace(raw)?;
+        Ok(repaired)
+    }
 }
 
 impl<'a, S: Storage<'a>> Exif<'a, S> {
+    fn new(storage: S, index: Arc<ExifIndex>, parse_mode: ParseMode) -> Self {
+        Self {
+            storage,
+            index,
+            parse_mode,
+            mode: SerializeMode::default(),
+            lifetime: PhantomData,
+        }
+    }
+
+    /// Index of `data`
+    ///
+    /// Only data that can also be edited via [`Document`] are accepted.
+    fn index(data: &mut [u8], mode: ParseMode) -> Result<Arc<ExifIndex>, Error> {
+        let mut document = Document::for_mut_slice_with_mode(data, mode)?;
+        ExifIndex::for_document(&mut document).map(Arc::new)
+    }
+
+    /// Read-only access to the stored data
+    ///
+    /// Neither copies nor parses the data. All values, including the ones
+    /// without a convenience function in [`Exif`], can be read via
+    /// [`Lookup`](crate::Lookup).
+    ///
+    /// ```
+    /// # let data = std::fs::read("example.jpg").unwrap();
+    /// # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    /// # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+    /// use gufo_exif::Lookup;
+    ///
+    /// let exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+    /// let exposure_program = exif
+    ///     .view()
+    ///     .lookup_short(gufo_common::field::ExposureProgram.into())
+    ///     .unwrap();
+    /// assert_eq!(exposure_program, Some(2));
+    /// ```
+    pub fn view(&self) -> ExifRef<'_> {
+        ExifRef::with_index(self.storage.data(), &self.index)
+    }
+
+    /// Access to the underlying [`Document`]
+    ///
+    /// Changes made via the document are written to the stored data directly.
+    /// For only reading values, [`view`](Self::view) is much cheaper.
+    ///
+    /// The index used for reading values is taken from the document after `f`
+    /// returns. An error is returned if the document can't be indexed anymore.
+    pub fn document<T>(&mut self, f: impl FnOnce(&mut Document<'_>) -> T) -> Result<T, Error> {
+        let mut document =
+            Document::for_mut_slice_with_mode(self.storage.data_mut(), self.parse_mode)?;
+        let result = f(&mut document);
+
+        self.index = Arc::new(ExifIndex::for_document(&mut document)?);
+
+        Ok(result)
+    }
+
+    /// Read-only [`Document`] for the stored data
+    ///
+    /// For operations that need the complete structure but don't change the
+    /// stored data. The data are neither copied nor changed.
+    pub(crate) fn read_document<'s, T>(
+        &'s self,
+        f: impl FnOnce(&mut Document<'s, &'s [u8]>) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        f(&mut Document::for_slice_with_mode(
+            self.storage.data(),
+            self.parse_mode,
+        )?)
+    }
+
+    /// Replace the stored data with new raw Exif data
+    fn replace(&mut self, mut data: Vec<u8>) -> Result<(), Error> {
+        let index = Self::index(&mut data, self.parse_mode)?;
+        self.storage.replace(data)?;
+        self.index = index;
+        Ok(())
+    }
+
+    /// Strategy used for generating raw Exif data
+    pub fn serialize_mode(&self) -> SerializeMode {
+        self.mode
+    }
+
+    /// Set strategy used for generating raw Exif data
+    ///
+    /// With [`SerializeMode::Rebuild`], [`serialize`](Self::serialize) writes
+    /// the complete structure anew and updated values can grow beyond their
+    /// original size.
+    pub fn set_serialize_mode(&mut self, mode: SerializeMode) {
+        self.mode = mode;
+    }
+
+    /// IFDs that have been skipped while parsing
+    ///
+    /// See [`ExifRef::parse_diagnostics`] for details.
+    pub fn parse_diagnostics(&self) -> Vec<ParseDiagnostic> {
+        self.view().parse_diagnostics().to_vec()
+    }
+
     /// Overwrite stored entry
     ///
-    /// The raw size of `value` can only be of the same size or smaller than the
-    /// existing size. Otherwise, an [`Error::WouldIncreaseDataStore`] is
+    /// With [`SerializeMode::InPlace`], the raw size of `value` can only be of
+    /// the same size or smaller than the existing size. Otherwise, an
+    /// [`Error::WouldIncreaseDataStore`] is returned. With
+    /// [`SerializeMode::Rebuild`], values of any size are stored, given the
+    /// storage is resizable.
+    ///
+    /// If the entry does not exist yet, it is inserted into the existing IFD.
+    /// This requires the storage to be resizable. For storages that can't be
+    /// resized, [`Error::StorageNotResizable`] is returned. With
+    /// [`SerializeMode::InPlace`], inserting an entry that would move a
+    /// MakerNote with an unknown structure is refused with
+    /// [`Error::MakerNoteNotMovable`]. [`SerializeMode::Rebuild`] keeps such
+    /// MakerNotes at their offset.
+    ///
+    /// Entries of MakerNotes with their own TIFF structure, like Nikon's, can
+    /// only be updated in place. Otherwise, [`Error::MakerNoteNotResizable`] is
     /// returned.
     pub fn update_entry(&mut self, tag_ifd: TagIfd, value: Typed) -> Result<(), Error> {
+        if !self.view().contains(tag_ifd) {
+            return self.insert_entry(tag_ifd, value);
+        }
+
+        match self.update_entry_in_place(tag_ifd, &value) {
+            Err(Error::WouldIncreaseDataStore) if self.mode == SerializeMode::Rebuild => {
+                self.update_entry_rebuild(tag_ifd, &value)
+            }
+            result => result,
+        }
+    }
+
+    /// Overwrite stored entry without changing the size of the data
+    fn update_entry_in_place(&mut self, tag_ifd: TagIfd, value: &Typed) -> Result<(), Error> {
         self.document(|document| {
+            let (document, tag_ifd) = document.resolve(tag_ifd);
             let data = value.serialize(document.endieness);
             let new_data_store = data.len() > document.index_size;
 
             let Some((_, mut entry)) = document.entry(tag_ifd) else {
-                return Err(Error::other("Inserting new entries not yet supported."));
+                return Err(Error::other(format!("Entry {tag_ifd:?} disappeared")));
             };
 
             let old_data_len = (cheq(entry.type_().size()) * entry.count()?).check()?;
@@ -193,7 +458,7 @@ impl<'a, S: Storage<'a>> Exif<'a, S> {
                 // Currently stored in data, but new in ifd
                 let old_data_offset_end = (cheq(old_data_offset) + old_data_len).check()?;
 
-                entry.update_offset(tag_ifd.tag, value.type_(), value.count()?, old_data_offset)?;
+                entry.update(tag_ifd.tag, value.type_(), value.count()?, data)?;
 
                 document
                     .data(old_data_offset..old_data_offset_end)
@@ -226,7 +491,55 @@ impl<'a, S: Storage<'a>> Exif<'a, S> {
             }
 
             Ok::<_, Error>(())
-        })
+        })?
+    }
+
+    /// Overwrite stored entry by rebuilding the complete structure
+    fn update_entry_rebuild(&mut self, tag_ifd: TagIfd, value: &Typed) -> Result<(), Error> {
+        self.check_resizable(tag_ifd)?;
+
+        let raw = self.read_document(|document| {
+            let mut tree = document.tree()?;
+            if tag_ifd == field::MakerNote.into() {
+                // The new MakerNote doesn't have to stay at the old offset
+                tree.opaque_maker_note = None;
+            }
+            let value = TreeValue::from_typed(value, tree.endieness)?;
+            tree.ifd_mut(tag_ifd.ifd)?
+                .entries
+                .insert(tag_ifd.tag, value);
+            tree.serialize()
+        })?;
+
+        self.replace(raw)
+    }
+
+    /// Insert a new entry
+    ///
+    /// The entry list of the IFD stays sorted by tag. All offsets pointing
+    /// behind the inserted entry are updated. With [`SerializeMode::Rebuild`],
+    /// the complete structure is laid out anew instead.
+    fn insert_entry(&mut self, tag_ifd: TagIfd, value: Typed) -> Result<(), Error> {
+        if self.mode == SerializeMode::Rebuild {
+            return self.update_entry_rebuild(tag_ifd, &value);
+        }
+
+        self.check_resizable(tag_ifd)?;
+
+        let mut raw = self.serialize()?;
+        let raw = Document::for_mut_slice(&mut raw)?.serialize_with_new_entry(tag_ifd, &value)?;
+
+        self.replace(raw)
+    }
+
+    /// Returns an error if the entry belongs to a MakerNote that can't change
+    /// its size
+    fn check_resizable(&self, tag_ifd: TagIfd) -> Result<(), Error> {
+        if self.view().is_embedded(tag_ifd.ifd) {
+            Err(Error::MakerNoteNotResizable(tag_ifd.ifd))
+        } else {
+            Ok(())
+        }
     }
 
     /// Bytes that change by an update
@@ -234,11 +547,19 @@ impl<'a, S: Storage<'a>> Exif<'a, S> {
     /// Identical to [`update_entry`](Self::update_entry). Aditionally, a list
     /// of bytes with their corresponding values is returned. Changing these
     /// bytes in the original raw exif would achieve the requested update.
+    ///
+    /// Since inserting a new entry changes the size of the data, an
+    /// [`Error::WouldIncreaseDataStore`] is returned if the entry does not
+    /// exist yet.
     pub fn update_entry_diff(
         &mut self,
         tag_ifd: TagIfd,
         value: Typed,
     ) -> Result<Vec<(usize, u8)>, Error> {
+        if !self.view().contains(tag_ifd) {
+            return Err(Error::WouldIncreaseDataStore);
+        }
+
         let serialized_old = self.serialize()?;
         self.update_entry(tag_ifd, value)?;
         let serialized_new = self.serialize()?;
@@ -252,4 +573,120 @@ impl<'a, S: Storage<'a>> Exif<'a, S> {
 
         Ok(changes)
     }
+
+    /// Store text with a character identifier code
+    ///
+    /// The text is encoded via [`Typed::character_identified_code_string`]
+    /// in the byte order of the stored data and written via
+    /// [`update_entry`](Self::update_entry).
+    pub fn set_character_identified_code_string(
+        &mut self,
+        tag_ifd: TagIfd,
+        s: &str,
+    ) -> Result<(), Error> {
+        let value = Typed::character_identified_code_string(s, self.endieness());
+        self.update_entry(tag_ifd, value)
+    }
+
+    /// Store comments on the image
+    ///
+    /// ```
+    /// # let data = std::fs::read("example.jpg").unwrap();
+    /// # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    /// # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+    /// use gufo_exif::structure::SerializeMode;
+    ///
+    /// let mut exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+    /// exif.set_serialize_mode(SerializeMode::Rebuild);
+    ///
+    /// exif.set_user_comment("Eule im Schnee ❄").unwrap();
+    /// assert_eq!(exif.user_comment().as_deref(), Some("Eule im Schnee ❄"));
+    /// ```
+    ///
+    /// Stored as ASCII if possible and as UNICODE otherwise. As with
+    /// [`update_entry`](Self::update_entry), a comment that is longer than
+    /// the existing one requires [`SerializeMode::Rebuild`].
+    pub fn set_user_comment(&mut self, comment: &str) -> Result<(), Error> {
+        self.set_character_identified_code_string(field::UserComment.into(), comment)
+    }
+
+    /// Store a new JPEG thumbnail
+    ///
+    /// Replaces an existing thumbnail or adds a [`IfdId::Thumbnail`] after the
+    /// primary IFD. Since the size of the data changes, the complete structure
+    /// is laid out anew, as with [`SerializeMode::Rebuild`]. This requires the
+    /// storage to be resizable.
+    pub fn set_thumbnail(&mut self, jpeg: Vec<u8>) -> Result<(), Error> {
+        self.edit_tree(|tree| {
+            let endieness = tree.endieness;
+            let length = u32::try_from(jpeg.len())?;
+
+            let thumbnail = tree.ifds.entry(IfdId::Thumbnail).or_default();
+            // Uncompressed thumbnails are replaced by the JPEG
+            thumbnail.entries.remove(&Tag::STRIP_OFFSETS);
+            thumbnail.entries.remove(&Tag::STRIP_BYTE_COUNTS);
+            thumbnail.entries.insert(
+                field::ThumbnailCompression::TAG,
+                TreeValue::from_typed(&Typed::Short(vec![COMPRESSION_JPEG]), endieness)?,
+            );
+            thumbnail.entries.insert(
+                field::JPEGInterchangeFormat::TAG,
+                TreeValue::ImageData {
+                    type_: Type::Long,
+                    chunks: vec![jpeg],
+                },
+            );
+            thumbnail.entries.insert(
+                field::JPEGInterchangeFormatLength::TAG,
+                TreeValue::from_typed(&Typed::Long(vec![length]), endieness)?,
+            );
+
+            tree.ifd_mut(IfdId::Primary)?.next = Some(IfdId::Thumbnail);
+
+            Ok(())
+        })
+    }
+
+    /// Change the structure via its [`Tree`] and lay out the data anew
+    ///
+    /// This requires the storage to be resizable.
+    fn edit_tree(&mut self, f: impl FnOnce(&mut Tree) -> Result<(), Error>) -> Result<(), Error> {
+        let raw = self.read_document(|document| {
+            let mut tree = document.tree()?;
+            f(&mut tree)?;
+            tree.serialize()
+        })?;
+
+        self.replace(raw)
+    }
+
+    /// Remove the thumbnail
+    ///
+    /// Removes the complete [`IfdId::Thumbnail`] with the thumbnail data.
+    /// IFDs that follow the thumbnail in the chain are kept. Returns `false`
+    /// if no thumbnail existed.
+    pub fn remove_thumbnail(&mut self) -> Result<bool, Error> {
+        let raw = self.read_document(|document| {
+            let mut tree = document.tree()?;
+
+            let Some(thumbnail) = tree.ifds.remove(&IfdId::Thumbnail) else {
+                return Ok(None);
+            };
+            // Keep the IFDs that follow in the chain
+            for ifd in tree.ifds.values_mut() {
+                if ifd.next == Some(IfdId::Thumbnail) {
+                    ifd.next = thumbnail.next;
+                }
+            }
+
+            tree.serialize().map(Some)
+        })?;
+
+        let Some(raw) = raw else {
+            return Ok(false);
+        };
+
+        self.replace(raw)?;
+        Ok(true)
+    }
 }
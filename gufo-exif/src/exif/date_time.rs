@@ -0,0 +1,238 @@
+use chrono::{Datelike, FixedOffset, TimeDelta, TimeZone, Timelike};
+use gufo_common::datetime::DateTime;
+use gufo_common::exif::TagIfd;
+use gufo_common::field;
+
+use crate::structure::{Typed, lookup_date_time};
+use crate::{Error, Exif, Storage};
+
+/// Number of subsecond digits written if the field doesn't exist yet
+const SUBSEC_DIGITS: usize = 3;
+
+/// Largest year that fits into the four digits of the date
+const MAX_YEAR: i32 = 9999;
+
+/// Date and time fields with their subsecond and offset companions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateTimeField {
+    /// Date and time the file was changed
+    ///
+    /// Stored in [`DateTime`](field::DateTime),
+    /// [`SubSecTime`](field::SubSecTime), and
+    /// [`OffsetTime`](field::OffsetTime).
+    Modified,
+    /// Date and time the original image data were generated
+    ///
+    /// Stored in [`DateTimeOriginal`](field::DateTimeOriginal),
+    /// [`SubSecTimeOriginal`](field::SubSecTimeOriginal), and
+    /// [`OffsetTimeOriginal`](field::OffsetTimeOriginal).
+    Original,
+    /// Date and time the image was stored as digital data
+    ///
+    /// Stored in [`DateTimeDigitized`](field::DateTimeDigitized),
+    /// [`SubsecTimeDigitized`](field::SubsecTimeDigitized), and
+    /// [`OffsetTimeDigitized`](field::OffsetTimeDigitized).
+    Digitized,
+}
+
+impl DateTimeField {
+    /// All fields
+    pub const ALL: [Self; 3] = [Self::Modified, Self::Original, Self::Digitized];
+
+    /// Fields for date and time, subseconds, and offset
+    pub fn tags(self) -> (TagIfd, TagIfd, TagIfd) {
+        match self {
+            Self::Modified => (
+                field::DateTime.into(),
+                field::SubSecTime.into(),
+                field::OffsetTime.into(),
+            ),
+            Self::Original => (
+                field::DateTimeOriginal.into(),
+                field::SubSecTimeOriginal.into(),
+                field::OffsetTimeOriginal.into(),
+            ),
+            Self::Digitized => (
+                field::DateTimeDigitized.into(),
+                field::SubsecTimeDigitized.into(),
+                field::OffsetTimeDigitized.into(),
+            ),
+        }
+    }
+}
+
+impl<'a, S: Storage<'a>> Exif<'a, S> {
+    /// Date and time stored in `field`
+    pub fn date_time_field(&self, field: DateTimeField) -> Option<DateTime> {
+        let (date_time, subsec, offset) = field.tags();
+        lookup_date_time(&mut self.view(), date_time, subsec, offset)
+    }
+
+    /// Entries that store `date_time` in `field`
+    ///
+    /// The date and time always take 20 bytes and the offset 7 bytes. The
+    /// subseconds keep the number of digits of an existing entry. Therefore,
+    /// the values can be written with
+    /// [`update_entry_diff`](Self::update_entry_diff) if the entries already
+    /// exist.
+    ///
+    /// Subseconds are only added if they are not zero. Existing subsecond
+    /// entries without digits are kept as they are. The offset is only
+    /// written for [`DateTime::FixedOffset`]. Years before 0 or after 9999
+    /// can't be stored.
+    pub fn date_time_values(
+        &self,
+        field: DateTimeField,
+        date_time: &DateTime,
+    ) -> Result<Vec<(TagIfd, Typed)>, Error> {
+        let (date_time_tag, subsec_tag, offset_tag) = field.tags();
+
+        let (naive, offset) = match date_time {
+            DateTime::FixedOffset(x) => (x.naive_local(), Some(*x.offset())),
+            DateTime::Naive(x) => (*x, None),
+        };
+
+        if !(0..=MAX_YEAR).contains(&naive.year()) {
+            return Err(Error::other(format!(
+                "Year of {date_time} can't be stored in Exif"
+            )));
+        }
+
+        let mut values = vec![(
+            date_time_tag,
+            Typed::Ascii(naive.format("%Y:%m:%d %H:%M:%S").to_string().into_bytes()),
+        )];
+
+        // Leap seconds are represented by nanoseconds above one second
+        let nanoseconds = naive.nanosecond().min(999_999_999);
+        let existing_digits = self
+            .view()
+            .entry_data(subsec_tag)
+            .ok()
+            .flatten()
+            .map(|x| x.count);
+        let digits = match existing_digits {
+            // Without the NUL terminator
+            Some(count) if count > 1 => Some(count.saturating_sub(1).min(9)),
+            Some(_) => None,
+            None if nanoseconds != 0 => Some(SUBSEC_DIGITS),
+            None => None,
+        };
+
+        if let Some(digits) = digits {
+            let subsec = format!("{nanoseconds:09}");
+            let subsec = subsec.get(..digits).ok_or(Error::IndexOverflow)?;
+            values.push((subsec_tag, Typed::Ascii(subsec.as_bytes().to_vec())));
+        }
+
+        if let Some(offset) = offset {
+            values.push((offset_tag, Typed::Ascii(format_offset(offset).into_bytes())));
+        }
+
+        Ok(values)
+    }
+
+    /// Store `date_time` in `field`
+    ///
+    /// ```
+    /// # let data = std::fs::read("example.jpg").unwrap();
+    /// # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    /// # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+    /// use gufo_common::datetime::DateTime;
+    /// use gufo_exif::DateTimeField;
+    ///
+    /// let mut exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+    ///
+    /// let date_time = chrono::DateTime::parse_from_rfc3339("2024-07-14T18:30:00+02:00").unwrap();
+    /// exif.set_date_time(DateTimeField::Original, &DateTime::FixedOffset(date_time))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     exif.date_time_original().unwrap().to_string(),
+    ///     "2024-07-14 18:30:00 +02:00"
+    /// );
+    /// ```
+    ///
+    /// Existing entries are overwritten in place. Missing subsecond or offset
+    /// entries are inserted, which requires a resizable storage.
+    pub fn set_date_time(
+        &mut self,
+        field: DateTimeField,
+        date_time: &DateTime,
+    ) -> Result<(), Error> {
+        for (tag_ifd, value) in self.date_time_values(field, date_time)? {
+            self.update_entry(tag_ifd, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Shift all stored dates and times by `delta`
+    ///
+    /// This corrects images taken with a wrong camera clock. The GPS date and
+    /// time are not changed since they are set from the satellites' clocks.
+    ///
+    /// Nothing is changed if one of the dates can't be shifted.
+    pub fn shift_date_times(&mut self, delta: TimeDelta) -> Result<(), Error> {
+        let mut values = Vec::new();
+
+        for field in DateTimeField::ALL {
+            let Some(date_time) = self.date_time_field(field) else {
+                continue;
+            };
+
+            let shifted = match date_time {
+                DateTime::FixedOffset(x) => x.checked_add_signed(delta).map(DateTime::FixedOffset),
+                DateTime::Naive(x) => x.checked_add_signed(delta).map(DateTime::Naive),
+            }
+            .ok_or_else(|| Error::other(format!("Can't shift {date_time} by {delta}")))?;
+
+            values.extend(self.date_time_values(field, &shifted)?);
+        }
+
+        for (tag_ifd, value) in values {
+            self.update_entry(tag_ifd, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Move all stored dates and times into the time zone `offset`
+    ///
+    /// Dates with an offset keep the point in time and the local time is
+    /// converted. Dates without an offset keep the local time and `offset` is
+    /// added.
+    pub fn set_time_zone(&mut self, offset: FixedOffset) -> Result<(), Error> {
+        let mut values = Vec::new();
+
+        for field in DateTimeField::ALL {
+            let Some(date_time) = self.date_time_field(field) else {
+                continue;
+            };
+
+            let converted = match date_time {
+                DateTime::FixedOffset(x) => x.with_timezone(&offset),
+                DateTime::Naive(x) => offset.from_local_datetime(&x).single().ok_or_else(|| {
+                    Error::other(format!("Can't convert {x} to time zone {offset}"))
+                })?,
+            };
+
+            values.extend(self.date_time_values(field, &DateTime::FixedOffset(converted))?);
+        }
+
+        for (tag_ifd, value) in values {
+            self.update_entry(tag_ifd, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Format offset as `+HH:MM`
+fn format_offset(offset: FixedOffset) -> String {
+    let seconds = offset.local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.unsigned_abs();
+
+    format!("{sign}{:02}:{:02}", seconds / 3600, seconds % 3600 / 60)
+}
@@ -0,0 +1,320 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use gufo_common::exif::{IfdId, Tag, TagIfd, Type};
+use gufo_common::math::cheq;
+use gufo_common::types::Rational;
+use serde::{Deserialize, Serialize};
+
+use crate::structure::Typed;
+use crate::structure::tree::{Tree, TreeIfd, TreeValue};
+use crate::structure::util::Endieness;
+use crate::{Error, Exif, Storage};
+
+/// Complete Exif structure that can be serialized via serde
+///
+/// Created via [`Exif::export`]. The raw Exif data can be generated again via
+/// [`to_raw`](Self::to_raw).
+///
+/// ```
+/// # let data = std::fs::read("example.jpg").unwrap();
+/// # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+/// # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+/// let exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+/// let export = exif.export().unwrap();
+///
+/// let model = export.entry(gufo_common::field::Model.into()).unwrap();
+/// assert_eq!(model.name.as_deref(), Some("Model"));
+///
+/// let exif = gufo_exif::Exif::for_vec(export.to_raw().unwrap()).unwrap();
+/// assert!(exif.model().is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExifExport {
+    pub endieness: Endieness,
+    pub big_tiff: bool,
+    pub ifds: Vec<IfdExport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IfdExport {
+    pub ifd: IfdId,
+    /// Next IFD in the chain of IFDs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<IfdId>,
+    pub entries: Vec<EntryExport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryExport {
+    pub tag: u16,
+    /// Name of the tag if it is known
+    ///
+    /// Only informative, it is ignored by [`ExifExport::to_raw`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Type,
+    pub count: usize,
+    pub value: ValueExport,
+}
+
+/// Decoded value of an entry
+///
+/// Rationals are stored as pairs of numerator and denominator. Binary data are
+/// stored base64 encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueExport {
+    Byte(Vec<u8>),
+    /// Text without the terminating NUL
+    ///
+    /// Text that isn't terminated by a NUL is stored as [`Raw`](Self::Raw).
+    Ascii(String),
+    Short(Vec<u16>),
+    Long(Vec<u32>),
+    Long8(Vec<u64>),
+    Rational(Vec<(u32, u32)>),
+    /// Base64 encoded
+    Undefined(String),
+    SLong(Vec<i32>),
+    SRational(Vec<(i32, i32)>),
+    /// Text without the terminating NUL
+    Utf8(String),
+    /// Base64 encoded bytes in the byte order of the structure
+    ///
+    /// Used for unknown types and text that isn't valid UTF-8 or not NUL
+    /// terminated.
+    Raw(String),
+    /// Pointer to an IFD
+    Ifd(IfdId),
+    /// Pointers to multiple IFDs, like in
+    /// [`SubIFDs`](gufo_common::field::SubIFDs)
+    SubIfds(Vec<IfdId>),
+    /// Base64 encoded chunks of image data, like strips or a JPEG thumbnail
+    ImageData(Vec<String>),
+}
+
+impl<'a, S: Storage<'a>> Exif<'a, S> {
+    /// Representation of all IFDs and entries that can be serialized via serde
+    ///
+    /// See [`ExifExport`] for an example.
+    pub fn export(&self) -> Result<ExifExport, Error> {
+        let tree = self.read_document(|x| x.tree())?;
+        ExifExport::from_tree(&tree)
+    }
+}
+
+impl ExifExport {
+    /// Entry `tag_ifd`
+    pub fn entry(&self, tag_ifd: TagIfd) -> Option<&EntryExport> {
+        self.ifds
+            .iter()
+            .find(|x| x.ifd == tag_ifd.ifd)?
+            .entries
+            .iter()
+            .find(|x| x.tag == tag_ifd.tag.0)
+    }
+
+    /// Generate raw Exif data
+    ///
+    /// The structure is laid out anew, as with
+    /// [`SerializeMode::Rebuild`](crate::structure::SerializeMode::Rebuild).
+    pub fn to_raw(&self) -> Result<Vec<u8>, Error> {
+        self.to_tree()?.serialize()
+    }
+
+    fn from_tree(tree: &Tree) -> Result<Self, Error> {
+        let mut ifds = Vec::new();
+
+        for (ifd_id, ifd) in &tree.ifds {
+            let mut entries = Vec::new();
+
+            for (tag, value) in &ifd.entries {
+                let (type_, count, value) = match value {
+                    TreeValue::Data { type_, count, data } => (
+                        *type_,
+                        *count,
+                        ValueExport::from_data(*type_, *count, data, tree.endieness)?,
+                    ),
+                    TreeValue::Ifd { type_, ifd } => (*type_, 1, ValueExport::Ifd(*ifd)),
+                    TreeValue::SubIfds { type_, ifds } => {
+                        (*type_, ifds.len(), ValueExport::SubIfds(ifds.clone()))
+                    }
+                    TreeValue::ImageData { type_, chunks } => (
+                        *type_,
+                        chunks.len(),
+                        ValueExport::ImageData(chunks.iter().map(|x| BASE64.encode(x)).collect()),
+                    ),
+                };
+
+                entries.push(EntryExport {
+                    tag: tag.0,
+                    name: gufo_common::exif::lookup_tag_name(TagIfd::new(*tag, *ifd_id))
+                        .map(ToString::to_string),
+                    type_,
+                    count,
+                    value,
+                });
+            }
+
+            ifds.push(IfdExport {
+                ifd: *ifd_id,
+                next: ifd.next,
+                entries,
+            });
+        }
+
+        Ok(Self {
+            endieness: tree.endieness,
+            big_tiff: tree.big_tiff,
+            ifds,
+        })
+    }
+
+    fn to_tree(&self) -> Result<Tree, Error> {
+        let mut tree = Tree::new(self.endieness, self.big_tiff);
+
+        for ifd in &self.ifds {
+            let mut tree_ifd = TreeIfd {
+                next: ifd.next,
+                ..Default::default()
+            };
+
+            for entry in &ifd.entries {
+                let type_ = entry.type_;
+                let value = match &entry.value {
+                    ValueExport::Ifd(ifd) => TreeValue::Ifd { type_, ifd: *ifd },
+                    ValueExport::SubIfds(ifds) => TreeValue::SubIfds {
+                        type_,
+                        ifds: ifds.clone(),
+                    },
+                    ValueExport::ImageData(chunks) => TreeValue::ImageData {
+                        type_,
+                        chunks: chunks.iter().map(decode_base64).collect::<Result<_, _>>()?,
+                    },
+                    value => {
+                        let (count, data) = value.to_data(type_, entry.count, self.endieness)?;
+                        TreeValue::Data { type_, count, data }
+                    }
+                };
+
+                tree_ifd.entries.insert(Tag(entry.tag), value);
+            }
+
+            if tree.ifds.insert(ifd.ifd, tree_ifd).is_some() {
+                return Err(Error::other(format!("IFD {:?} exists twice", ifd.ifd)));
+            }
+        }
+
+        Ok(tree)
+    }
+}
+
+impl ValueExport {
+    fn from_data(
+        type_: Type,
+        count: usize,
+        data: &[u8],
+        endieness: Endieness,
+    ) -> Result<Self, Error> {
+        Ok(match type_ {
+            Type::Ascii | Type::Utf8 => {
+                // Only a single NUL is added again
+                let text = data
+                    .strip_suffix(b"\0")
+                    .and_then(|x| String::from_utf8(x.to_vec()).ok());
+                match text {
+                    Some(text) if type_ == Type::Ascii => Self::Ascii(text),
+                    Some(text) => Self::Utf8(text),
+                    None => Self::Raw(BASE64.encode(data)),
+                }
+            }
+            Type::Unknown(_) => Self::Raw(BASE64.encode(data)),
+            _ => match Typed::new(type_, count, data, endieness)? {
+                Typed::Byte(x) => Self::Byte(x),
+                Typed::Short(x) => Self::Short(x),
+                Typed::Long(x) => Self::Long(x),
+                Typed::Long8(x) => Self::Long8(x),
+                Typed::Rational(x) => {
+                    Self::Rational(x.iter().map(|x| (x.numerator, x.denominator)).collect())
+                }
+                Typed::Undefined(x) => Self::Undefined(BASE64.encode(x)),
+                Typed::SLong(x) => Self::SLong(x),
+                Typed::SRational(x) => {
+                    Self::SRational(x.iter().map(|x| (x.numerator, x.denominator)).collect())
+                }
+                Typed::Ascii(_) | Typed::Utf8(_) | Typed::Unknown(..) => {
+                    Self::Raw(BASE64.encode(data))
+                }
+            },
+        })
+    }
+
+    /// Count and raw data in `endieness` for an entry with `type_`
+    ///
+    /// The count is determined by the value, except for raw data and padded
+    /// text.
+    fn to_data(
+        &self,
+        type_: Type,
+        count: usize,
+        endieness: Endieness,
+    ) -> Result<(usize, Vec<u8>), Error> {
+        let typed = match (self, type_) {
+            (Self::Raw(data), _) => {
+                let data = decode_base64(data)?;
+                if data.len() != (cheq(count) * type_.size()).check()? {
+                    return Err(Error::other(format!(
+                        "Raw data of {} bytes don't match count {count}",
+                        data.len()
+                    )));
+                }
+                return Ok((count, data));
+            }
+            (Self::Ascii(text), Type::Ascii) | (Self::Utf8(text), Type::Utf8) => {
+                let mut data = text.as_bytes().to_vec();
+                data.push(0);
+                // Keep the size of fixed size fields, like for dates
+                if data.len() < count {
+                    data.resize(count, 0);
+                }
+                return Ok((data.len(), data));
+            }
+            (Self::Byte(x), Type::Byte) => Typed::Byte(x.clone()),
+            (Self::Short(x), Type::Short) => Typed::Short(x.clone()),
+            (Self::Long(x), Type::Long | Type::Ifd) => Typed::Long(x.clone()),
+            (Self::Long8(x), Type::Long8 | Type::Ifd8) => Typed::Long8(x.clone()),
+            (Self::Rational(x), Type::Rational) => Typed::Rational(
+                x.iter()
+                    .map(|(numerator, denominator)| Rational {
+                        numerator: *numerator,
+                        denominator: *denominator,
+                    })
+                    .collect(),
+            ),
+            (Self::Undefined(x), Type::Undefined) => Typed::Undefined(decode_base64(x)?),
+            (Self::SLong(x), Type::SLong) => Typed::SLong(x.clone()),
+            (Self::SRational(x), Type::SRational) => Typed::SRational(
+                x.iter()
+                    .map(|(numerator, denominator)| Rational {
+                        numerator: *numerator,
+                        denominator: *denominator,
+                    })
+                    .collect(),
+            ),
+            (value, type_) => {
+                return Err(Error::other(format!(
+                    "Value {value:?} doesn't match type {type_:?}"
+                )));
+            }
+        };
+
+        Ok((typed.count()?, typed.serialize(endieness)))
+    }
+}
+
+fn decode_base64(data: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
+    BASE64
+        .decode(data)
+        .map_err(|err| Error::other(format!("Invalid base64 data: {err}")))
+}
@@ -0,0 +1,222 @@
+use std::collections::btree_map;
+
+use gufo_common::exif::{Field, IfdId, Tag, TagIfd};
+use gufo_common::field;
+use gufo_common::geography::Location;
+use gufo_common::types::Rational;
+
+use crate::structure::Typed;
+use crate::structure::tree::TreeValue;
+use crate::{Error, Exif, Storage};
+
+/// GPS version 2.3
+const GPS_VERSION_ID: [u8; 4] = [2, 3, 0, 0];
+
+/// Denominator for the seconds of coordinates and timestamps
+const SECONDS_DENOMINATOR: u32 = 1000;
+
+/// Denominator for the altitude in meters
+const ALTITUDE_DENOMINATOR: u32 = 100;
+
+impl<'a, S: Storage<'a>> Exif<'a, S> {
+    /// Store latitude and longitude
+    ///
+    /// ```
+    /// # let data = std::fs::read("example.jpg").unwrap();
+    /// # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    /// # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+    /// use gufo_common::geography::{Coord, Location};
+    ///
+    /// let mut exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+    /// assert!(exif.gps_location().is_none());
+    ///
+    /// let location = Location::new_from_coord(Coord(-46.235194), Coord(126.068528));
+    /// exif.set_gps_location(location).unwrap();
+    ///
+    /// assert_eq!(
+    ///     exif.gps_location().unwrap().iso_6709(),
+    ///     r#"46°14'06.7"S 126°04'06.7"E"#
+    /// );
+    /// ```
+    ///
+    /// The entries are overwritten in place if all of them exist and the new
+    /// values fit. Otherwise, the GPS IFD and missing entries are created and
+    /// the complete structure is laid out anew, as with
+    /// [`SerializeMode::Rebuild`](crate::structure::SerializeMode::Rebuild).
+    /// This requires the storage to be resizable.
+    pub fn set_gps_location(&mut self, location: Location) -> Result<(), Error> {
+        let (lat_ref, lat) = location.lat_ref_deg_min_sec();
+        let (lon_ref, lon) = location.lon_ref_deg_min_sec();
+
+        self.set_gps_entries([
+            (
+                field::GPSLatitudeRef::TAG,
+                Typed::Ascii(lat_ref.to_string().into_bytes()),
+            ),
+            (field::GPSLatitude::TAG, Typed::Rational(deg_min_sec(lat)?)),
+            (
+                field::GPSLongitudeRef::TAG,
+                Typed::Ascii(lon_ref.to_string().into_bytes()),
+            ),
+            (field::GPSLongitude::TAG, Typed::Rational(deg_min_sec(lon)?)),
+        ])
+    }
+
+    /// Store the altitude in meters
+    ///
+    /// Negative values are below sea level. See
+    /// [`set_gps_location`](Self::set_gps_location) for how the data are
+    /// stored.
+    pub fn set_gps_altitude(&mut self, altitude: f64) -> Result<(), Error> {
+        let altitude_ref = u8::from(altitude < 0.);
+        let altitude = Rational::from_f64(altitude.abs(), ALTITUDE_DENOMINATOR)
+            .ok_or_else(|| Error::other(format!("Invalid altitude: {altitude}")))?;
+
+        self.set_gps_entries([
+            (field::GPSAltitudeRef::TAG, Typed::Byte(vec![altitude_ref])),
+            (field::GPSAltitude::TAG, Typed::Rational(vec![altitude])),
+        ])
+    }
+
+    /// Store the GPS date and time
+    ///
+    /// GPS time is always UTC. See
+    /// [`set_gps_location`](Self::set_gps_location) for how the data are
+    /// stored.
+    #[cfg(feature = "chrono")]
+    pub fn set_gps_date_time(
+        &mut self,
+        date_time: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), Error> {
+        use chrono::Timelike;
+        use gufo_common::math::cheq;
+
+        let date = date_time.format("%Y:%m:%d").to_string();
+        // Truncate instead of rounding to never end up with 60 seconds
+        let milliseconds = (cheq(date_time.nanosecond().min(999_999_999)) / 1_000_000).check()?;
+        let seconds = (cheq(date_time.second()) * SECONDS_DENOMINATOR + milliseconds).check()?;
+
+        let time = vec![
+            Rational::new(date_time.hour(), 1),
+            Rational::new(date_time.minute(), 1),
+            Rational::new(seconds, SECONDS_DENOMINATOR),
+        ];
+
+        self.set_gps_entries([
+            (field::GPSDateStamp::TAG, Typed::Ascii(date.into_bytes())),
+            (field::GPSTimeStamp::TAG, Typed::Rational(time)),
+        ])
+    }
+
+    /// Store the name of the method used for location finding
+    ///
+    /// Common values are `"GPS"`, `"CELLID"`, `"WLAN"`, and `"MANUAL"`. The
+    /// text is stored with a character identifier code, see
+    /// [`Typed::character_identified_code_string`]. See
+    /// [`set_gps_location`](Self::set_gps_location) for how the data are
+    /// stored.
+    pub fn set_gps_processing_method(&mut self, method: &str) -> Result<(), Error> {
+        let value = Typed::character_identified_code_string(method, self.endieness());
+        self.set_gps_entries([(field::GPSProcessingMethod::TAG, value)])
+    }
+
+    /// Store the name of the GPS area
+    ///
+    /// The text is stored with a character identifier code, see
+    /// [`Typed::character_identified_code_string`]. See
+    /// [`set_gps_location`](Self::set_gps_location) for how the data are
+    /// stored.
+    pub fn set_gps_area_information(&mut self, area: &str) -> Result<(), Error> {
+        let value = Typed::character_identified_code_string(area, self.endieness());
+        self.set_gps_entries([(field::GPSAreaInformation::TAG, value)])
+    }
+
+    /// Remove the GPS IFD with all its entries
+    ///
+    /// Returns `false` if no GPS IFD existed. This requires the storage to be
+    /// resizable.
+    pub fn remove_gps(&mut self) -> Result<bool, Error> {
+        if !self.view().ifds().any(|(ifd, _)| ifd == IfdId::Gps) {
+            return Ok(false);
+        }
+
+        self.edit_tree(|tree| {
+            tree.ifds.remove(&IfdId::Gps);
+            if let Some(primary) = tree.ifds.get_mut(&IfdId::Primary) {
+                primary.entries.remove(&Tag::GPS_INFO_IFD_POINTER);
+            }
+
+            Ok(())
+        })?;
+
+        Ok(true)
+    }
+
+    /// Insert or replace entries in the GPS IFD
+    fn set_gps_entries(
+        &mut self,
+        entries: impl IntoIterator<Item = (Tag, Typed)>,
+    ) -> Result<(), Error> {
+        let entries = entries.into_iter().collect::<Vec<_>>();
+
+        if self.gps_entries_fit(&entries)? {
+            for (tag, value) in entries {
+                self.update_entry(TagIfd::new(tag, IfdId::Gps), value)?;
+            }
+            return Ok(());
+        }
+
+        self.edit_tree(|tree| {
+            let endieness = tree.endieness;
+            let gps = tree.ifds.entry(IfdId::Gps).or_default();
+
+            if let btree_map::Entry::Vacant(entry) = gps.entries.entry(field::GPSVersionID::TAG) {
+                entry.insert(TreeValue::from_typed(
+                    &Typed::Byte(GPS_VERSION_ID.to_vec()),
+                    endieness,
+                )?);
+            }
+
+            for (tag, value) in entries {
+                gps.entries
+                    .insert(tag, TreeValue::from_typed(&value, endieness)?);
+            }
+
+            tree.link_ifds();
+
+            Ok(())
+        })
+    }
+
+    /// Whether all `entries` exist in the GPS IFD and can be overwritten in
+    /// place
+    fn gps_entries_fit(&self, entries: &[(Tag, Typed)]) -> Result<bool, Error> {
+        self.read_document(|document| {
+            let endieness = document.endieness;
+            let index_size = document.index_size;
+
+            for (tag, value) in entries {
+                let Some(entry) = document.entry_data_ref(TagIfd::new(*tag, IfdId::Gps))? else {
+                    return Ok(false);
+                };
+
+                if value.serialize(endieness).len() > entry.data.len().max(index_size) {
+                    return Ok(false);
+                }
+            }
+
+            Ok(true)
+        })
+    }
+}
+
+/// Rationals for degrees, minutes, and seconds or hours, minutes, and seconds
+fn deg_min_sec((deg, min, sec): (f64, f64, f64)) -> Result<Vec<Rational<u32>>, Error> {
+    [(deg, 1), (min, 1), (sec, SECONDS_DENOMINATOR)]
+        .into_iter()
+        .map(|(value, denominator)| {
+            Rational::from_f64(value, denominator)
+                .ok_or_else(|| Error::other(format!("Invalid value: {value}")))
+        })
+        .collect()
+}
@@ -1,43 +1,117 @@
 use gufo_common::types::Rational;
-use gufo_common::{geography, hardware, orientation};
+use gufo_common::{capture, geography, hardware, orientation};
 
-use crate::structure::Document;
+use crate::structure::{Endieness, Lookup, SerializeMode};
 use crate::{Error, Exif, Storage};
 
 impl<'a, S: Storage<'a>> Exif<'a, S> {
     /// Generate raw exif data representing the Exif data
+    ///
+    /// The layout of the data is determined by the
+    /// [`serialize_mode`](Self::serialize_mode).
     pub fn serialize(&self) -> Result<Vec<u8>, Error> {
-        self.document(|x| x.serialize())
+        match self.serialize_mode() {
+            // Changes are always written to the stored data directly
+            SerializeMode::InPlace => Ok(self.storage.data().to_vec()),
+            mode => self.read_document(|x| x.serialize_with_mode(mode)),
+        }
+    }
+
+    /// Byte order of the stored data
+    pub fn endieness(&self) -> Endieness {
+        self.view().endieness()
     }
 
-    /// Access to the underlying [`Document`]
-    pub fn document<T>(&self, f: impl FnOnce(&mut Document<'_>) -> T) -> T {
-        self.document.access(|x| f(x))
+    /// Generate raw Exif data in the byte order `endieness`
+    ///
+    /// If the byte order differs from the stored one, the complete structure
+    /// is rebuilt, like with
+    /// [`SerializeMode::Rebuild`](crate::structure::SerializeMode::Rebuild),
+    /// and all values are re-encoded. See
+    /// [`Document::serialize_with_endieness`](crate::structure::Document::serialize_with_endieness)
+    /// for the handling of MakerNotes.
+    ///
+    /// ```
+    /// # let data = std::fs::read("example.jpg").unwrap();
+    /// # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    /// # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+    /// use gufo_exif::structure::Endieness;
+    ///
+    /// let exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+    /// let raw_big_endian = exif.serialize_with(Endieness::Big).unwrap();
+    /// assert!(raw_big_endian.starts_with(b"MM"));
+    ///
+    /// let exif = gufo_exif::Exif::for_vec(raw_big_endian).unwrap();
+    /// assert_eq!(exif.model().as_deref(), Some("Canon EOS 400D DIGITAL"));
+    /// ```
+    pub fn serialize_with(&self, endieness: Endieness) -> Result<Vec<u8>, Error> {
+        if self.endieness() == endieness {
+            self.serialize()
+        } else {
+            self.read_document(|x| x.serialize_with_endieness(endieness))
+        }
     }
 
     /// Name of the main person who created the image
     pub fn artist(&self) -> Option<String> {
-        self.document(|x| x.artist())
+        self.view().artist()
     }
 
     /// Owner of the camera used in photography
     pub fn camera_owner_name(&self) -> Option<String> {
-        self.document(|x| x.camera_owner_name())
+        self.view().camera_owner_name()
+    }
+
+    /// Color space of the image data
+    pub fn color_space(&self) -> Option<capture::ColorSpace> {
+        self.view().color_space()
+    }
+
+    /// Contrast processing applied by the camera
+    pub fn contrast(&self) -> Option<capture::Contrast> {
+        self.view().contrast()
     }
 
     /// Copyright information
     pub fn copyright(&self) -> Option<String> {
-        self.document(|x| x.copyright())
+        self.view().copyright()
+    }
+
+    /// Use of special processing on the image data
+    pub fn custom_rendered(&self) -> Option<capture::CustomRendered> {
+        self.view().custom_rendered()
+    }
+
+    #[cfg(feature = "chrono")]
+    /// The date and time when the file was changed
+    pub fn date_time(&self) -> Option<gufo_common::datetime::DateTime> {
+        self.view().date_time()
+    }
+
+    #[cfg(feature = "chrono")]
+    /// The date and time when the image was stored as digital data
+    pub fn date_time_digitized(&self) -> Option<gufo_common::datetime::DateTime> {
+        self.view().date_time_digitized()
     }
 
     #[cfg(feature = "chrono")]
     /// The date and time when the original image data was generated
     pub fn date_time_original(&self) -> Option<gufo_common::datetime::DateTime> {
-        self.document(|x| x.date_time_original())
+        self.view().date_time_original()
     }
 
     pub fn digital_zoom_ratio(&self) -> Option<Rational<u32>> {
-        self.document(|x| x.digital_zoom_ratio())
+        self.view().digital_zoom_ratio()
+    }
+
+    /// Exposure mode set when the image was shot
+    pub fn exposure_mode(&self) -> Option<capture::ExposureMode> {
+        self.view().exposure_mode()
+    }
+
+    /// Program used by the camera to set the exposure
+    pub fn exposure_program(&self) -> Option<capture::ExposureProgram> {
+        self.view().exposure_program()
     }
 
     /// Exposure time in seconds
@@ -46,49 +120,96 @@ impl<'a, S: Storage<'a>> Exif<'a, S> {
     /// is typically one, such that the value is given in its common for like
     /// "1/60 sec".
     pub fn exposure_time(&self) -> Option<Rational<u32>> {
-        self.document(|x| x.exposure_time())
+        self.view().exposure_time()
     }
 
     /// Aperture
     pub fn f_number(&self) -> Option<Rational<u32>> {
-        self.document(|x| x.f_number())
+        self.view().f_number()
+    }
+
+    /// Status of the flash
+    pub fn flash(&self) -> Option<capture::Flash> {
+        self.view().flash()
     }
 
     /// Focal length in mm
     pub fn focal_length(&self) -> Option<Rational<u32>> {
-        self.document(|x| x.focal_length())
+        self.view().focal_length()
+    }
+
+    /// Degree of overall image gain adjustment
+    pub fn gain_control(&self) -> Option<capture::GainControl> {
+        self.view().gain_control()
+    }
+
+    /// GPS altitude in meters
+    ///
+    /// Negative values are below sea level.
+    pub fn gps_altitude(&self) -> Option<f64> {
+        self.view().gps_altitude()
+    }
+
+    /// Name of the GPS area
+    pub fn gps_area_information(&self) -> Option<String> {
+        self.view().gps_area_information()
+    }
+
+    #[cfg(feature = "chrono")]
+    /// GPS date and time in UTC
+    pub fn gps_date_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.view().gps_date_time()
     }
 
     /// GPS location
     pub fn gps_location(&self) -> Option<geography::Location> {
-        self.document(|x| x.gps_location())
+        self.view().gps_location()
+    }
+
+    /// Name of the method used for location finding, like `"GPS"` or
+    /// `"NETWORK"`
+    pub fn gps_processing_method(&self) -> Option<String> {
+        self.view().gps_processing_method()
     }
 
     /// ISO
     pub fn iso_speed_rating(&self) -> Option<u16> {
-        self.document(|x| x.iso_speed_rating())
+        self.view().iso_speed_rating()
     }
 
     pub fn lens_make(&self) -> Option<String> {
-        self.document(|x| x.lens_make())
+        self.view().lens_make()
     }
 
+    /// Lens model
+    ///
+    /// Falls back to the lens model from the MakerNote.
     pub fn lens_model(&self) -> Option<String> {
-        self.document(|x| x.lens_model())
+        self.view().lens_model()
     }
 
     pub fn lens_specification(&self) -> Option<hardware::LensSpecification> {
-        self.document(|x| x.lens_specification())
+        self.view().lens_specification()
+    }
+
+    /// Kind of light source
+    pub fn light_source(&self) -> Option<capture::LightSource> {
+        self.view().light_source()
     }
 
     /// Camera manifacturer
     pub fn make(&self) -> Option<String> {
-        self.document(|x| x.make())
+        self.view().make()
+    }
+
+    /// Metering mode
+    pub fn metering_mode(&self) -> Option<capture::MeteringMode> {
+        self.view().metering_mode()
     }
 
     /// Camera model
     pub fn model(&self) -> Option<String> {
-        self.document(|x| x.model())
+        self.view().model()
     }
 
     /// Image orientation
@@ -96,7 +217,34 @@ impl<'a, S: Storage<'a>> Exif<'a, S> {
     /// Rotation and mirroring that have to be applied to show the image
     /// correctly
     pub fn orientation(&self) -> Option<orientation::Orientation> {
-        self.document(|x| x.orientation())
+        self.view().orientation()
+    }
+
+    /// Saturation processing applied by the camera
+    pub fn saturation(&self) -> Option<capture::Saturation> {
+        self.view().saturation()
+    }
+
+    /// Type of scene that was shot
+    pub fn scene_capture_type(&self) -> Option<capture::SceneCaptureType> {
+        self.view().scene_capture_type()
+    }
+
+    /// Type of image sensor
+    pub fn sensing_method(&self) -> Option<capture::SensingMethod> {
+        self.view().sensing_method()
+    }
+
+    /// Serial number of the camera body
+    ///
+    /// Falls back to the serial number from the MakerNote.
+    pub fn serial_number(&self) -> Option<String> {
+        self.view().serial_number()
+    }
+
+    /// Sharpness processing applied by the camera
+    pub fn sharpness(&self) -> Option<capture::Sharpness> {
+        self.view().sharpness()
     }
 
     /// Name and version of software or firmware
@@ -104,11 +252,31 @@ impl<'a, S: Storage<'a>> Exif<'a, S> {
     /// In practice, this often contains the name, version, and operating system
     /// of the image editing software used to edit an image.
     pub fn software(&self) -> Option<String> {
-        self.document(|x| x.software())
+        self.view().software()
+    }
+
+    /// Distance to the subject
+    pub fn subject_distance_range(&self) -> Option<capture::SubjectDistanceRange> {
+        self.view().subject_distance_range()
+    }
+
+    /// Embedded JPEG thumbnail
+    ///
+    /// The thumbnail is stored in the [`IfdId::Thumbnail`], which is the IFD
+    /// following the primary IFD.
+    ///
+    /// [`IfdId::Thumbnail`]: gufo_common::exif::IfdId::Thumbnail
+    pub fn thumbnail(&self) -> Option<Vec<u8>> {
+        self.view().thumbnail()
     }
 
     /// Freely write keywords or comments on the image
     pub fn user_comment(&self) -> Option<String> {
-        self.document(|x| x.user_comment())
+        self.view().user_comment()
+    }
+
+    /// White balance mode
+    pub fn white_balance(&self) -> Option<capture::WhiteBalance> {
+        self.view().white_balance()
     }
 }
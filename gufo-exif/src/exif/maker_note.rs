@@ -0,0 +1,82 @@
+use crate::maker_note::{Vendor, apple, canon, nikon};
+use crate::structure::Lookup;
+use crate::{Exif, Storage};
+
+impl<'a, S: Storage<'a>> Exif<'a, S> {
+    /// Manufacturer that defines the structure of the MakerNote
+    ///
+    /// Returns `None` if there is no MakerNote or it couldn't be read. See
+    /// [`maker_note`](crate::maker_note) for details.
+    pub fn maker_note_vendor(&self) -> Option<Vendor> {
+        self.view().maker_note_vendor()
+    }
+
+    /// Identifier that links a photo to the video of its Live Photo
+    ///
+    /// The video carries the same identifier in its QuickTime metadata.
+    pub fn apple_content_identifier(&self) -> Option<String> {
+        self.view().apple_content_identifier()
+    }
+
+    /// Identifier shared by all photos of a burst
+    pub fn apple_burst_uuid(&self) -> Option<String> {
+        self.view().apple_burst_uuid()
+    }
+
+    /// HDR headroom from an Apple MakerNote
+    pub fn apple_hdr_headroom(&self) -> Option<f64> {
+        self.view().apple_hdr_headroom()
+    }
+
+    /// HDR gain from an Apple MakerNote
+    pub fn apple_hdr_gain(&self) -> Option<f64> {
+        self.view().apple_hdr_gain()
+    }
+
+    /// Acceleration of the device when the photo was taken
+    pub fn apple_acceleration_vector(&self) -> Option<apple::AccelerationVector> {
+        self.view().apple_acceleration_vector()
+    }
+
+    /// Camera settings from a Canon MakerNote
+    pub fn canon_camera_settings(&self) -> Option<canon::CameraSettings> {
+        self.view().canon_camera_settings()
+    }
+
+    /// Canon specific identifier of the lens
+    ///
+    /// Also available if [`LensType::name`](canon::LensType::name) doesn't
+    /// know the lens, such that the identifier can be resolved elsewhere.
+    pub fn canon_lens_type(&self) -> Option<canon::LensType> {
+        self.view().canon_lens_type()
+    }
+
+    /// Focal length from a Canon MakerNote
+    pub fn canon_focal_length(&self) -> Option<canon::FocalLength> {
+        self.view().canon_focal_length()
+    }
+
+    /// Shot information from a Canon MakerNote
+    pub fn canon_shot_info(&self) -> Option<canon::ShotInfo> {
+        self.view().canon_shot_info()
+    }
+
+    /// File information from a Canon MakerNote
+    pub fn canon_file_info(&self) -> Option<canon::FileInfo> {
+        self.view().canon_file_info()
+    }
+
+    /// Lens features from a Nikon MakerNote
+    pub fn nikon_lens_type(&self) -> Option<nikon::LensType> {
+        self.view().nikon_lens_type()
+    }
+
+    /// Number of shutter actuations of the camera
+    ///
+    /// The shutter count is only available in the MakerNote of some camera
+    /// models. For Canon, only the early EOS-1D models store it, see
+    /// [`FileInfo::shutter_count`](canon::FileInfo::shutter_count).
+    pub fn shutter_count(&self) -> Option<u32> {
+        self.view().shutter_count()
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::BTreeSet;
+
+use gufo_common::exif::{IfdId, TagIfd};
+use gufo_common::field;
+
+use crate::structure::tree::{Tree, TreeValue};
+use crate::structure::{Bytes, Document};
+use crate::{Error, Exif, Storage};
+
+/// Data removed by [`Exif::sanitize`]
+///
+/// Profiles can be combined via [`with`](Self::with). Custom profiles are
+/// created starting from [`new`](Self::new).
+///
+/// ```
+/// # use gufo_exif::SanitizeProfile;
+/// use gufo_common::field;
+///
+/// let profile = SanitizeProfile::location()
+///     .with(SanitizeProfile::device_identity())
+///     .remove_entry(field::Artist);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SanitizeProfile {
+    entries: BTreeSet<TagIfd>,
+    ifds: BTreeSet<IfdId>,
+    keep_only: Option<BTreeSet<TagIfd>>,
+}
+
+impl SanitizeProfile {
+    /// Profile that doesn't remove anything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove the GPS IFD with all location data
+    pub fn location() -> Self {
+        Self::new().remove_ifd(IfdId::Gps)
+    }
+
+    /// Remove serial numbers, owner names, and unique IDs
+    pub fn device_identity() -> Self {
+        Self::new()
+            .remove_entry(field::BodySerialNumber)
+            .remove_entry(field::LensSerialNumber)
+            .remove_entry(field::CameraSerialNumber)
+            .remove_entry(field::CameraOwnerName)
+            .remove_entry(field::ImageUniqueID)
+            .remove_entry(field::CanonCameraOwnerName)
+            .remove_entry(field::CanonSerialNumber)
+            .remove_entry(field::CanonInternalSerialNumber)
+            .remove_entry(field::NikonSerialNumber)
+            .remove_entry(field::AppleBurstUUID)
+            .remove_entry(field::AppleContentIdentifier)
+            .remove_entry(field::AppleImageUniqueID)
+    }
+
+    /// Remove the MakerNote
+    pub fn maker_notes() -> Self {
+        Self::new().remove_entry(field::MakerNote)
+    }
+
+    /// Remove everything except the orientation and the color space
+    ///
+    /// The Exif version is kept as well since it is mandatory for the Exif
+    /// IFD, which contains the color space.
+    pub fn all_but_orientation_and_color_space() -> Self {
+        Self::new().keep_only([
+            field::Orientation.into(),
+            field::ColorSpace.into(),
+            field::ExifVersion.into(),
+        ])
+    }
+
+    /// Remove the entry `tag_ifd`
+    ///
+    /// Removing an entry that points to an IFD, like the
+    /// [`MakerNote`](field::MakerNote), removes that IFD as well.
+    pub fn remove_entry(mut self, tag_ifd: impl Into<TagIfd>) -> Self {
+        self.entries.insert(tag_ifd.into());
+        self
+    }
+
+    /// Remove the IFD `ifd` with all its entries and the pointers to it
+    pub fn remove_ifd(mut self, ifd: IfdId) -> Self {
+        self.ifds.insert(ifd);
+        self
+    }
+
+    /// Remove all entries that are not listed in `entries`
+    ///
+    /// Pointers to IFDs that still contain entries are kept. Calling this
+    /// repeatedly only keeps entries that are contained in all lists.
+    pub fn keep_only(mut self, entries: impl IntoIterator<Item = TagIfd>) -> Self {
+        let entries = entries.into_iter().collect::<BTreeSet<_>>();
+        self.keep_only = Some(match self.keep_only {
+            Some(keep_only) => keep_only.intersection(&entries).copied().collect(),
+            None => entries,
+        });
+        self
+    }
+
+    /// Remove everything that is removed by `self` or `other`
+    pub fn with(mut self, other: Self) -> Self {
+        self.entries.extend(other.entries);
+        self.ifds.extend(other.ifds);
+        if let Some(keep_only) = other.keep_only {
+            self = self.keep_only(keep_only);
+        }
+        self
+    }
+
+    /// Entries to remove from a MakerNote with its own TIFF structure
+    ///
+    /// Such MakerNotes are part of the tree as a whole since offsets in them
+    /// are relative to the MakerNote. Therefore, their entries are removed in
+    /// place after the tree has been serialized, see
+    /// [`remove_embedded`](Self::remove_embedded).
+    fn embedded_entries<'a, B: Bytes<'a>>(
+        &self,
+        document: &mut Document<'a, B>,
+    ) -> Result<Vec<TagIfd>, Error> {
+        let Some(ifd_id) = document
+            .maker_note_vendor()
+            .map(IfdId::MakerNote)
+            .filter(|x| document.is_embedded(*x))
+        else {
+            return Ok(Vec::new());
+        };
+
+        let tags = document
+            .entries()?
+            .remove(&ifd_id)
+            .unwrap_or_default()
+            .into_keys()
+            .map(|tag| TagIfd::new(tag, ifd_id));
+
+        Ok(tags
+            .filter(|tag_ifd| {
+                self.entries.contains(tag_ifd)
+                    || self
+                        .keep_only
+                        .as_ref()
+                        .is_some_and(|keep_only| !keep_only.contains(tag_ifd))
+            })
+            .collect())
+    }
+
+    /// Remove `entries` from the embedded MakerNote in `raw`
+    ///
+    /// Nothing is removed if the MakerNote itself has been removed.
+    fn remove_embedded(raw: Vec<u8>, entries: &[TagIfd]) -> Result<Vec<u8>, Error> {
+        let mut raw = raw;
+        let mut document = Document::for_mut_slice(&mut raw)?;
+
+        let mut changed = false;
+        for tag_ifd in entries {
+            if document.is_embedded(tag_ifd.ifd) {
+                changed |= document.remove_embedded_entry(*tag_ifd)?;
+            }
+        }
+
+        if changed {
+            document.serialize()
+        } else {
+            Ok(raw)
+        }
+    }
+
+    /// Apply the profile to `tree` and drop unreachable IFDs
+    fn apply(&self, tree: &mut Tree) -> SanitizeReport {
+        let reachable = reachable_ifds(tree);
+        tree.ifds.retain(|ifd_id, _| reachable.contains(ifd_id));
+        let entries_before = entries(tree);
+        let ifds_before = tree.ifds.keys().copied().collect::<BTreeSet<_>>();
+
+        for ifd in &self.ifds {
+            remove_ifd(tree, *ifd);
+        }
+
+        for tag_ifd in &self.entries {
+            if let Some(ifd) = tree.ifds.get_mut(&tag_ifd.ifd) {
+                ifd.entries.remove(&tag_ifd.tag);
+            }
+        }
+
+        if let Some(keep_only) = &self.keep_only {
+            for (ifd_id, ifd) in &mut tree.ifds {
+                ifd.entries.retain(|tag, value| {
+                    matches!(value, TreeValue::Ifd { .. } | TreeValue::SubIfds { .. })
+                        || keep_only.contains(&TagIfd::new(*tag, *ifd_id))
+                });
+            }
+        }
+
+        // Removing empty IFDs can leave their parents empty
+        while let Some(empty) = tree
+            .ifds
+            .iter()
+            .find(|(ifd_id, ifd)| **ifd_id != IfdId::Primary && ifd.entries.is_empty())
+            .map(|(ifd_id, _)| *ifd_id)
+        {
+            remove_ifd(tree, empty);
+        }
+
+        let reachable = reachable_ifds(tree);
+        tree.ifds.retain(|ifd_id, _| reachable.contains(ifd_id));
+
+        let entries_after = entries(tree);
+
+        SanitizeReport {
+            removed_entries: entries_before.difference(&entries_after).copied().collect(),
+            removed_ifds: ifds_before.difference(&reachable).copied().collect(),
+        }
+    }
+}
+
+/// Data removed by [`Exif::sanitize`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SanitizeReport {
+    /// Removed entries, including the entries of removed IFDs
+    pub removed_entries: Vec<TagIfd>,
+    /// IFDs that have been removed completely
+    pub removed_ifds: Vec<IfdId>,
+}
+
+impl SanitizeReport {
+    /// Returns `true` if nothing has been removed
+    pub fn is_empty(&self) -> bool {
+        self.removed_entries.is_empty() && self.removed_ifds.is_empty()
+    }
+}
+
+impl<'a, S: Storage<'a>> Exif<'a, S> {
+    /// Remove data according to `profile`
+    ///
+    /// ```
+    /// # let data = std::fs::read("example.jpg").unwrap();
+    /// # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    /// # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+    /// use gufo_exif::SanitizeProfile;
+    ///
+    /// let mut exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+    /// let orientation = exif.orientation();
+    ///
+    /// let report = exif
+    ///     .sanitize(&SanitizeProfile::all_but_orientation_and_color_space())
+    ///     .unwrap();
+    ///
+    /// assert!(!report.is_empty());
+    /// assert!(exif.model().is_none());
+    /// assert_eq!(exif.orientation(), orientation);
+    /// ```
+    ///
+    /// The complete structure is laid out anew, as with
+    /// [`compact`](Exif::compact), such that no residues of the removed data
+    /// remain. This requires the storage to be resizable. MakerNotes with their
+    /// own TIFF structure keep their size. Entries removed from them are
+    /// overwritten with zeros instead.
+    /// MakerNotes with an unknown structure stay at their offset, unless they
+    /// are removed.
+    pub fn sanitize(&mut self, profile: &SanitizeProfile) -> Result<SanitizeReport, Error> {
+        let (raw, report) = self.read_document(|document| {
+            let removed_embedded = profile.embedded_entries(document)?;
+
+            let mut tree = document.tree()?;
+            let mut report = profile.apply(&mut tree);
+            let raw = SanitizeProfile::remove_embedded(tree.serialize()?, &removed_embedded)?;
+
+            report.removed_entries.extend(removed_embedded);
+            report.removed_entries.sort();
+
+            Ok((raw, report))
+        })?;
+
+        self.replace(raw)?;
+
+        Ok(report)
+    }
+}
+
+/// Remove an IFD, the pointers to it, and unlink it from the IFD chain
+fn remove_ifd(tree: &mut Tree, ifd_id: IfdId) {
+    let next = tree.ifds.remove(&ifd_id).and_then(|x| x.next);
+
+    for ifd in tree.ifds.values_mut() {
+        ifd.entries.retain(|_, value| match value {
+            TreeValue::Ifd { ifd, .. } => *ifd != ifd_id,
+            TreeValue::SubIfds { ifds, .. } => {
+                ifds.retain(|x| *x != ifd_id);
+                !ifds.is_empty()
+            }
+            TreeValue::Data { .. } | TreeValue::ImageData { .. } => true,
+        });
+
+        if ifd.next == Some(ifd_id) {
+            ifd.next = next;
+        }
+    }
+}
+
+/// IFDs that are written when serializing the tree
+fn reachable_ifds(tree: &Tree) -> BTreeSet<IfdId> {
+    let mut reachable = BTreeSet::new();
+    let mut pending = vec![IfdId::Primary];
+
+    while let Some(ifd_id) = pending.pop() {
+        let Some(ifd) = tree.ifds.get(&ifd_id) else {
+            continue;
+        };
+        if !reachable.insert(ifd_id) {
+            continue;
+        }
+
+        for value in ifd.entries.values() {
+            match value {
+                TreeValue::Ifd { ifd, .. } => pending.push(*ifd),
+                TreeValue::SubIfds { ifds, .. } => pending.extend(ifds),
+                TreeValue::Data { .. } | TreeValue::ImageData { .. } => {}
+            }
+        }
+        pending.extend(ifd.next);
+    }
+
+    reachable
+}
+
+/// All entries of the tree
+fn entries(tree: &Tree) -> BTreeSet<TagIfd> {
+    tree.ifds
+        .iter()
+        .flat_map(|(ifd_id, ifd)| ifd.entries.keys().map(|tag| TagIfd::new(*tag, *ifd_id)))
+        .collect()
+}
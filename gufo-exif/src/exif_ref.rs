@@ -0,0 +1,389 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use gufo_common::exif::{IfdId, Tag, TagIfd};
+use gufo_common::math::cheq;
+use indexmap::IndexMap;
+
+use crate::Error;
+use crate::maker_note::Vendor;
+use crate::structure::util::{Bytes, Endieness};
+use crate::structure::{
+    Document, EntryDataRef, EntryTyped, Lookup, ParseDiagnostic, ParseMode, Type, Typed,
+    ValueOrOffsetRef,
+};
+
+/// Read-only Exif data based on a [`&[u8]`](slice)
+///
+/// Unlike [`Exif`](crate::Exif), the data are neither copied nor changed. Only
+/// an index of the IFDs and the positions of their entries is allocated while
+/// parsing. Values are read via [`Lookup`], which also provides the
+/// convenience functions of [`Exif`](crate::Exif), or borrowed directly via
+/// [`entry_data`](Self::entry_data).
+///
+/// ```
+/// # let data = std::fs::read("example.jpg").unwrap();
+/// # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+/// # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+/// use gufo_exif::Lookup;
+///
+/// let mut exif = gufo_exif::ExifRef::for_slice(&raw_exif).unwrap();
+/// assert_eq!(exif.model().as_deref(), Some("Canon EOS 400D DIGITAL"));
+///
+/// let model = exif
+///     .entry_data(gufo_common::field::Model.into())
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(model.data, b"Canon EOS 400D DIGITAL\0");
+/// ```
+///
+/// The data are parsed via a read-only [`Document`]. In contrast to the
+/// document, further IFDs in the chain and all SubIFDs are read directly.
+#[derive(Debug, Clone)]
+pub struct ExifRef<'a> {
+    data: &'a [u8],
+    index: Cow<'a, ExifIndex>,
+}
+
+/// Positions of the IFDs and their entries without the data
+///
+/// Taken from a parsed [`Document`]. Kept by [`Exif`](crate::Exif) next to
+/// its data such that values can be read without parsing again.
+#[derive(Debug, Clone)]
+pub(crate) struct ExifIndex {
+    endieness: Endieness,
+    ifds: BTreeMap<IfdId, IfdIndex>,
+    maker_note: Option<MakerNoteIndex>,
+    /// IFDs that have been skipped while parsing
+    diagnostics: Vec<ParseDiagnostic>,
+}
+
+/// MakerNote with its own TIFF structure
+#[derive(Debug, Clone)]
+struct MakerNoteIndex {
+    vendor: Vendor,
+    /// Position of the MakerNote's structure within the Exif data
+    range: Range<usize>,
+    index: Box<ExifIndex>,
+}
+
+/// Position of an IFD and of its entries
+#[derive(Debug, Clone)]
+struct IfdIndex {
+    offset: usize,
+    entries: IndexMap<u16, EntryIndex>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EntryIndex {
+    type_: Type,
+    count: usize,
+    /// Position of the value, within the entry if it fits there
+    data_pos: usize,
+}
+
+impl<'a> ExifRef<'a> {
+    pub fn for_slice(data: &'a [u8]) -> Result<Self, Error> {
+        Self::for_slice_with_mode(data, ParseMode::default())
+    }
+
+    /// Parse with the given handling of unreadable IFDs
+    pub fn for_slice_with_mode(data: &'a [u8], mode: ParseMode) -> Result<Self, Error> {
+        let mut document = Document::for_slice_with_mode(data, mode)?;
+
+        Ok(Self {
+            data,
+            index: Cow::Owned(ExifIndex::for_document(&mut document)?),
+        })
+    }
+
+    /// Use an existing index of `data`
+    pub(crate) fn with_index(data: &'a [u8], index: &'a ExifIndex) -> Self {
+        Self {
+            data,
+            index: Cow::Borrowed(index),
+        }
+    }
+
+    /// Byte order of the data
+    pub fn endieness(&self) -> Endieness {
+        self.index.endieness
+    }
+
+    /// IFDs that have been skipped while parsing
+    ///
+    /// Unreadable IFDs in the chain after the thumbnail IFD and unreadable
+    /// SubIFDs are always skipped.
+    pub fn parse_diagnostics(&self) -> &[ParseDiagnostic] {
+        &self.index.diagnostics
+    }
+
+    /// The underlying raw Exif data
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// All IFDs with their position
+    pub fn ifds(&self) -> impl Iterator<Item = (IfdId, usize)> + '_ {
+        self.index
+            .ifds
+            .iter()
+            .map(|(ifd_id, ifd)| (*ifd_id, ifd.offset))
+    }
+
+    /// Lookup entry with arbitrary type
+    pub fn lookup(&self, tag_ifd: TagIfd) -> Result<Option<Typed>, Error> {
+        self.index.lookup(self.data, tag_ifd)
+    }
+
+    /// Raw data of an entry without copying them
+    pub fn entry_data(&self, tag_ifd: TagIfd) -> Result<Option<EntryDataRef<'a>>, Error> {
+        self.index.entry_data(self.data, tag_ifd)
+    }
+
+    pub fn entries(&self) -> BTreeMap<IfdId, BTreeMap<Tag, EntryTyped>> {
+        self.index.entries(self.data)
+    }
+
+    /// Manufacturer that defines the structure of the MakerNote
+    pub fn maker_note_vendor(&self) -> Option<Vendor> {
+        self.index.maker_note_vendor()
+    }
+
+    /// Returns `true` if the entry exists, even if its data can't be read
+    pub(crate) fn contains(&self, tag_ifd: TagIfd) -> bool {
+        let (index, _, tag_ifd) = self.index.resolve(self.data, tag_ifd);
+        index
+            .ifds
+            .get(&tag_ifd.ifd)
+            .is_some_and(|ifd| ifd.entries.contains_key(&tag_ifd.tag.0))
+    }
+
+    /// Returns `true` if `ifd` belongs to a MakerNote with its own TIFF
+    /// structure
+    pub(crate) fn is_embedded(&self, ifd: IfdId) -> bool {
+        self.index
+            .maker_note
+            .as_ref()
+            .is_some_and(|x| ifd == IfdId::MakerNote(x.vendor))
+    }
+}
+
+impl ExifIndex {
+    /// Index of all IFDs of `document`
+    ///
+    /// Reads all IFDs in the chain and all SubIFDs first.
+    pub(crate) fn for_document<'a, B: Bytes<'a>>(
+        document: &mut Document<'a, B>,
+    ) -> Result<Self, Error> {
+        document.load_all_ifds()?;
+
+        let n_entries_size = document.n_entries_size;
+        let entry_size = document.entry_size;
+        // Values that fit into the entry follow the tag, type, and count
+        let value_offset = (cheq(4) + document.index_size).check()?;
+
+        let mut ifds = BTreeMap::new();
+        for (ifd_id, (offset, ifd)) in document.ifds().iter() {
+            let mut entries = IndexMap::new();
+
+            for tag in ifd.tags() {
+                let (Some(entry), Some(n)) = (ifd.entry_ref(tag)?, ifd.stored_entry_index(tag))
+                else {
+                    continue;
+                };
+
+                let data_pos = match entry.value_or_offset {
+                    ValueOrOffsetRef::Offset(data_pos) => data_pos,
+                    ValueOrOffsetRef::Value(_) => {
+                        (cheq(*offset) + n_entries_size + cheq(n) * entry_size + value_offset)
+                            .check()?
+                    }
+                };
+
+                entries.insert(
+                    tag.0,
+                    EntryIndex {
+                        type_: entry.type_,
+                        count: entry.count,
+                        data_pos,
+                    },
+                );
+            }
+
+            ifds.insert(
+                *ifd_id,
+                IfdIndex {
+                    offset: *offset,
+                    entries,
+                },
+            );
+        }
+
+        let maker_note_len = document
+            .ifds()
+            .get(&IfdId::Exif)
+            .map(|(_, ifd)| ifd.entry_ref(Tag::MAKER_NOTE))
+            .transpose()?
+            .flatten()
+            .map(|x| x.count);
+
+        let maker_note = match (document.embedded_maker_note(), maker_note_len) {
+            (Some(maker_note), Some(len)) => {
+                // Offsets in the MakerNote count from behind the header
+                let start = (cheq(maker_note.pos) + maker_note.header.as_slice().len()).check()?;
+                let end = (cheq(maker_note.pos) + len).check()?;
+
+                Some(MakerNoteIndex {
+                    vendor: maker_note.vendor,
+                    range: start..end,
+                    index: Box::new(Self::for_document(&mut maker_note.document)?),
+                })
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            endieness: document.endieness(),
+            ifds,
+            maker_note,
+            diagnostics: document.parse_diagnostics().to_vec(),
+        })
+    }
+
+    fn lookup(&self, data: &[u8], tag_ifd: TagIfd) -> Result<Option<Typed>, Error> {
+        // Embedded MakerNotes can use a different byte order
+        let (index, data, tag_ifd) = self.resolve(data, tag_ifd);
+
+        let Some(entry) = index.entry_data_local(data, tag_ifd)? else {
+            return Ok(None);
+        };
+
+        Typed::new(entry.type_, entry.count, entry.data, index.endieness).map(Some)
+    }
+
+    fn entry_data<'d>(
+        &self,
+        data: &'d [u8],
+        tag_ifd: TagIfd,
+    ) -> Result<Option<EntryDataRef<'d>>, Error> {
+        let (index, data, tag_ifd) = self.resolve(data, tag_ifd);
+        index.entry_data_local(data, tag_ifd)
+    }
+
+    fn entries(&self, data: &[u8]) -> BTreeMap<IfdId, BTreeMap<Tag, EntryTyped>> {
+        let mut xs = BTreeMap::new();
+
+        for (ifd_id, ifd) in &self.ifds {
+            let entries = ifd
+                .entries
+                .iter()
+                .map(|(tag, entry)| {
+                    let tag_ifd = TagIfd::new(Tag(*tag), *ifd_id);
+                    let data = self.lookup(data, tag_ifd).transpose().unwrap_or_else(|| {
+                        Err(Error::Other(format!(
+                            "Couldn't find tag_ifd, but it should haven been there: {tag_ifd:?}."
+                        )))
+                    });
+
+                    let x = EntryTyped {
+                        tag_ifd,
+                        count: entry.count,
+                        type_: entry.type_,
+                        data,
+                    };
+
+                    (tag_ifd.tag, x)
+                })
+                .collect();
+
+            xs.insert(*ifd_id, entries);
+        }
+
+        if let Some(maker_note) = &self.maker_note {
+            let ifd_id = IfdId::MakerNote(maker_note.vendor);
+            let maker_note_data = data.get(maker_note.range.clone()).unwrap_or_default();
+            let mut maker_note_entries = maker_note.index.entries(maker_note_data);
+            if let Some(mut entries) = maker_note_entries.remove(&IfdId::Primary) {
+                for entry in entries.values_mut() {
+                    entry.tag_ifd.ifd = ifd_id;
+                }
+                xs.insert(ifd_id, entries);
+            }
+        }
+
+        xs
+    }
+
+    fn maker_note_vendor(&self) -> Option<Vendor> {
+        if let Some(maker_note) = &self.maker_note {
+            return Some(maker_note.vendor);
+        }
+
+        self.ifds.keys().find_map(|ifd| match ifd {
+            IfdId::MakerNote(vendor) => Some(*vendor),
+            _ => None,
+        })
+    }
+
+    /// Index and data that contain the entry and the entry's location within
+    /// them
+    ///
+    /// Entries of MakerNotes with their own TIFF structure are stored as
+    /// primary IFD of a separate index.
+    fn resolve<'d>(&self, data: &'d [u8], tag_ifd: TagIfd) -> (&Self, &'d [u8], TagIfd) {
+        match &self.maker_note {
+            Some(maker_note) if tag_ifd.ifd == IfdId::MakerNote(maker_note.vendor) => (
+                &maker_note.index,
+                data.get(maker_note.range.clone()).unwrap_or_default(),
+                TagIfd::new(tag_ifd.tag, IfdId::Primary),
+            ),
+            _ => (self, data, tag_ifd),
+        }
+    }
+
+    /// Entry data without looking into embedded MakerNotes
+    fn entry_data_local<'d>(
+        &self,
+        data: &'d [u8],
+        tag_ifd: TagIfd,
+    ) -> Result<Option<EntryDataRef<'d>>, Error> {
+        let Some((n_entry, _, entry)) = self
+            .ifds
+            .get(&tag_ifd.ifd)
+            .and_then(|ifd| ifd.entries.get_full(&tag_ifd.tag.0))
+        else {
+            return Ok(None);
+        };
+
+        let len = (cheq(entry.count) * entry.type_.size()).check()?;
+        let range = entry.data_pos..(cheq(entry.data_pos) + len).check()?;
+        let data = data.get(range.clone()).ok_or(Error::IndexNotFound(range))?;
+
+        Ok(Some(EntryDataRef {
+            n_entry,
+            count: entry.count,
+            type_: entry.type_,
+            data,
+        }))
+    }
+}
+
+impl Lookup for ExifRef<'_> {
+    fn lookup(&mut self, tag_ifd: TagIfd) -> Result<Option<Typed>, Error> {
+        ExifRef::lookup(self, tag_ifd)
+    }
+
+    fn endieness(&self) -> Endieness {
+        self.index.endieness
+    }
+
+    fn maker_note_vendor(&self) -> Option<Vendor> {
+        ExifRef::maker_note_vendor(self)
+    }
+
+    fn raw_data(&mut self, range: Range<usize>) -> Option<&[u8]> {
+        self.data.get(range)
+    }
+}
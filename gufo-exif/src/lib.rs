@@ -14,15 +14,18 @@
 //! assert_eq!(exif.model().as_deref(), Some("Canon EOS 400D DIGITAL"));
 //! ```
 //!
-//! Other values can be looked up manually:
+//! Other values can be looked up manually via [`Lookup`]:
 //!
 //! ```
 //! # let data = std::fs::read("example.jpg").unwrap();
 //! # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
 //! # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
-//! # let mut exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+//! # let exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+//! use gufo_exif::Lookup;
+//!
 //! let exposure_program = exif
-//!     .document(|document| document.lookup_short(gufo_common::field::ExposureProgram.into()))
+//!     .view()
+//!     .lookup_short(gufo_common::field::ExposureProgram.into())
 //!     .unwrap()
 //!     .unwrap();
 //! assert_eq!(exposure_program, 2);
@@ -42,6 +45,7 @@
 //!         document.lookup(gufo_common::exif::TagIfd::new(Tag(0x8822), IfdId::Exif))
 //!     })
 //!     .unwrap()
+//!     .unwrap()
 //!     .unwrap();
 //! assert_eq!(exposure_program, gufo_exif::Typed::Short(vec![2]));
 //! ```
@@ -70,13 +74,24 @@
 //! let raw_exif = exif.serialize();
 //! ```
 
+mod builder;
+mod diff;
 mod error;
 mod exif;
+mod exif_ref;
+pub mod maker_note;
 pub mod structure;
 
+pub use builder::ExifBuilder;
+pub use diff::{EntryChange, EntryContent, EntryDiff, EntryValue, ExifDiff, StructureChange, diff};
 pub use error::Error;
-pub use exif::{Exif, Storage};
-pub use structure::Typed;
+#[cfg(feature = "chrono")]
+pub use exif::DateTimeField;
+#[cfg(feature = "serde")]
+pub use exif::{EntryExport, ExifExport, IfdExport, ValueExport};
+pub use exif::{Exif, SanitizeProfile, SanitizeReport, Storage};
+pub use exif_ref::ExifRef;
+pub use structure::{EntryDataRef, Lookup, Typed};
 
 /// Version of [`Exif`] based on a [`Vec<u8>`].
 pub type ExifOwned = Exif<'static, exif::OwnedStore>;
@@ -0,0 +1,78 @@
+//! Manufacturer specific MakerNote data
+//!
+//! The MakerNote is stored in the [`MakerNote`](gufo_common::field::MakerNote)
+//! entry of the Exif IFD. Its structure is defined by the camera manufacturer.
+//! The vendor is detected when parsing and determines the
+//! [`IfdId::MakerNote`](gufo_common::exif::IfdId::MakerNote) namespace the
+//! entries are stored in.
+//!
+//! ```
+//! # let data = std::fs::read("example.jpg").unwrap();
+//! # let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+//! # let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+//! let exif = gufo_exif::Exif::for_vec(raw_exif).unwrap();
+//! assert_eq!(
+//!     exif.maker_note_vendor(),
+//!     Some(gufo_exif::maker_note::Vendor::Canon)
+//! );
+//!
+//! let camera_settings = exif.canon_camera_settings().unwrap();
+//! assert_eq!(camera_settings.focal_length_range(), Some((17., 70.)));
+//!
+//! let shot_info = exif.canon_shot_info().unwrap();
+//! assert_eq!(shot_info.camera_temperature_celsius(), Some(29));
+//! ```
+
+pub mod apple;
+pub mod canon;
+pub mod nikon;
+
+pub use gufo_common::exif::Vendor;
+
+use crate::structure::util::Endieness;
+
+/// Number of bytes at the start of the MakerNote that are used for detection
+pub(crate) const HEADER_LEN: usize = 16;
+
+/// How the MakerNote is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Layout {
+    /// Plain IFD with offsets relative to the TIFF header of the Exif data
+    Ifd,
+    /// Separate TIFF structure behind a header of `header_len` bytes
+    ///
+    /// Offsets are relative to the embedded TIFF header. Therefore, the
+    /// MakerNote can only be moved as a whole.
+    EmbeddedTiff { header_len: usize },
+    /// IFD at `ifd_pos` with offsets relative to the start of the MakerNote
+    ///
+    /// The byte order can differ from the Exif data. Like
+    /// [`EmbeddedTiff`](Self::EmbeddedTiff), the MakerNote can only be moved as
+    /// a whole.
+    RelativeIfd {
+        endieness: Endieness,
+        ifd_pos: usize,
+    },
+}
+
+/// Detect the manufacturer that defines the MakerNote structure
+///
+/// The `make` is the raw value of the [`Make`](gufo_common::field::Make)
+/// entry and `header` are the first bytes of the MakerNote. The `endieness`
+/// is the one of the Exif data.
+pub(crate) fn detect(make: Option<&[u8]>, header: &[u8], endieness: Endieness) -> (Vendor, Layout) {
+    let make = make.unwrap_or_default();
+
+    if let Some(header_len) = nikon::embedded_tiff_header_len(header) {
+        (Vendor::Nikon, Layout::EmbeddedTiff { header_len })
+    } else if let Some((endieness, ifd_pos)) = apple::relative_ifd(header) {
+        (Vendor::Apple, Layout::RelativeIfd { endieness, ifd_pos })
+    } else if make.starts_with(b"Canon") && canon::is_ifd(header, endieness) {
+        (Vendor::Canon, Layout::Ifd)
+    } else if make.starts_with(b"NIKON") && !header.starts_with(nikon::HEADER_MAGIC) {
+        // Older Nikon models store the MakerNote as plain IFD
+        (Vendor::Nikon, Layout::Ifd)
+    } else {
+        (Vendor::Unknown, Layout::Ifd)
+    }
+}
@@ -0,0 +1,52 @@
+//! Apple MakerNote
+//!
+//! iOS devices store the MakerNote as IFD behind the header `Apple iOS\0`, two
+//! bytes for the version, and the byte order as `MM` or `II`. All offsets
+//! within the MakerNote are relative to its start.
+//!
+//! The entries are available under
+//! [`IfdId::MakerNote(Vendor::Apple)`](gufo_common::exif::IfdId::MakerNote).
+
+use crate::structure::util::Endieness;
+
+/// Magic bytes at the start of the MakerNote
+pub const HEADER_MAGIC: &[u8] = b"Apple iOS\0";
+
+/// Position of the byte order in the MakerNote
+const BYTE_ORDER_POS: usize = 12;
+
+/// Position of the IFD in the MakerNote
+const IFD_POS: usize = 14;
+
+/// Byte order and position of the IFD
+pub(crate) fn relative_ifd(header: &[u8]) -> Option<(Endieness, usize)> {
+    if !header.starts_with(HEADER_MAGIC) {
+        return None;
+    }
+
+    let endieness = match header.get(BYTE_ORDER_POS..IFD_POS)? {
+        b"MM" => Endieness::Big,
+        b"II" => Endieness::Litte,
+        _ => return None,
+    };
+
+    Some((endieness, IFD_POS))
+}
+
+/// Decoded [`AppleAccelerationVector`](gufo_common::field::AppleAccelerationVector)
+///
+/// Values are in units of g. As viewed from the front of the device, positive
+/// X points to the left, positive Y to the bottom, and positive Z into the
+/// screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AccelerationVector {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl From<[f64; 3]> for AccelerationVector {
+    fn from([x, y, z]: [f64; 3]) -> Self {
+        Self { x, y, z }
+    }
+}
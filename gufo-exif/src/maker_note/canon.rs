@@ -0,0 +1,617 @@
+//! Canon MakerNote
+//!
+//! Canon stores the MakerNote as plain IFD without a header. Offsets are
+//! relative to the start of the TIFF header like for all other IFDs.
+//!
+//! Several entries, like
+//! [`CanonCameraSettings`](gufo_common::field::CanonCameraSettings), contain an
+//! array of shorts where each index has a specific meaning. The types in this
+//! module decode these arrays. Values that are not present in the array are
+//! `None`.
+
+use crate::structure::util::Endieness;
+
+/// Largest number of entries expected in the MakerNote IFD
+const MAX_ENTRIES: u16 = 512;
+
+/// Returns `true` if `header` looks like the start of an IFD
+///
+/// Canon MakerNotes don't have a header that identifies them. Instead, the
+/// number of entries and the type of the first entry are checked.
+pub(crate) fn is_ifd(header: &[u8], endieness: Endieness) -> bool {
+    let (Some(n_entries), Some(type_)) = (header.get(0..2), header.get(4..6)) else {
+        return false;
+    };
+
+    let n_entries = endieness.u16_from_bytes(n_entries).unwrap_or_default();
+    let type_ = endieness.u16_from_bytes(type_).unwrap_or_default();
+
+    (1..=MAX_ENTRIES).contains(&n_entries) && (1..=13).contains(&type_)
+}
+
+/// Decoded [`CanonCameraSettings`](gufo_common::field::CanonCameraSettings)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CameraSettings {
+    pub macro_mode: Option<i16>,
+    /// Self timer in 1/10 seconds
+    pub self_timer: Option<i16>,
+    pub quality: Option<i16>,
+    pub flash_mode: Option<i16>,
+    pub continuous_drive: Option<i16>,
+    pub focus_mode: Option<i16>,
+    pub record_mode: Option<i16>,
+    pub image_size: Option<i16>,
+    pub easy_mode: Option<i16>,
+    pub digital_zoom: Option<i16>,
+    pub contrast: Option<i16>,
+    pub saturation: Option<i16>,
+    pub sharpness: Option<i16>,
+    pub camera_iso: Option<i16>,
+    pub metering_mode: Option<i16>,
+    pub focus_range: Option<i16>,
+    pub af_point: Option<u16>,
+    pub exposure_mode: Option<i16>,
+    /// Canon specific identifier of the lens type, see
+    /// [`lens_name`](Self::lens_name)
+    pub lens_type: Option<LensType>,
+    /// Longest focal length of the lens in [`focal_units`](Self::focal_units)
+    pub max_focal_length: Option<u16>,
+    /// Shortest focal length of the lens in [`focal_units`](Self::focal_units)
+    pub min_focal_length: Option<u16>,
+    /// Focal units per mm
+    pub focal_units: Option<i16>,
+    /// Largest aperture of the lens in Canon EV, see [`aperture`]
+    pub max_aperture: Option<i16>,
+    /// Smallest aperture of the lens in Canon EV, see [`aperture`]
+    pub min_aperture: Option<i16>,
+    pub flash_activity: Option<i16>,
+    pub flash_bits: Option<u16>,
+    pub focus_continuous: Option<i16>,
+    pub ae_setting: Option<i16>,
+    pub image_stabilization: Option<i16>,
+    pub display_aperture: Option<i16>,
+    pub zoom_source_width: Option<i16>,
+    pub zoom_target_width: Option<i16>,
+    pub spot_metering_mode: Option<i16>,
+    pub photo_effect: Option<i16>,
+    pub manual_flash_output: Option<i16>,
+    pub color_tone: Option<i16>,
+    pub sraw_quality: Option<i16>,
+}
+
+impl CameraSettings {
+    pub fn from_shorts(values: &[u16]) -> Self {
+        Self {
+            macro_mode: signed(values, 1),
+            self_timer: signed(values, 2),
+            quality: signed(values, 3),
+            flash_mode: signed(values, 4),
+            continuous_drive: signed(values, 5),
+            focus_mode: signed(values, 7),
+            record_mode: signed(values, 9),
+            image_size: signed(values, 10),
+            easy_mode: signed(values, 11),
+            digital_zoom: signed(values, 12),
+            contrast: signed(values, 13),
+            saturation: signed(values, 14),
+            sharpness: signed(values, 15),
+            camera_iso: signed(values, 16),
+            metering_mode: signed(values, 17),
+            focus_range: signed(values, 18),
+            af_point: unsigned(values, 19),
+            exposure_mode: signed(values, 20),
+            lens_type: unsigned(values, 22).map(LensType),
+            max_focal_length: unsigned(values, 23),
+            min_focal_length: unsigned(values, 24),
+            focal_units: signed(values, 25),
+            max_aperture: signed(values, 26),
+            min_aperture: signed(values, 27),
+            flash_activity: signed(values, 28),
+            flash_bits: unsigned(values, 29),
+            focus_continuous: signed(values, 32),
+            ae_setting: signed(values, 33),
+            image_stabilization: signed(values, 34),
+            display_aperture: signed(values, 35),
+            zoom_source_width: signed(values, 36),
+            zoom_target_width: signed(values, 37),
+            spot_metering_mode: signed(values, 39),
+            photo_effect: signed(values, 40),
+            manual_flash_output: signed(values, 41),
+            color_tone: signed(values, 42),
+            sraw_quality: signed(values, 46),
+        }
+    }
+
+    /// Shortest and longest focal length of the lens in mm
+    pub fn focal_length_range(&self) -> Option<(f64, f64)> {
+        let units = f64::from(self.focal_units.filter(|x| *x > 0).unwrap_or(1));
+        let min = f64::from(self.min_focal_length.filter(|x| *x > 0)?);
+        let max = f64::from(self.max_focal_length.filter(|x| *x > 0)?);
+
+        Some((min / units, max / units))
+    }
+
+    /// Name of the lens
+    ///
+    /// Only known for some lenses, see [`LensType::name`].
+    pub fn lens_name(&self) -> Option<&'static str> {
+        self.lens_type?.name(self.focal_length_range())
+    }
+
+    /// Largest aperture of the lens as f-number
+    pub fn max_f_number(&self) -> Option<f64> {
+        self.max_aperture.filter(|x| *x > 0).map(aperture)
+    }
+
+    /// Smallest aperture of the lens as f-number
+    pub fn min_f_number(&self) -> Option<f64> {
+        self.min_aperture.filter(|x| *x > 0).map(aperture)
+    }
+}
+
+/// Canon specific identifier of the lens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LensType(pub u16);
+
+impl LensType {
+    /// No lens information available, like for manual lenses
+    pub const NOT_AVAILABLE: Self = Self(0xFFFF);
+    /// Shared by all RF lenses, which are therefore not resolved by
+    /// [`name`](Self::name)
+    pub const RF: Self = Self(61182);
+
+    /// Name of the lens
+    ///
+    /// Some identifiers are shared by several lenses, often by third party
+    /// lenses. Therefore, a lens is only identified if it matches the
+    /// `focal_length_range` given by [`CameraSettings::focal_length_range`].
+    /// If the range is unknown, only the identifier is used. Canon EF, EF-S,
+    /// and EF-M lenses are known, apart from combinations with extenders.
+    /// Other identifiers can be resolved via the raw value in `self.0`.
+    ///
+    /// ```
+    /// use gufo_exif::maker_note::canon::LensType;
+    ///
+    /// assert_eq!(
+    ///     LensType(1).name(Some((50., 50.))),
+    ///     Some("Canon EF 50mm f/1.8")
+    /// );
+    /// assert_eq!(LensType(1).name(Some((17., 70.))), None);
+    /// ```
+    pub fn name(self, focal_length_range: Option<(f64, f64)>) -> Option<&'static str> {
+        LENSES
+            .iter()
+            .find(|(id, min, max, _)| {
+                *id == self.0
+                    && focal_length_range.is_none_or(|(range_min, range_max)| {
+                        (range_min - f64::from(*min)).abs() < 1.
+                            && (range_max - f64::from(*max)).abs() < 1.
+                    })
+            })
+            .map(|(.., name)| *name)
+    }
+}
+
+/// Known lenses with identifier, shortest and longest focal length in mm, and
+/// name
+const LENSES: &[(u16, u16, u16, &str)] = &[
+    (1, 50, 50, "Canon EF 50mm f/1.8"),
+    (2, 28, 28, "Canon EF 28mm f/2.8"),
+    (3, 135, 135, "Canon EF 135mm f/2.8 Soft"),
+    (4, 35, 105, "Canon EF 35-105mm f/3.5-4.5"),
+    (5, 35, 70, "Canon EF 35-70mm f/3.5-4.5"),
+    (6, 28, 70, "Canon EF 28-70mm f/3.5-4.5"),
+    (7, 100, 300, "Canon EF 100-300mm f/5.6L"),
+    (8, 100, 300, "Canon EF 100-300mm f/5.6"),
+    (9, 70, 210, "Canon EF 70-210mm f/4"),
+    (10, 50, 50, "Canon EF 50mm f/2.5 Macro"),
+    (11, 35, 35, "Canon EF 35mm f/2"),
+    (13, 15, 15, "Canon EF 15mm f/2.8 Fisheye"),
+    (14, 50, 200, "Canon EF 50-200mm f/3.5-4.5L"),
+    (15, 50, 200, "Canon EF 50-200mm f/3.5-4.5"),
+    (16, 35, 135, "Canon EF 35-135mm f/3.5-4.5"),
+    (17, 35, 70, "Canon EF 35-70mm f/3.5-4.5A"),
+    (18, 28, 70, "Canon EF 28-70mm f/3.5-4.5"),
+    (20, 100, 200, "Canon EF 100-200mm f/4.5A"),
+    (21, 80, 200, "Canon EF 80-200mm f/2.8L"),
+    (22, 20, 35, "Canon EF 20-35mm f/2.8L"),
+    (23, 35, 105, "Canon EF 35-105mm f/3.5-4.5"),
+    (24, 35, 80, "Canon EF 35-80mm f/4-5.6 Power Zoom"),
+    (25, 35, 80, "Canon EF 35-80mm f/4-5.6 Power Zoom"),
+    (26, 100, 100, "Canon EF 100mm f/2.8 Macro"),
+    (27, 35, 80, "Canon EF 35-80mm f/4-5.6"),
+    (28, 80, 200, "Canon EF 80-200mm f/4.5-5.6"),
+    (29, 50, 50, "Canon EF 50mm f/1.8 II"),
+    (30, 35, 105, "Canon EF 35-105mm f/4.5-5.6"),
+    (31, 75, 300, "Canon EF 75-300mm f/4-5.6"),
+    (32, 24, 24, "Canon EF 24mm f/2.8"),
+    (35, 35, 80, "Canon EF 35-80mm f/4-5.6"),
+    (36, 38, 76, "Canon EF 38-76mm f/4.5-5.6"),
+    (37, 35, 80, "Canon EF 35-80mm f/4-5.6"),
+    (38, 80, 200, "Canon EF 80-200mm f/4.5-5.6 II"),
+    (39, 75, 300, "Canon EF 75-300mm f/4-5.6"),
+    (40, 28, 80, "Canon EF 28-80mm f/3.5-5.6"),
+    (41, 28, 90, "Canon EF 28-90mm f/4-5.6"),
+    (42, 28, 200, "Canon EF 28-200mm f/3.5-5.6"),
+    (43, 28, 105, "Canon EF 28-105mm f/4-5.6"),
+    (44, 90, 300, "Canon EF 90-300mm f/4.5-5.6"),
+    (45, 18, 55, "Canon EF-S 18-55mm f/3.5-5.6"),
+    (46, 28, 90, "Canon EF 28-90mm f/4-5.6"),
+    (48, 18, 55, "Canon EF-S 18-55mm f/3.5-5.6 IS"),
+    (49, 55, 250, "Canon EF-S 55-250mm f/4-5.6 IS"),
+    (50, 18, 200, "Canon EF-S 18-200mm f/3.5-5.6 IS"),
+    (51, 18, 135, "Canon EF-S 18-135mm f/3.5-5.6 IS"),
+    (52, 18, 55, "Canon EF-S 18-55mm f/3.5-5.6 IS II"),
+    (53, 18, 55, "Canon EF-S 18-55mm f/3.5-5.6 III"),
+    (54, 55, 250, "Canon EF-S 55-250mm f/4-5.6 IS II"),
+    (94, 17, 17, "Canon TS-E 17mm f/4L"),
+    (95, 24, 24, "Canon TS-E 24mm f/3.5L II"),
+    (124, 65, 65, "Canon MP-E 65mm f/2.8 1-5x Macro Photo"),
+    (125, 24, 24, "Canon TS-E 24mm f/3.5L"),
+    (126, 45, 45, "Canon TS-E 45mm f/2.8"),
+    (127, 90, 90, "Canon TS-E 90mm f/2.8"),
+    (129, 300, 300, "Canon EF 300mm f/2.8L USM"),
+    (130, 50, 50, "Canon EF 50mm f/1.0L USM"),
+    (131, 28, 80, "Canon EF 28-80mm f/2.8-4L USM"),
+    (131, 17, 70, "Sigma 17-70mm f/2.8-4.5 DC Macro"),
+    (132, 1200, 1200, "Canon EF 1200mm f/5.6L USM"),
+    (134, 600, 600, "Canon EF 600mm f/4L IS USM"),
+    (135, 200, 200, "Canon EF 200mm f/1.8L USM"),
+    (136, 300, 300, "Canon EF 300mm f/2.8L USM"),
+    (137, 85, 85, "Canon EF 85mm f/1.2L USM"),
+    (138, 28, 80, "Canon EF 28-80mm f/2.8-4L"),
+    (139, 400, 400, "Canon EF 400mm f/2.8L USM"),
+    (140, 500, 500, "Canon EF 500mm f/4.5L USM"),
+    (141, 500, 500, "Canon EF 500mm f/4.5L USM"),
+    (142, 300, 300, "Canon EF 300mm f/2.8L IS USM"),
+    (143, 500, 500, "Canon EF 500mm f/4L IS USM"),
+    (144, 35, 135, "Canon EF 35-135mm f/4-5.6 USM"),
+    (145, 100, 300, "Canon EF 100-300mm f/4.5-5.6 USM"),
+    (146, 70, 210, "Canon EF 70-210mm f/3.5-4.5 USM"),
+    (147, 35, 135, "Canon EF 35-135mm f/4-5.6 USM"),
+    (148, 28, 80, "Canon EF 28-80mm f/3.5-5.6 USM"),
+    (149, 100, 100, "Canon EF 100mm f/2 USM"),
+    (150, 14, 14, "Canon EF 14mm f/2.8L USM"),
+    (151, 200, 200, "Canon EF 200mm f/2.8L USM"),
+    (152, 300, 300, "Canon EF 300mm f/4L IS USM"),
+    (153, 35, 350, "Canon EF 35-350mm f/3.5-5.6L USM"),
+    (154, 20, 20, "Canon EF 20mm f/2.8 USM"),
+    (155, 85, 85, "Canon EF 85mm f/1.8 USM"),
+    (156, 28, 105, "Canon EF 28-105mm f/3.5-4.5 USM"),
+    (160, 20, 35, "Canon EF 20-35mm f/3.5-4.5 USM"),
+    (161, 28, 70, "Canon EF 28-70mm f/2.8L USM"),
+    (162, 200, 200, "Canon EF 200mm f/2.8L USM"),
+    (163, 300, 300, "Canon EF 300mm f/4L"),
+    (164, 400, 400, "Canon EF 400mm f/5.6L"),
+    (165, 70, 200, "Canon EF 70-200mm f/2.8L USM"),
+    (168, 28, 28, "Canon EF 28mm f/1.8 USM"),
+    (169, 17, 35, "Canon EF 17-35mm f/2.8L USM"),
+    (170, 200, 200, "Canon EF 200mm f/2.8L II USM"),
+    (171, 300, 300, "Canon EF 300mm f/4L USM"),
+    (172, 400, 400, "Canon EF 400mm f/5.6L USM"),
+    (173, 180, 180, "Canon EF 180mm Macro f/3.5L USM"),
+    (174, 135, 135, "Canon EF 135mm f/2L USM"),
+    (175, 400, 400, "Canon EF 400mm f/2.8L USM"),
+    (176, 24, 85, "Canon EF 24-85mm f/3.5-4.5 USM"),
+    (177, 300, 300, "Canon EF 300mm f/4L IS USM"),
+    (178, 28, 135, "Canon EF 28-135mm f/3.5-5.6 IS"),
+    (179, 24, 24, "Canon EF 24mm f/1.4L USM"),
+    (180, 35, 35, "Canon EF 35mm f/1.4L USM"),
+    (183, 100, 400, "Canon EF 100-400mm f/4.5-5.6L IS USM"),
+    (185, 600, 600, "Canon EF 600mm f/4L IS USM"),
+    (186, 70, 200, "Canon EF 70-200mm f/4L USM"),
+    (190, 100, 100, "Canon EF 100mm f/2.8 Macro USM"),
+    (191, 400, 400, "Canon EF 400mm f/4 DO IS USM"),
+    (193, 35, 80, "Canon EF 35-80mm f/4-5.6 USM"),
+    (194, 80, 200, "Canon EF 80-200mm f/4.5-5.6 USM"),
+    (195, 35, 105, "Canon EF 35-105mm f/4.5-5.6 USM"),
+    (196, 75, 300, "Canon EF 75-300mm f/4-5.6 USM"),
+    (197, 75, 300, "Canon EF 75-300mm f/4-5.6 IS USM"),
+    (198, 50, 50, "Canon EF 50mm f/1.4 USM"),
+    (199, 28, 80, "Canon EF 28-80mm f/3.5-5.6 USM"),
+    (200, 75, 300, "Canon EF 75-300mm f/4-5.6 USM"),
+    (201, 28, 80, "Canon EF 28-80mm f/3.5-5.6 USM"),
+    (202, 28, 80, "Canon EF 28-80mm f/3.5-5.6 USM IV"),
+    (208, 22, 55, "Canon EF 22-55mm f/4-5.6 USM"),
+    (209, 55, 200, "Canon EF 55-200mm f/4.5-5.6"),
+    (210, 28, 90, "Canon EF 28-90mm f/4-5.6 USM"),
+    (211, 28, 200, "Canon EF 28-200mm f/3.5-5.6 USM"),
+    (212, 28, 105, "Canon EF 28-105mm f/4-5.6 USM"),
+    (213, 90, 300, "Canon EF 90-300mm f/4.5-5.6 USM"),
+    (214, 18, 55, "Canon EF-S 18-55mm f/3.5-5.6 USM"),
+    (215, 55, 200, "Canon EF 55-200mm f/4.5-5.6 II USM"),
+    (224, 70, 200, "Canon EF 70-200mm f/2.8L IS USM"),
+    (228, 28, 105, "Canon EF 28-105mm f/3.5-4.5 USM"),
+    (229, 16, 35, "Canon EF 16-35mm f/2.8L USM"),
+    (230, 24, 70, "Canon EF 24-70mm f/2.8L USM"),
+    (231, 17, 40, "Canon EF 17-40mm f/4L USM"),
+    (232, 70, 300, "Canon EF 70-300mm f/4.5-5.6 DO IS USM"),
+    (233, 28, 300, "Canon EF 28-300mm f/3.5-5.6L IS USM"),
+    (234, 17, 85, "Canon EF-S 17-85mm f/4-5.6 IS USM"),
+    (235, 10, 22, "Canon EF-S 10-22mm f/3.5-4.5 USM"),
+    (236, 60, 60, "Canon EF-S 60mm f/2.8 Macro USM"),
+    (237, 24, 105, "Canon EF 24-105mm f/4L IS USM"),
+    (238, 70, 300, "Canon EF 70-300mm f/4-5.6 IS USM"),
+    (239, 85, 85, "Canon EF 85mm f/1.2L II USM"),
+    (240, 17, 55, "Canon EF-S 17-55mm f/2.8 IS USM"),
+    (241, 50, 50, "Canon EF 50mm f/1.2L USM"),
+    (242, 70, 200, "Canon EF 70-200mm f/4L IS USM"),
+    (246, 16, 35, "Canon EF 16-35mm f/2.8L II USM"),
+    (247, 14, 14, "Canon EF 14mm f/2.8L II USM"),
+    (248, 200, 200, "Canon EF 200mm f/2L IS USM"),
+    (249, 800, 800, "Canon EF 800mm f/5.6L IS USM"),
+    (250, 24, 24, "Canon EF 24mm f/1.4L II USM"),
+    (251, 70, 200, "Canon EF 70-200mm f/2.8L IS II USM"),
+    (254, 100, 100, "Canon EF 100mm f/2.8L Macro IS USM"),
+    (488, 15, 85, "Canon EF-S 15-85mm f/3.5-5.6 IS USM"),
+    (489, 70, 300, "Canon EF 70-300mm f/4-5.6L IS USM"),
+    (490, 8, 15, "Canon EF 8-15mm f/4L Fisheye USM"),
+    (491, 300, 300, "Canon EF 300mm f/2.8L IS II USM"),
+    (492, 400, 400, "Canon EF 400mm f/2.8L IS II USM"),
+    (493, 500, 500, "Canon EF 500mm f/4L IS II USM"),
+    (493, 24, 105, "Canon EF 24-105mm f/4L IS USM"),
+    (494, 600, 600, "Canon EF 600mm f/4L IS II USM"),
+    (495, 24, 70, "Canon EF 24-70mm f/2.8L II USM"),
+    (496, 200, 400, "Canon EF 200-400mm f/4L IS USM"),
+    (502, 28, 28, "Canon EF 28mm f/2.8 IS USM"),
+    (503, 24, 24, "Canon EF 24mm f/2.8 IS USM"),
+    (504, 24, 70, "Canon EF 24-70mm f/4L IS USM"),
+    (505, 35, 35, "Canon EF 35mm f/2 IS USM"),
+    (506, 400, 400, "Canon EF 400mm f/4 DO IS II USM"),
+    (507, 16, 35, "Canon EF 16-35mm f/4L IS USM"),
+    (508, 11, 24, "Canon EF 11-24mm f/4L USM"),
+    (747, 100, 400, "Canon EF 100-400mm f/4.5-5.6L IS II USM"),
+    (750, 35, 35, "Canon EF 35mm f/1.4L II USM"),
+    (751, 16, 35, "Canon EF 16-35mm f/2.8L III USM"),
+    (752, 24, 105, "Canon EF 24-105mm f/4L IS II USM"),
+    (753, 85, 85, "Canon EF 85mm f/1.4L IS USM"),
+    (754, 70, 200, "Canon EF 70-200mm f/4L IS II USM"),
+    (757, 400, 400, "Canon EF 400mm f/2.8L IS III USM"),
+    (758, 600, 600, "Canon EF 600mm f/4L IS III USM"),
+    (4142, 18, 135, "Canon EF-S 18-135mm f/3.5-5.6 IS STM"),
+    (4143, 18, 55, "Canon EF-M 18-55mm f/3.5-5.6 IS STM"),
+    (4144, 40, 40, "Canon EF 40mm f/2.8 STM"),
+    (4145, 22, 22, "Canon EF-M 22mm f/2 STM"),
+    (4146, 18, 55, "Canon EF-S 18-55mm f/3.5-5.6 IS STM"),
+    (4147, 11, 22, "Canon EF-M 11-22mm f/4-5.6 IS STM"),
+    (4148, 55, 250, "Canon EF-S 55-250mm f/4-5.6 IS STM"),
+    (4149, 55, 200, "Canon EF-M 55-200mm f/4.5-6.3 IS STM"),
+    (4150, 10, 18, "Canon EF-S 10-18mm f/4.5-5.6 IS STM"),
+    (4152, 24, 105, "Canon EF 24-105mm f/3.5-5.6 IS STM"),
+    (4153, 15, 45, "Canon EF-M 15-45mm f/3.5-6.3 IS STM"),
+    (4154, 24, 24, "Canon EF-S 24mm f/2.8 STM"),
+    (4155, 28, 28, "Canon EF-M 28mm f/3.5 Macro IS STM"),
+    (4156, 50, 50, "Canon EF 50mm f/1.8 STM"),
+    (4157, 18, 150, "Canon EF-M 18-150mm f/3.5-6.3 IS STM"),
+    (4158, 18, 55, "Canon EF-S 18-55mm f/4-5.6 IS STM"),
+    (4159, 32, 32, "Canon EF-M 32mm f/1.4 STM"),
+    (4160, 35, 35, "Canon EF-S 35mm f/2.8 Macro IS STM"),
+    (36910, 70, 300, "Canon EF 70-300mm f/4-5.6 IS II USM"),
+    (36912, 18, 135, "Canon EF-S 18-135mm f/3.5-5.6 IS USM"),
+];
+
+/// Decoded [`CanonFocalLength`](gufo_common::field::CanonFocalLength)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FocalLength {
+    /// Fixed (1) or zoom (2) lens
+    pub focal_type: Option<u16>,
+    /// Focal length in focal units, see [`CameraSettings::focal_units`]
+    pub focal_length: Option<u16>,
+    pub focal_plane_x_size: Option<u16>,
+    pub focal_plane_y_size: Option<u16>,
+}
+
+impl FocalLength {
+    pub fn from_shorts(values: &[u16]) -> Self {
+        Self {
+            focal_type: unsigned(values, 0),
+            focal_length: unsigned(values, 1),
+            focal_plane_x_size: unsigned(values, 2),
+            focal_plane_y_size: unsigned(values, 3),
+        }
+    }
+}
+
+/// Decoded [`CanonShotInfo`](gufo_common::field::CanonShotInfo)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShotInfo {
+    pub auto_iso: Option<i16>,
+    pub base_iso: Option<i16>,
+    pub measured_ev: Option<i16>,
+    pub target_aperture: Option<i16>,
+    pub target_exposure_time: Option<i16>,
+    pub exposure_compensation: Option<i16>,
+    pub white_balance: Option<i16>,
+    pub slow_shutter: Option<i16>,
+    /// Number of the image in a continuous burst
+    pub sequence_number: Option<i16>,
+    pub optical_zoom_code: Option<i16>,
+    /// Camera temperature with an offset of 128, see
+    /// [`camera_temperature_celsius`](Self::camera_temperature_celsius)
+    pub camera_temperature: Option<i16>,
+    pub flash_guide_number: Option<i16>,
+    pub af_points_in_focus: Option<u16>,
+    pub flash_exposure_compensation: Option<i16>,
+    pub auto_exposure_bracketing: Option<i16>,
+    pub aeb_bracket_value: Option<i16>,
+    pub control_mode: Option<i16>,
+    /// Upper focus distance in 1/100 m
+    pub focus_distance_upper: Option<u16>,
+    /// Lower focus distance in 1/100 m
+    pub focus_distance_lower: Option<u16>,
+    /// Aperture in Canon EV, see [`f_number`](Self::f_number)
+    pub f_number_ev: Option<i16>,
+    /// Exposure time in Canon EV, see [`exposure_time`](Self::exposure_time)
+    pub exposure_time_ev: Option<i16>,
+    pub measured_ev2: Option<i16>,
+    pub bulb_duration: Option<i16>,
+    pub camera_type: Option<i16>,
+    pub auto_rotate: Option<i16>,
+    pub nd_filter: Option<i16>,
+    pub self_timer2: Option<i16>,
+    pub flash_output: Option<i16>,
+}
+
+impl ShotInfo {
+    pub fn from_shorts(values: &[u16]) -> Self {
+        Self {
+            auto_iso: signed(values, 1),
+            base_iso: signed(values, 2),
+            measured_ev: signed(values, 3),
+            target_aperture: signed(values, 4),
+            target_exposure_time: signed(values, 5),
+            exposure_compensation: signed(values, 6),
+            white_balance: signed(values, 7),
+            slow_shutter: signed(values, 8),
+            sequence_number: signed(values, 9),
+            optical_zoom_code: signed(values, 10),
+            camera_temperature: signed(values, 12),
+            flash_guide_number: signed(values, 13),
+            af_points_in_focus: unsigned(values, 14),
+            flash_exposure_compensation: signed(values, 15),
+            auto_exposure_bracketing: signed(values, 16),
+            aeb_bracket_value: signed(values, 17),
+            control_mode: signed(values, 18),
+            focus_distance_upper: unsigned(values, 19),
+            focus_distance_lower: unsigned(values, 20),
+            f_number_ev: signed(values, 21),
+            exposure_time_ev: signed(values, 22),
+            measured_ev2: signed(values, 23),
+            bulb_duration: signed(values, 24),
+            camera_type: signed(values, 26),
+            auto_rotate: signed(values, 27),
+            nd_filter: signed(values, 28),
+            self_timer2: signed(values, 29),
+            flash_output: signed(values, 33),
+        }
+    }
+
+    /// Aperture as f-number
+    pub fn f_number(&self) -> Option<f64> {
+        self.f_number_ev.filter(|x| *x != 0).map(aperture)
+    }
+
+    /// Exposure time in seconds
+    pub fn exposure_time(&self) -> Option<f64> {
+        self.exposure_time_ev.filter(|x| *x != 0).map(exposure_time)
+    }
+
+    /// Camera temperature in °C
+    pub fn camera_temperature_celsius(&self) -> Option<i16> {
+        self.camera_temperature
+            .filter(|x| *x != 0)
+            .and_then(|x| x.checked_sub(128))
+    }
+}
+
+/// Decoded [`CanonFileInfo`](gufo_common::field::CanonFileInfo)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileInfo {
+    /// File number or shutter count, depending on the camera model
+    ///
+    /// The value is stored in the first two shorts, the first one holding the
+    /// upper bits. It is only a shutter count for the early EOS-1D models,
+    /// see [`shutter_count`](Self::shutter_count).
+    pub file_number_or_shutter_count: Option<u32>,
+    pub bracket_mode: Option<i16>,
+    pub bracket_value: Option<i16>,
+    pub bracket_shot_number: Option<i16>,
+    pub raw_jpg_quality: Option<i16>,
+    pub raw_jpg_size: Option<i16>,
+    pub long_exposure_noise_reduction: Option<i16>,
+    pub wb_bracket_mode: Option<i16>,
+    pub wb_bracket_value_ab: Option<i16>,
+    pub wb_bracket_value_gm: Option<i16>,
+    pub filter_effect: Option<i16>,
+    pub toning_effect: Option<i16>,
+    pub macro_magnification: Option<i16>,
+    pub live_view_shooting: Option<i16>,
+    /// Upper focus distance in 1/100 m
+    pub focus_distance_upper: Option<u16>,
+    /// Lower focus distance in 1/100 m
+    pub focus_distance_lower: Option<u16>,
+    pub flash_exposure_lock: Option<i16>,
+}
+
+impl FileInfo {
+    pub fn from_shorts(values: &[u16]) -> Self {
+        let file_number_or_shutter_count = unsigned(values, 1)
+            .zip(unsigned(values, 2))
+            .map(|(upper, lower)| (u32::from(upper) << 16) | u32::from(lower));
+
+        Self {
+            file_number_or_shutter_count,
+            bracket_mode: signed(values, 3),
+            bracket_value: signed(values, 4),
+            bracket_shot_number: signed(values, 5),
+            raw_jpg_quality: signed(values, 6),
+            raw_jpg_size: signed(values, 7),
+            long_exposure_noise_reduction: signed(values, 8),
+            wb_bracket_mode: signed(values, 9),
+            wb_bracket_value_ab: signed(values, 12),
+            wb_bracket_value_gm: signed(values, 13),
+            filter_effect: signed(values, 14),
+            toning_effect: signed(values, 15),
+            macro_magnification: signed(values, 16),
+            live_view_shooting: signed(values, 19),
+            focus_distance_upper: unsigned(values, 20),
+            focus_distance_lower: unsigned(values, 21),
+            flash_exposure_lock: signed(values, 25),
+        }
+    }
+
+    /// Shutter count for cameras that store it in the file info
+    ///
+    /// Only the early EOS-1D models store the shutter count in the MakerNote.
+    /// For other models, the value is a file number and `None` is returned.
+    pub fn shutter_count(&self, model: &str) -> Option<u32> {
+        let model = model.trim().trim_start_matches("Canon").trim();
+
+        let stores_shutter_count = matches!(
+            model.to_ascii_uppercase().as_str(),
+            "EOS-1D" | "EOS-1DS" | "EOS-1D MARK II" | "EOS-1DS MARK II" | "EOS-1D MARK II N"
+        );
+
+        if stores_shutter_count {
+            self.file_number_or_shutter_count
+        } else {
+            None
+        }
+    }
+}
+
+/// Convert Canon's EV encoding to EV
+///
+/// The lower five bits contain fractions in 1/32 EV. The values `0x0C` and
+/// `0x14` encode 1/3 and 2/3 EV.
+pub fn canon_ev(value: i16) -> f64 {
+    let sign = if value < 0 { -1. } else { 1. };
+    let value = value.unsigned_abs();
+
+    let whole = f64::from(value & !0x1F);
+    let frac = match value & 0x1F {
+        0x0C => 32. / 3.,
+        0x14 => 64. / 3.,
+        frac => f64::from(frac),
+    };
+
+    sign * (whole + frac) / 32.
+}
+
+/// Convert Canon EV aperture to f-number
+pub fn aperture(value: i16) -> f64 {
+    2_f64.powf(canon_ev(value) / 2.)
+}
+
+/// Convert Canon EV exposure time to seconds
+pub fn exposure_time(value: i16) -> f64 {
+    2_f64.powf(-canon_ev(value))
+}
+
+fn unsigned(values: &[u16], index: usize) -> Option<u16> {
+    values.get(index).copied()
+}
+
+fn signed(values: &[u16], index: usize) -> Option<i16> {
+    unsigned(values, index).map(u16::cast_signed)
+}
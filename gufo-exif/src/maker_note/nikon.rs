@@ -0,0 +1,62 @@
+//! Nikon MakerNote
+//!
+//! Newer Nikon cameras store the MakerNote as separate TIFF structure behind
+//! the header `Nikon\0` and two bytes for the version. All offsets within the
+//! MakerNote are relative to this embedded TIFF header. Older models store a
+//! plain IFD without header.
+//!
+//! Both variants are available under
+//! [`IfdId::MakerNote(Vendor::Nikon)`](gufo_common::exif::IfdId::MakerNote).
+
+/// Magic bytes at the start of MakerNotes with an embedded TIFF structure
+pub const HEADER_MAGIC: &[u8] = b"Nikon\0";
+
+/// Position of the embedded TIFF header in the MakerNote
+const EMBEDDED_TIFF_POS: usize = 10;
+
+/// Length of the header before the embedded TIFF structure
+pub(crate) fn embedded_tiff_header_len(header: &[u8]) -> Option<usize> {
+    let tiff_magic = header.get(EMBEDDED_TIFF_POS..EMBEDDED_TIFF_POS + 4)?;
+
+    (header.starts_with(HEADER_MAGIC)
+        && matches!(tiff_magic, b"II*\0" | b"MM\0*" | b"II+\0" | b"MM\0+"))
+    .then_some(EMBEDDED_TIFF_POS)
+}
+
+/// Decoded [`NikonLensType`](gufo_common::field::NikonLensType)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LensType {
+    /// Manual focus lens
+    pub manual_focus: bool,
+    /// Lens reports the focus distance
+    pub d: bool,
+    /// Lens without aperture ring
+    pub g: bool,
+    /// Vibration reduction
+    pub vr: bool,
+    /// Lens for Nikon 1 cameras
+    pub nikon_1: bool,
+    /// Lens used with the FT-1 mount adapter
+    pub ft_1: bool,
+    /// Electromagnetic aperture
+    pub e: bool,
+    /// Focus with a stepping motor
+    pub af_p: bool,
+}
+
+impl From<u8> for LensType {
+    fn from(value: u8) -> Self {
+        let bit = |n: u8| value & (1 << n) != 0;
+
+        Self {
+            manual_focus: bit(0),
+            d: bit(1),
+            g: bit(2),
+            vr: bit(3),
+            nikon_1: bit(4),
+            ft_1: bit(5),
+            e: bit(6),
+            af_p: bit(7),
+        }
+    }
+}
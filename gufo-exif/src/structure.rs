@@ -2,11 +2,17 @@ mod document;
 mod entry;
 mod file;
 mod ifd;
+mod lookup;
+pub(crate) mod tree;
 mod type_;
-mod util;
+pub(crate) mod util;
 
 pub use document::*;
 pub use entry::*;
 use file::*;
 pub use ifd::*;
+pub use lookup::Lookup;
+#[cfg(feature = "chrono")]
+pub(crate) use lookup::lookup_date_time;
 pub use type_::*;
+pub use util::{Bytes, Endieness};
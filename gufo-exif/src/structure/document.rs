@@ -1,29 +1,96 @@
-mod high_level;
+mod edit;
+mod lazy;
+mod maker_note;
+mod rebuild;
 mod type_lookup;
+mod validate;
 
 use std::collections::BTreeMap;
 use std::ops::Range;
 
 use gufo_common::exif::{IfdId, TagIfd};
 use gufo_common::math::cheq;
+pub(crate) use maker_note::EmbeddedMakerNote;
+pub use validate::{Finding, FindingKind, Severity};
 
 use super::Ifd;
 use crate::Error;
-use crate::structure::util::Endieness;
-use crate::structure::{Entry, Parser, Type, Typed, ValueOrOffset};
+use crate::structure::util::{Bytes, Endieness, handle_error};
+use crate::structure::{
+    Entry, Ifds, Parser, RawMakerNote, Type, Typed, ValueOrOffset, ValueOrOffsetRef,
+};
 
 /// Exif Document
 ///
 /// Access to the lower lying structures of an Exif document.
+///
+/// Documents for a [`&mut [u8]`](slice) can change the data in place. Documents
+/// for a [`&[u8]`](slice), created via [`for_slice`](Document::for_slice), can
+/// only read them.
 #[derive(Debug)]
-pub struct Document<'a> {
-    ifds: BTreeMap<IfdId, (usize, Ifd<'a>)>,
-    data: Vec<(usize, &'a mut [u8])>,
+pub struct Document<'a, B: Bytes<'a> = &'a mut [u8]> {
+    ifds: BTreeMap<IfdId, (usize, Ifd<'a, B>)>,
+    data: Vec<(usize, B)>,
     pub(crate) endieness: Endieness,
-    primary_ifd_offset: &'a mut [u8],
+    primary_ifd_offset: B,
+    /// MakerNote with its own TIFF structure
+    maker_note: Option<EmbeddedMakerNote<'a, B>>,
     pub(crate) index_size: usize,
     pub(crate) n_entries_size: usize,
     pub(crate) entry_size: usize,
+    /// IFDs that have been skipped while parsing
+    diagnostics: Vec<ParseDiagnostic>,
+}
+
+/// How to handle IFDs that can't be read while parsing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Fail if the primary, Exif, GPS, or interoperability IFD can't be read
+    ///
+    /// Unreadable MakerNotes and thumbnail IFDs are always skipped.
+    #[default]
+    Strict,
+    /// Skip IFDs that can't be read and keep everything else
+    ///
+    /// Skipped IFDs are reported via [`Document::parse_diagnostics`]. Their
+    /// data stay untouched when serializing in place. Only the primary IFD
+    /// is required.
+    Lenient,
+}
+
+/// IFD that has been skipped while parsing
+#[derive(Debug, Clone)]
+pub struct ParseDiagnostic {
+    pub ifd: IfdId,
+    /// Position the IFD should have been read from
+    pub offset: usize,
+    pub error: Error,
+}
+
+impl std::fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to read {:?} IFD at {}: {}",
+            self.ifd, self.offset, self.error
+        )
+    }
+}
+
+/// Strategy to generate raw Exif data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SerializeMode {
+    /// Keep all IFDs and data at their original position
+    ///
+    /// Only the bytes that have been changed differ from the original data.
+    #[default]
+    InPlace,
+    /// Rebuild the complete structure
+    ///
+    /// All IFDs, their data, and the pointers to sub-IFDs are written anew.
+    /// Values can change to arbitrary sizes. Data that are not referenced from
+    /// any IFD are not carried over.
+    Rebuild,
 }
 
 pub struct EntryTyped {
@@ -40,37 +107,124 @@ pub struct EntryData<'a> {
     pub data: &'a mut [u8],
 }
 
+/// Entry with its data borrowed from the Exif data
+#[derive(Debug, Clone, Copy)]
+pub struct EntryDataRef<'a> {
+    pub n_entry: usize,
+    pub count: usize,
+    pub type_: Type,
+    pub data: &'a [u8],
+}
+
 impl<'a> Document<'a> {
     pub fn for_mut_slice(data: &'a mut [u8]) -> Result<Self, Error> {
+        Self::for_mut_slice_with_mode(data, ParseMode::default())
+    }
+
+    /// Parse with the given handling of unreadable IFDs
+    pub fn for_mut_slice_with_mode(data: &'a mut [u8], mode: ParseMode) -> Result<Self, Error> {
+        Self::parse(data, mode)
+    }
+}
+
+impl<'a> Document<'a, &'a [u8]> {
+    /// Document that can only read the data
+    pub fn for_slice(data: &'a [u8]) -> Result<Self, Error> {
+        Self::for_slice_with_mode(data, ParseMode::default())
+    }
+
+    /// Parse with the given handling of unreadable IFDs
+    pub fn for_slice_with_mode(data: &'a [u8], mode: ParseMode) -> Result<Self, Error> {
+        Self::parse(data, mode)
+    }
+}
+
+impl<'a, B: Bytes<'a>> Document<'a, B> {
+    fn parse(data: B, mode: ParseMode) -> Result<Self, Error> {
         let mut file_parser = Parser::new(data)?;
 
-        let ifds = file_parser.parse()?;
+        let parsed = file_parser.parse(mode)?;
+
+        let mut document = Self::from_parser(file_parser, parsed.ifds, parsed.maker_note);
+        document.diagnostics = parsed.diagnostics;
+
+        Ok(document)
+    }
+
+    /// Document for a single IFD without TIFF header
+    ///
+    /// Offsets are relative to the start of `data`. This is used for
+    /// MakerNotes that only consist of an IFD.
+    pub(crate) fn for_relative_ifd(
+        data: B,
+        endieness: Endieness,
+        ifd_offset: usize,
+    ) -> Result<Self, Error> {
+        let mut file_parser = Parser::new_headerless(data, endieness);
+
+        let ifds = file_parser.parse_ifd(ifd_offset)?;
+
+        Ok(Self::from_parser(file_parser, ifds, None))
+    }
+
+    fn from_parser(
+        file_parser: Parser<'a, B>,
+        ifds: Ifds<'a, B>,
+        raw_maker_note: Option<RawMakerNote<B>>,
+    ) -> Self {
         let endieness = file_parser.endieness();
         let index_size = file_parser.index_size();
         let n_entries_size = file_parser.n_entries_size();
         let entry_size = file_parser.entry_size();
 
-        let (primary_ifd_offset, data) = file_parser.data();
+        let (primary_ifd_offset, mut data) = file_parser.data();
+
+        let maker_note = match raw_maker_note.map(EmbeddedMakerNote::parse) {
+            Some(Ok(maker_note)) => Some(maker_note),
+            Some(Err(raw_data)) => {
+                // Keep MakerNote as plain data if it can't be read
+                data.push(raw_data);
+                None
+            }
+            None => None,
+        };
 
-        Ok(Self {
+        Self {
             ifds,
             data,
             endieness,
             primary_ifd_offset,
+            maker_note,
             index_size,
             n_entries_size,
             entry_size,
-        })
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Byte order of the data
+    pub fn endieness(&self) -> Endieness {
+        self.endieness
+    }
+
+    /// IFDs that have been skipped in [`ParseMode::Lenient`]
+    pub fn parse_diagnostics(&self) -> &[ParseDiagnostic] {
+        &self.diagnostics
     }
 
     pub fn serialize(&mut self) -> Result<Vec<u8>, Error> {
         let mut data = self
             .data
             .iter()
-            .map(|(pos, data)| (*pos, data.to_vec()))
+            .map(|(pos, data)| (*pos, data.as_slice().to_vec()))
             .collect::<Vec<_>>();
 
-        data.push((4, self.primary_ifd_offset.to_vec()));
+        // Documents without TIFF header have no offset to the primary IFD
+        let primary_ifd_offset = self.primary_ifd_offset.as_slice();
+        if !primary_ifd_offset.is_empty() {
+            let primary_ifd_offset_pos = if self.index_size == 8 { 8 } else { 4 };
+            data.push((primary_ifd_offset_pos, primary_ifd_offset.to_vec()));
+        }
 
         let ifd_data = self
             .ifds()
@@ -80,6 +234,10 @@ impl<'a> Document<'a> {
 
         data.extend(ifd_data);
 
+        if let Some(maker_note) = &mut self.maker_note {
+            data.push((maker_note.pos, maker_note.serialize()?));
+        }
+
         data.sort_by_key(|(pos, _)| *pos);
 
         let mut vec = Vec::new();
@@ -96,16 +254,32 @@ impl<'a> Document<'a> {
         Ok(vec)
     }
 
+    /// Generate raw Exif data using the given strategy
+    pub fn serialize_with_mode(&mut self, mode: SerializeMode) -> Result<Vec<u8>, Error> {
+        match mode {
+            SerializeMode::InPlace => self.serialize(),
+            SerializeMode::Rebuild => self.tree()?.serialize(),
+        }
+    }
+
     pub fn entries(
         &mut self,
     ) -> Result<BTreeMap<IfdId, BTreeMap<gufo_common::exif::Tag, EntryTyped>>, Error> {
+        self.load_all_ifds()?;
+
         let mut entries = Vec::new();
 
-        for (ifd_id, (_, list)) in self.ifds.iter_mut() {
-            for entry in list.entries()?.values() {
-                let tag_ifd = TagIfd::new(entry.tag(), *ifd_id);
+        for (ifd_id, (_, list)) in &self.ifds {
+            let mut tags = list.tags();
+            tags.sort();
+
+            for tag in tags {
+                let Some(entry) = list.entry_ref(tag)? else {
+                    continue;
+                };
+                let tag_ifd = TagIfd::new(tag, *ifd_id);
 
-                entries.push((tag_ifd, entry.count()?, entry.type_()));
+                entries.push((tag_ifd, entry.count, entry.type_));
             }
         }
 
@@ -134,6 +308,17 @@ impl<'a> Document<'a> {
             y.insert(tag_ifd.tag, x);
         }
 
+        if let Some(maker_note) = &mut self.maker_note {
+            let ifd_id = IfdId::MakerNote(maker_note.vendor);
+            let mut maker_note_entries = maker_note.document.entries()?;
+            if let Some(mut entries) = maker_note_entries.remove(&IfdId::Primary) {
+                for entry in entries.values_mut() {
+                    entry.tag_ifd.ifd = ifd_id;
+                }
+                xs.insert(ifd_id, entries);
+            }
+        }
+
         Ok(xs)
     }
 
@@ -145,16 +330,101 @@ impl<'a> Document<'a> {
         Some(ifd_offset + entry_relative_offset)
     }
 
-    pub fn ifd(&mut self, ifd: IfdId) -> Option<&mut Ifd<'a>> {
-        Some(&mut self.ifds.get_mut(&ifd)?.1)
+    pub fn ifd(&mut self, ifd: IfdId) -> Result<Option<&mut Ifd<'a, B>>, Error> {
+        Ok(self.ifd_pos(ifd)?.map(|x| &mut x.1))
+    }
+
+    /// IFD with its position
+    ///
+    /// IFDs that have not been read yet are read first. Returns an error if
+    /// the IFD exists but can't be read.
+    pub fn ifd_pos(&mut self, ifd: IfdId) -> Result<Option<&mut (usize, Ifd<'a, B>)>, Error> {
+        self.load_ifd(ifd)?;
+        Ok(self.ifds.get_mut(&ifd))
     }
 
-    pub fn ifd_pos(&mut self, ifd: IfdId) -> Option<&mut (usize, Ifd<'a>)> {
-        self.ifds.get_mut(&ifd)
+    /// Document that contains the entry and the entry's location within it
+    ///
+    /// Entries of MakerNotes with their own TIFF structure are stored in a
+    /// separate document in the primary IFD.
+    pub(crate) fn resolve(&mut self, tag_ifd: TagIfd) -> (&mut Self, TagIfd) {
+        match (self.is_embedded(tag_ifd.ifd), self) {
+            (
+                true,
+                Self {
+                    maker_note: Some(maker_note),
+                    ..
+                },
+            ) => (
+                &mut maker_note.document,
+                TagIfd::new(tag_ifd.tag, IfdId::Primary),
+            ),
+            (_, document) => (document, tag_ifd),
+        }
     }
 
+    /// Raw data of an entry without the option to change them
+    pub fn entry_data_ref(&mut self, tag_ifd: TagIfd) -> Result<Option<EntryDataRef<'_>>, Error> {
+        let (document, tag_ifd) = self.resolve(tag_ifd);
+        document.entry_data_ref_local(tag_ifd)
+    }
+
+    /// Entry data without looking into embedded MakerNotes
+    fn entry_data_ref_local(&mut self, tag_ifd: TagIfd) -> Result<Option<EntryDataRef<'_>>, Error> {
+        self.load_ifd(tag_ifd.ifd)?;
+        let Some((_, ifd)) = self.ifds.get(&tag_ifd.ifd) else {
+            return Ok(None);
+        };
+        let Some(entry) = ifd.entry_ref(tag_ifd.tag)? else {
+            return Ok(None);
+        };
+
+        let len = (cheq(entry.count) * entry.type_.size()).check()?;
+        let data = match entry.value_or_offset {
+            ValueOrOffsetRef::Offset(offset) => {
+                let data_range = offset..(cheq(offset) + len).check()?;
+                self.data_ref(data_range.clone())
+                    .ok_or(Error::IndexNotFound(data_range))?
+            }
+            // Only return the bytes actually used by the value
+            ValueOrOffsetRef::Value(value) => value.get(..len).ok_or(Error::IndexOverflow)?,
+        };
+
+        Ok(Some(EntryDataRef {
+            n_entry: entry.n_entry,
+            count: entry.count,
+            type_: entry.type_,
+            data,
+        }))
+    }
+
+    /// Data at `range` without the option to change them
+    pub fn data_ref(&self, range: Range<usize>) -> Option<&[u8]> {
+        self.data
+            .iter()
+            .map(|(p, x)| (*p, x.as_slice()))
+            .find(|(p, x)| {
+                *p <= range.start && p.checked_add(x.len()).is_some_and(|end| range.end <= end)
+            })
+            .and_then(|(p, x)| x.get(range.start.checked_sub(p)?..range.end.checked_sub(p)?))
+    }
+
+    pub fn data_blocks(&mut self) -> &[(usize, B)] {
+        &self.data
+    }
+
+    /// All IFDs that have been read
+    ///
+    /// See [`load_all_ifds`](Self::load_all_ifds) to read all IFDs first.
+    pub fn ifds(&mut self) -> &mut BTreeMap<IfdId, (usize, Ifd<'a, B>)> {
+        &mut self.ifds
+    }
+}
+
+impl<'a> Document<'a> {
     pub fn entry(&mut self, tag_ifd: TagIfd) -> Option<(usize, Entry<'_>)> {
-        let ifd: &mut Ifd<'a> = self.ifd(tag_ifd.ifd)?;
+        let (document, tag_ifd) = self.resolve(tag_ifd);
+        let ifd: &mut Ifd<'a> = handle_error(document.ifd(tag_ifd.ifd))?;
         crate::forall_formats!(Ifd, ifd, ifd, {
             let entry = ifd.entries.get_full_mut(&tag_ifd.tag.0)?;
             Some((entry.0, entry.2.as_entry()))
@@ -162,7 +432,13 @@ impl<'a> Document<'a> {
     }
 
     pub fn entry_data(&mut self, tag_ifd: TagIfd) -> Result<Option<EntryData<'_>>, Error> {
-        let Some(ifd) = self.ifd(tag_ifd.ifd) else {
+        let (document, tag_ifd) = self.resolve(tag_ifd);
+        document.entry_data_local(tag_ifd)
+    }
+
+    /// Entry data without looking into embedded MakerNotes
+    fn entry_data_local(&mut self, tag_ifd: TagIfd) -> Result<Option<EntryData<'_>>, Error> {
+        let Some(ifd) = self.ifd(tag_ifd.ifd)? else {
             return Ok(None);
         };
 
@@ -192,7 +468,7 @@ impl<'a> Document<'a> {
             self.data(data_range.clone())
                 .ok_or(Error::IndexNotFound(data_range))?
         } else {
-            let Some(ifd) = self.ifd(tag_ifd.ifd) else {
+            let Some(ifd) = self.ifd(tag_ifd.ifd)? else {
                 return Ok(None);
             };
 
@@ -211,7 +487,9 @@ impl<'a> Document<'a> {
             };
 
             if let ValueOrOffset::Value(value) = value_or_offset {
-                value
+                // Only return the bytes actually used by the value
+                let len = (cheq(count) * type_.size()).check()?;
+                value.get_mut(..len).ok_or(Error::IndexOverflow)?
             } else {
                 unreachable!()
             }
@@ -231,12 +509,4 @@ impl<'a> Document<'a> {
             .find(|(p, x)| *p <= range.start && range.end <= p + x.len())
             .and_then(|(p, x)| x.get_mut(range.start - *p..range.end - *p))
     }
-
-    pub fn data_blocks(&mut self) -> &[(usize, &mut [u8])] {
-        &self.data
-    }
-
-    pub fn ifds(&mut self) -> &mut BTreeMap<IfdId, (usize, Ifd<'a>)> {
-        &mut self.ifds
-    }
 }
@@ -0,0 +1,224 @@
+use gufo_common::exif::{IfdId, Tag, TagIfd, Vendor};
+use gufo_common::math::cheq;
+
+use super::Document;
+use crate::Error;
+use crate::structure::{Type, Typed, ValueOrOffset};
+
+impl<'a> Document<'a> {
+    /// Move everything that points to `pos` or behind by `shift` bytes
+    ///
+    /// This covers the offset of the primary IFD in the header, the offsets to
+    /// following IFDs, pointers to sub-IFDs, offsets to entry data, and offsets
+    /// to image data. Data blocks that contain `pos`, like the MakerNote, grow
+    /// by `shift` bytes. A MakerNote with an unknown structure behind `pos` is
+    /// refused with [`Error::MakerNoteNotMovable`].
+    pub(crate) fn shift_offsets(&mut self, pos: usize, shift: usize) -> Result<(), Error> {
+        let endieness = self.endieness;
+        let shift_u32 = u32::try_from(shift)?;
+
+        let primary_ifd_offset = endieness.index_from_bytes(self.primary_ifd_offset)?;
+        if primary_ifd_offset >= pos {
+            let new_offset = (cheq(primary_ifd_offset) + shift).check()?;
+            self.primary_ifd_offset
+                .copy_from_slice(&endieness.index_to_bytes(new_offset, self.index_size)?);
+        }
+
+        // Values of data offsets are updated before the entries' own offsets
+        // change, since the data are only moved when serializing
+        let data_offset_entries = self
+            .ifds
+            .iter()
+            .filter(|(ifd_id, _)| !matches!(ifd_id, IfdId::MakerNote(_)))
+            .flat_map(|(ifd_id, (_, ifd))| {
+                ifd.tags()
+                    .into_iter()
+                    .filter(|tag| tag.is_data_offset())
+                    .map(|tag| TagIfd::new(tag, *ifd_id))
+            })
+            .collect::<Vec<_>>();
+
+        for tag_ifd in data_offset_entries {
+            let Some(typed) = self.lookup(tag_ifd)? else {
+                continue;
+            };
+
+            let shifted = match typed {
+                Typed::Short(offsets) => Typed::Short(
+                    offsets
+                        .into_iter()
+                        .map(|x| {
+                            if x as usize >= pos {
+                                Ok((cheq(x) + u16::try_from(shift)?).check()?)
+                            } else {
+                                Ok(x)
+                            }
+                        })
+                        .collect::<Result<_, Error>>()?,
+                ),
+                Typed::Long(offsets) => Typed::Long(
+                    offsets
+                        .into_iter()
+                        .map(|x| {
+                            if x as usize >= pos {
+                                (cheq(x) + shift_u32).check()
+                            } else {
+                                Ok(x)
+                            }
+                        })
+                        .collect::<Result<_, _>>()?,
+                ),
+                Typed::Long8(offsets) => Typed::Long8(
+                    offsets
+                        .into_iter()
+                        .map(|x| {
+                            if usize::try_from(x)? >= pos {
+                                Ok((cheq(x) + u64::try_from(shift)?).check()?)
+                            } else {
+                                Ok(x)
+                            }
+                        })
+                        .collect::<Result<_, Error>>()?,
+                ),
+                typed => {
+                    return Err(Error::TypeMissmatch(
+                        typed.type_(),
+                        &[Type::Short, Type::Long, Type::Long8],
+                    ));
+                }
+            };
+
+            let data = shifted.serialize(endieness);
+            let entry_data = self
+                .entry_data(tag_ifd)?
+                .ok_or_else(|| Error::other(format!("Entry {tag_ifd:?} disappeared")))?;
+            entry_data
+                .data
+                .get_mut(..data.len())
+                .ok_or(Error::IndexOverflow)?
+                .copy_from_slice(&data);
+        }
+
+        // The structure of the MakerNote is unknown and might contain absolute
+        // offsets
+        let opaque_maker_note = self.maker_note.is_none() && self.maker_note_vendor().is_none();
+
+        for (ifd_id, (_, ifd)) in self.ifds.iter_mut() {
+            let next_ifd_offset = ifd.next_ifd_offset()?;
+            if next_ifd_offset != 0 && next_ifd_offset >= pos {
+                ifd.set_next_ifd_offset((cheq(next_ifd_offset) + shift).check()?)?;
+            }
+
+            for (tag, mut entry) in ifd.entries()? {
+                let type_ = entry.type_();
+                let count = entry.count()?;
+
+                if let ValueOrOffset::Offset(offset) = entry.value_or_offset()? {
+                    let offset_end = (cheq(offset) + cheq(count) * type_.size()).check()?;
+
+                    if offset >= pos {
+                        if opaque_maker_note && *ifd_id == IfdId::Exif && tag == Tag::MAKER_NOTE {
+                            return Err(Error::MakerNoteNotMovable(IfdId::MakerNote(
+                                Vendor::Unknown,
+                            )));
+                        }
+                        entry.set_offset((cheq(offset) + shift).check()?)?;
+                    } else if offset_end > pos {
+                        if type_.size() != 1 {
+                            return Err(Error::other(format!(
+                                "Can't grow data of {tag:?} in {ifd_id:?} with type {type_:?}"
+                            )));
+                        }
+                        entry.set_count((cheq(count) + shift).check()?)?;
+                    }
+                }
+
+                // The MakerNote can use the same tags with other meanings
+                if matches!(ifd_id, IfdId::MakerNote(_)) {
+                    continue;
+                }
+
+                if tag.is_exif_specific_ifd() {
+                    let pointer = entry.ifd_pointer()?;
+                    if pointer >= pos {
+                        entry.set_ifd_pointer((cheq(pointer) + shift).check()?)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Serialize with an additional entry
+    ///
+    /// The new entry is inserted into the entry list of its IFD such that the
+    /// list stays sorted by tag. All following data is moved backwards. If the
+    /// value does not fit into the entry, it is appended at the end of the
+    /// data.
+    ///
+    /// The document itself is modified during the operation and should not be
+    /// used afterward.
+    pub(crate) fn serialize_with_new_entry(
+        &mut self,
+        tag_ifd: TagIfd,
+        value: &Typed,
+    ) -> Result<Vec<u8>, Error> {
+        let endieness = self.endieness;
+        let entry_size = self.entry_size;
+        let index_size = self.index_size;
+        let n_entries_size = self.n_entries_size;
+
+        if self.entry(tag_ifd).is_some() {
+            return Err(Error::other(format!("Entry {tag_ifd:?} already exists")));
+        }
+
+        let (ifd_pos, ifd) = self
+            .ifds
+            .get_mut(&tag_ifd.ifd)
+            .ok_or(Error::IfdNotFound(tag_ifd.ifd))?;
+
+        let tags = ifd.tags();
+        let n_entry = tags
+            .iter()
+            .position(|tag| *tag > tag_ifd.tag)
+            .unwrap_or(tags.len());
+
+        let insert_pos = (cheq(*ifd_pos) + n_entries_size + cheq(n_entry) * entry_size).check()?;
+
+        ifd.set_n_entries((cheq(tags.len()) + 1).check()?)?;
+        self.shift_offsets(insert_pos, entry_size)?;
+
+        let mut raw = self.serialize()?;
+
+        let data = value.serialize(endieness);
+        let count = value.count()?;
+
+        let mut entry = Vec::with_capacity(entry_size);
+        entry.extend_from_slice(&endieness.u16_to_bytes(tag_ifd.tag.0));
+        entry.extend_from_slice(&endieness.u16_to_bytes(value.type_().u16()));
+        entry.extend_from_slice(&endieness.index_to_bytes(count, index_size)?);
+
+        let data_offset = if data.len() > index_size {
+            // Data are stored behind all existing data, aligned to a word boundary
+            let end = (cheq(raw.len()) + entry_size).check()?;
+            let offset = (cheq(end) + end % 2).check()?;
+            entry.extend_from_slice(&endieness.index_to_bytes(offset, index_size)?);
+            Some(offset)
+        } else {
+            let mut value = data.clone();
+            value.resize(index_size, 0);
+            entry.extend_from_slice(&value);
+            None
+        };
+
+        raw.splice(insert_pos..insert_pos, entry);
+
+        if let Some(offset) = data_offset {
+            raw.resize(offset, 0);
+            raw.extend_from_slice(&data);
+        }
+
+        Ok(raw)
+    }
+}
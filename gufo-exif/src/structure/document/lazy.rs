@@ -0,0 +1,141 @@
+use gufo_common::exif::{IfdId, Tag, TagIfd};
+
+use super::{Document, ParseDiagnostic};
+use crate::Error;
+use crate::structure::util::Bytes;
+use crate::structure::{Lookup, Parser};
+
+impl<'a, B: Bytes<'a>> Document<'a, B> {
+    //! IFDs that are only read when they are accessed
+    //!
+    //! This concerns IFDs in the chain after the thumbnail IFD, like further
+    //! pages of a TIFF, and all [`IfdId::SubIfd`]s.
+
+    /// Read an IFD that has not been read while parsing
+    ///
+    /// Returns `false` if the IFD does not exist. Accessing entries reads the
+    /// IFD automatically.
+    pub fn load_ifd(&mut self, ifd_id: IfdId) -> Result<bool, Error> {
+        if self.ifds.contains_key(&ifd_id) {
+            return Ok(true);
+        }
+
+        // Load the chain step by step to avoid deep recursion
+        if let IfdId::Chain(chain) = ifd_id {
+            for previous in 2..chain.index() {
+                if !self.load_ifd(IfdId::chain(previous))? {
+                    return Ok(false);
+                }
+            }
+        }
+
+        let Some(offset) = self.lazy_ifd_offset(ifd_id)? else {
+            return Ok(false);
+        };
+
+        let data = std::mem::take(&mut self.data);
+        let mut parser = Parser::for_data_blocks(data, self.endieness, self.index_size == 8)?;
+        let ifd = parser.read_ifd_at(ifd_id, offset);
+        // The data are not touched if reading fails
+        self.data = parser.data().1;
+
+        self.ifds.insert(ifd_id, (offset, ifd?));
+
+        Ok(true)
+    }
+
+    /// Read all IFDs in the chain and all SubIFDs
+    ///
+    /// IFDs that can't be read are skipped and reported via
+    /// [`parse_diagnostics`](Self::parse_diagnostics).
+    pub fn load_all_ifds(&mut self) -> Result<(), Error> {
+        if self.ifds.contains_key(&IfdId::Thumbnail) {
+            for n in 2..=u16::MAX {
+                if !self.try_load_ifd(IfdId::chain(n))? {
+                    break;
+                }
+            }
+        }
+
+        let mut parents = self
+            .ifds
+            .keys()
+            .copied()
+            .filter(|x| x.is_image())
+            .collect::<Vec<_>>();
+
+        while let Some(parent) = parents.pop() {
+            let n_sub_ifds = self
+                .lookup_offsets(TagIfd::new(Tag::SUB_IFDS, parent))
+                .ok()
+                .flatten()
+                .map_or(0, |x| x.len());
+
+            for index in 0..n_sub_ifds {
+                let Some(sub_ifd) = u16::try_from(index).ok().and_then(|x| parent.sub_ifd(x))
+                else {
+                    break;
+                };
+
+                if self.try_load_ifd(sub_ifd)? {
+                    parents.push(sub_ifd);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Like [`load_ifd`](Self::load_ifd) but records failures as diagnostic
+    fn try_load_ifd(&mut self, ifd_id: IfdId) -> Result<bool, Error> {
+        match self.load_ifd(ifd_id) {
+            Ok(loaded) => Ok(loaded),
+            Err(error) => {
+                let offset = self.lazy_ifd_offset(ifd_id)?.unwrap_or_default();
+
+                if !self.diagnostics.iter().any(|x| x.ifd == ifd_id) {
+                    self.diagnostics.push(ParseDiagnostic {
+                        ifd: ifd_id,
+                        offset,
+                        error,
+                    });
+                }
+
+                Ok(false)
+            }
+        }
+    }
+
+    /// Position of an IFD that is read lazily
+    fn lazy_ifd_offset(&mut self, ifd_id: IfdId) -> Result<Option<usize>, Error> {
+        let offset = match ifd_id {
+            IfdId::Chain(chain) => {
+                let previous =
+                    IfdId::chain(chain.index().checked_sub(1).ok_or(Error::IndexOverflow)?);
+                let Some(previous) = self.ifds.get(&previous) else {
+                    return Ok(None);
+                };
+
+                previous.1.next_ifd_offset()?
+            }
+            IfdId::SubIfd(sub_ifd) => {
+                let parent = sub_ifd.parent();
+                if !self.load_ifd(parent)? {
+                    return Ok(None);
+                }
+
+                let offsets = self
+                    .lookup_offsets(TagIfd::new(Tag::SUB_IFDS, parent))?
+                    .unwrap_or_default();
+                let Some(offset) = offsets.get(usize::from(sub_ifd.index())) else {
+                    return Ok(None);
+                };
+
+                *offset
+            }
+            _ => return Ok(None),
+        };
+
+        Ok((offset != 0).then_some(offset))
+    }
+}
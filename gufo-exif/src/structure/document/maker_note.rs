@@ -0,0 +1,154 @@
+use gufo_common::exif::{IfdId, TagIfd};
+use gufo_common::math::cheq;
+
+use super::Document;
+use crate::Error;
+use crate::maker_note::{Layout, Vendor};
+use crate::structure::file::RawMakerNote;
+use crate::structure::util::Bytes;
+
+/// MakerNote with its own offsets
+///
+/// The MakerNote is kept in one piece and always serialized with its original
+/// layout since offsets within it are relative to the MakerNote.
+#[derive(Debug)]
+pub(crate) struct EmbeddedMakerNote<'a, B: Bytes<'a> = &'a mut [u8]> {
+    pub vendor: Vendor,
+    /// Position of the MakerNote in the Exif data
+    pub pos: usize,
+    /// Data before the TIFF structure, like `Nikon\0` and the version
+    pub header: B,
+    pub document: Box<Document<'a, B>>,
+}
+
+impl<'a, B: Bytes<'a>> EmbeddedMakerNote<'a, B> {
+    /// Read the structure of the MakerNote
+    ///
+    /// Returns the position and the data if the structure can't be read.
+    pub fn parse(raw: RawMakerNote<B>) -> Result<Self, (usize, B)> {
+        // Check without taking the data first to not lose them if reading fails
+        if let Err(_err) = EmbeddedMakerNote::read(raw.data.as_slice(), raw.layout) {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("Failed to read {:?} MakerNote: {_err}", raw.vendor);
+            return Err((raw.pos, raw.data));
+        }
+
+        let Ok((header, document)) = Self::read(raw.data, raw.layout) else {
+            unreachable!()
+        };
+
+        Ok(Self {
+            vendor: raw.vendor,
+            pos: raw.pos,
+            header,
+            document: Box::new(document),
+        })
+    }
+
+    /// Split the data into header and document
+    fn read(data: B, layout: Layout) -> Result<(B, Document<'a, B>), Error> {
+        match layout {
+            Layout::EmbeddedTiff { header_len } => {
+                let (header, data) = data
+                    .split_at_checked(header_len)
+                    .ok_or(Error::IndexOverflow)?;
+                Ok((header, Document::parse(data, Default::default())?))
+            }
+            Layout::RelativeIfd { endieness, ifd_pos } => {
+                // The header stays part of the document since offsets count from there
+                let (header, data) = data.split_at_checked(0).ok_or(Error::IndexOverflow)?;
+                Ok((
+                    header,
+                    Document::for_relative_ifd(data, endieness, ifd_pos)?,
+                ))
+            }
+            Layout::Ifd => Err(Error::other("MakerNote is not embedded")),
+        }
+    }
+
+    pub fn serialize(&mut self) -> Result<Vec<u8>, Error> {
+        let mut data = self.header.as_slice().to_vec();
+        data.extend(self.document.serialize()?);
+        Ok(data)
+    }
+}
+
+impl<'a, B: Bytes<'a>> Document<'a, B> {
+    //! Access to manufacturer specific MakerNote data. See
+    //! [`Exif`](crate::Exif) for documentation.
+
+    pub fn maker_note_vendor(&self) -> Option<Vendor> {
+        if let Some(maker_note) = &self.maker_note {
+            return Some(maker_note.vendor);
+        }
+
+        self.ifds.keys().find_map(|ifd| match ifd {
+            IfdId::MakerNote(vendor) => Some(*vendor),
+            _ => None,
+        })
+    }
+
+    /// Returns true if the IFD is part of a MakerNote with its own TIFF
+    /// structure
+    ///
+    /// Entries can be updated in place but the size of the MakerNote can't be
+    /// changed.
+    pub(crate) fn is_embedded(&self, ifd: IfdId) -> bool {
+        self.maker_note
+            .as_ref()
+            .is_some_and(|x| ifd == IfdId::MakerNote(x.vendor))
+    }
+
+    /// Raw data of a MakerNote with its own TIFF structure
+    pub(crate) fn embedded_maker_note_data(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        self.maker_note
+            .as_mut()
+            .map(|maker_note| maker_note.serialize())
+            .transpose()
+    }
+
+    /// MakerNote with its own TIFF structure
+    pub(crate) fn embedded_maker_note(&mut self) -> Option<&mut EmbeddedMakerNote<'a, B>> {
+        self.maker_note.as_mut()
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Remove an entry of a MakerNote with its own TIFF structure
+    ///
+    /// The MakerNote keeps its size and layout. The entry and its data are
+    /// overwritten with zeros and the remaining entries move up. Returns
+    /// `false` if the entry does not exist.
+    pub(crate) fn remove_embedded_entry(&mut self, tag_ifd: TagIfd) -> Result<bool, Error> {
+        if !self.is_embedded(tag_ifd.ifd) {
+            return Err(Error::other(format!("{tag_ifd:?} is not embedded")));
+        }
+
+        let (document, tag_ifd) = self.resolve(tag_ifd);
+
+        let Some(entry_data) = document.entry_data(tag_ifd)? else {
+            return Ok(false);
+        };
+        entry_data.data.fill(0);
+
+        let n_entries_size = document.n_entries_size;
+        let entry_size = document.entry_size;
+        let index_size = document.index_size;
+
+        let Some((ifd_pos, ifd)) = document.ifd_pos(tag_ifd.ifd)? else {
+            return Ok(false);
+        };
+        let ifd_pos = *ifd_pos;
+        let Some(freed) = ifd.remove_entry(tag_ifd.tag)? else {
+            return Ok(false);
+        };
+
+        // The freed space is now behind the offset to the next IFD
+        let freed_pos =
+            (cheq(ifd_pos) + n_entries_size + cheq(ifd.n_entries()) * entry_size + index_size)
+                .check()?;
+        document.data.push((freed_pos, freed));
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,250 @@
+use gufo_common::exif::{IfdId, Tag, TagIfd, Vendor};
+use gufo_common::math::cheq;
+
+use super::Document;
+use super::validate::Repairs;
+use crate::Error;
+use crate::structure::tree::{Tree, TreeIfd, TreeValue};
+use crate::structure::util::{Bytes, handle_error_};
+use crate::structure::{Endieness, Lookup, ValueOrOffsetRef};
+
+impl<'a, B: Bytes<'a>> Document<'a, B> {
+    /// Owned representation of all IFDs and the data they reference
+    ///
+    /// Data that are not referenced from any IFD are not part of the tree.
+    /// MakerNotes with an unknown structure stay at their offset when the tree
+    /// is serialized since they might contain absolute offsets.
+    pub(crate) fn tree(&mut self) -> Result<Tree, Error> {
+        self.tree_repaired(&Repairs::default())
+    }
+
+    /// Owned representation with `repairs` applied
+    pub(crate) fn tree_repaired(&mut self, repairs: &Repairs) -> Result<Tree, Error> {
+        self.load_all_ifds()?;
+        let mut tree = Tree::new(self.endieness, self.index_size == 8);
+
+        let ifd_ids = self.ifds.keys().copied().collect::<Vec<_>>();
+        for ifd_id in ifd_ids {
+            let tags = self.ifd(ifd_id)?.map(|x| x.tags()).unwrap_or_default();
+            let next = if repairs.cut_next.contains(&ifd_id) {
+                None
+            } else {
+                self.next_ifd(ifd_id)?
+            };
+            let mut tree_ifd = TreeIfd {
+                next,
+                ..Default::default()
+            };
+
+            for tag in tags {
+                let tag_ifd = TagIfd::new(tag, ifd_id);
+                if repairs.drop.contains(&tag_ifd) {
+                    continue;
+                }
+                let Some(type_) = self
+                    .ifds
+                    .get(&ifd_id)
+                    .and_then(|(_, ifd)| handle_error_(ifd.entry_ref(tag)).flatten())
+                    .map(|x| x.type_)
+                else {
+                    continue;
+                };
+
+                if ifd_id == IfdId::Exif
+                    && tag == Tag::MAKER_NOTE
+                    && let Some(data) = self.embedded_maker_note_data()?
+                {
+                    // Keep the MakerNote's layout since offsets in it are relative
+                    tree_ifd.entries.insert(
+                        tag,
+                        TreeValue::Data {
+                            type_,
+                            count: data.len(),
+                            data,
+                        },
+                    );
+                    continue;
+                }
+
+                if tag == Tag::SUB_IFDS && ifd_id.is_image() {
+                    let ifds = self.sub_ifds(tag_ifd)?;
+                    // Pointers to IFDs that couldn't be read are dropped
+                    if !ifds.is_empty() {
+                        tree_ifd
+                            .entries
+                            .insert(tag, TreeValue::SubIfds { type_, ifds });
+                    }
+                    continue;
+                }
+
+                if let Some(sub_ifd) = self.sub_ifd(tag_ifd) {
+                    tree_ifd.entries.insert(
+                        tag,
+                        TreeValue::Ifd {
+                            type_,
+                            ifd: sub_ifd,
+                        },
+                    );
+                    continue;
+                }
+
+                if !matches!(ifd_id, IfdId::MakerNote(_)) {
+                    if tag.is_exif_specific_ifd() {
+                        // Pointer to an IFD that couldn't be read
+                        continue;
+                    }
+
+                    if let Some(length_tag) = tag.data_length_tag() {
+                        let chunks = self.image_data(tag_ifd, length_tag)?;
+                        tree_ifd
+                            .entries
+                            .insert(tag, TreeValue::ImageData { type_, chunks });
+                        continue;
+                    }
+                }
+
+                if ifd_id == IfdId::Exif
+                    && tag == Tag::MAKER_NOTE
+                    && let Some(ValueOrOffsetRef::Offset(offset)) = self
+                        .ifds
+                        .get(&ifd_id)
+                        .and_then(|(_, ifd)| handle_error_(ifd.entry_ref(tag)).flatten())
+                        .map(|x| x.value_or_offset)
+                {
+                    let vendor = self.maker_note_vendor().unwrap_or(Vendor::Unknown);
+                    tree.opaque_maker_note = Some((IfdId::MakerNote(vendor), offset));
+                }
+
+                let entry = self
+                    .entry_data_ref(tag_ifd)?
+                    .ok_or_else(|| Error::other(format!("Entry {tag_ifd:?} disappeared")))?;
+
+                let (count, data) = match repairs.truncate.get(&tag_ifd) {
+                    Some(count) if *count < entry.count => {
+                        let len = (cheq(*count) * entry.type_.size()).check()?;
+                        (*count, entry.data.get(..len).ok_or(Error::IndexOverflow)?)
+                    }
+                    _ => (entry.count, entry.data),
+                };
+
+                tree_ifd.entries.insert(
+                    tag,
+                    TreeValue::Data {
+                        type_: entry.type_,
+                        count,
+                        data: data.to_vec(),
+                    },
+                );
+            }
+
+            tree.ifds.insert(ifd_id, tree_ifd);
+        }
+
+        Ok(tree)
+    }
+
+    /// Rebuild the structure in the byte order `endieness`
+    ///
+    /// MakerNotes with their own TIFF structure keep their byte order. Canon
+    /// and Nikon MakerNotes without own structure are converted like other
+    /// IFDs. For other MakerNotes, it is unknown whether their data depend on
+    /// the byte order, and [`Error::MakerNoteByteOrder`] is returned.
+    pub fn serialize_with_endieness(&mut self, endieness: Endieness) -> Result<Vec<u8>, Error> {
+        self.check_maker_note_byte_order()?;
+
+        let mut tree = self.tree()?;
+        tree.convert_byte_order(endieness)?;
+        tree.serialize()
+    }
+
+    fn check_maker_note_byte_order(&mut self) -> Result<(), Error> {
+        if self.maker_note.is_some() {
+            return Ok(());
+        }
+
+        let has_maker_note = self
+            .ifd(IfdId::Exif)?
+            .is_some_and(|x| x.tags().contains(&Tag::MAKER_NOTE));
+
+        match self.maker_note_vendor() {
+            _ if !has_maker_note => Ok(()),
+            Some(Vendor::Canon | Vendor::Nikon) => Ok(()),
+            vendor => Err(Error::MakerNoteByteOrder(IfdId::MakerNote(
+                vendor.unwrap_or(Vendor::Unknown),
+            ))),
+        }
+    }
+
+    /// Returns the IFD an entry points to if that IFD has been read
+    pub(crate) fn sub_ifd(&self, tag_ifd: TagIfd) -> Option<IfdId> {
+        let sub_ifd = match tag_ifd.ifd {
+            IfdId::MakerNote(_) => None,
+            IfdId::Exif if tag_ifd.tag == Tag::MAKER_NOTE => {
+                Some(IfdId::MakerNote(self.maker_note_vendor()?))
+            }
+            IfdId::Primary | IfdId::Exif => tag_ifd.tag.exif_specific_ifd(),
+            // Only the primary IFD has Exif, GPS, and interoperability IFDs
+            _ => None,
+        }?;
+
+        self.ifds.contains_key(&sub_ifd).then_some(sub_ifd)
+    }
+
+    /// SubIFDs referenced by a [`Tag::SUB_IFDS`] entry that have been read
+    fn sub_ifds(&mut self, tag_ifd: TagIfd) -> Result<Vec<IfdId>, Error> {
+        let n_sub_ifds = self.lookup_offsets(tag_ifd)?.map_or(0, |x| x.len());
+
+        Ok((0..n_sub_ifds)
+            .map_while(|index| tag_ifd.ifd.sub_ifd(u16::try_from(index).ok()?))
+            .filter(|sub_ifd| self.ifds.contains_key(sub_ifd))
+            .collect())
+    }
+
+    /// Returns the IFD that follows `ifd_id` in the chain if that IFD has
+    /// been read
+    fn next_ifd(&mut self, ifd_id: IfdId) -> Result<Option<IfdId>, Error> {
+        let Some(ifd) = self.ifd(ifd_id)? else {
+            return Ok(None);
+        };
+
+        let next_ifd_offset = ifd.next_ifd_offset()?;
+        if next_ifd_offset == 0 || matches!(ifd_id, IfdId::MakerNote(_)) {
+            return Ok(None);
+        }
+
+        Ok(self
+            .ifds
+            .iter()
+            .find(|(_, (pos, _))| *pos == next_ifd_offset)
+            .map(|(ifd_id, _)| *ifd_id))
+    }
+
+    /// Image data referenced by offsets in `tag_ifd` with lengths in
+    /// `length_tag`
+    pub(crate) fn image_data(
+        &mut self,
+        tag_ifd: TagIfd,
+        length_tag: Tag,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        let offsets = self.lookup_offsets(tag_ifd)?.unwrap_or_default();
+        let lengths = self
+            .lookup_offsets(TagIfd::new(length_tag, tag_ifd.ifd))?
+            .unwrap_or_default();
+
+        if offsets.len() != lengths.len() {
+            return Err(Error::ElementCountMissmatch(lengths.len(), offsets.len()));
+        }
+
+        offsets
+            .into_iter()
+            .zip(lengths)
+            .map(|(offset, length)| {
+                let range = offset..(cheq(offset) + length).check()?;
+                Ok(self
+                    .data_ref(range.clone())
+                    .ok_or(Error::IndexNotFound(range))?
+                    .to_vec())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,617 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+use std::ops::Range;
+
+use gufo_common::exif::{Count, IfdId, Tag, TagIfd, Vendor, lookup_tag_info};
+use gufo_common::field;
+use gufo_common::math::cheq;
+
+use super::Document;
+use crate::Error;
+use crate::structure::util::Bytes;
+use crate::structure::{Lookup, Type, ValueOrOffsetRef};
+
+/// Value of the compression tag for JPEG compressed data
+const COMPRESSION_JPEG: u16 = 6;
+
+/// Problem in the structure of Exif data found by
+/// [`Document::validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Position in the raw Exif data the finding refers to
+    ///
+    /// This is the position of the entry in the IFD's entry list, or the
+    /// position of the IFD for findings that concern the whole IFD.
+    pub offset: usize,
+    pub ifd: IfdId,
+    pub tag: Option<Tag>,
+    pub kind: FindingKind,
+    /// Can be fixed by [`Document::repair`]
+    pub repairable: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Violates the standard, but the data can be read
+    Warning,
+    /// Data can't be read or are ambiguous
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FindingKind {
+    /// Entries are not sorted by tag
+    UnsortedEntries,
+    /// Tags occur more than once, only the last entry is used
+    DuplicateEntries { stored: usize, unique: usize },
+    /// Data of the entry are beyond the end of the Exif data
+    DataOutOfBounds { range: Range<usize>, len: usize },
+    /// Data of the entry overlap the header, an IFD, or an embedded MakerNote
+    DataOverlapsStructure { range: Range<usize> },
+    /// Data of the entry overlap with data of another entry
+    OverlappingData { other: TagIfd },
+    /// Type is not allowed for the tag
+    TypeMismatch {
+        type_: Type,
+        expected: &'static [Type],
+    },
+    /// Number of values does not match the tag's definition
+    CountMismatch { count: usize, expected: Count },
+    /// The next IFD in the chain points back to an IFD that has been read
+    IfdLoop { target: IfdId },
+    /// IFD that should be the last in its chain points to another IFD
+    UnexpectedNextIfd { offset: usize },
+    /// IFD is referenced but couldn't be read
+    UnreadableIfd { target: IfdId },
+    /// Tag that is mandatory according to the Exif standard is missing
+    MissingTag,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} at {} in {:?}",
+            self.severity, self.offset, self.ifd
+        )?;
+
+        if let Some(tag) = self.tag {
+            match gufo_common::exif::lookup_tag_name(TagIfd::new(tag, self.ifd)) {
+                Some(name) => write!(f, " ({name})")?,
+                None => write!(f, " (Tag {:#06x})", tag.0)?,
+            }
+        }
+
+        f.write_str(": ")?;
+
+        match &self.kind {
+            FindingKind::UnsortedEntries => f.write_str("Entries are not sorted by tag"),
+            FindingKind::DuplicateEntries { stored, unique } => {
+                write!(
+                    f,
+                    "{stored} entries are stored, but only {unique} unique tags"
+                )
+            }
+            FindingKind::DataOutOfBounds { range, len } => {
+                write!(
+                    f,
+                    "Data at {range:?} are outside of the {len} bytes of data"
+                )
+            }
+            FindingKind::DataOverlapsStructure { range } => {
+                write!(f, "Data at {range:?} overlap with the header or an IFD")
+            }
+            FindingKind::OverlappingData { other } => {
+                write!(f, "Data overlap with data of {other:?}")
+            }
+            FindingKind::TypeMismatch { type_, expected } => {
+                write!(f, "Type {type_:?} is not one of {expected:?}")
+            }
+            FindingKind::CountMismatch { count, expected } => {
+                write!(f, "Count {count} does not match expected {expected:?}")
+            }
+            FindingKind::IfdLoop { target } => write!(f, "Next IFD points back to {target:?}"),
+            FindingKind::UnexpectedNextIfd { offset } => {
+                write!(f, "Unexpected next IFD at {offset}")
+            }
+            FindingKind::UnreadableIfd { target } => write!(f, "Can't read {target:?}"),
+            FindingKind::MissingTag => f.write_str("Mandatory tag is missing"),
+        }
+    }
+}
+
+/// Changes applied when building a [`Tree`](crate::structure::tree::Tree)
+#[derive(Debug, Default)]
+pub(crate) struct Repairs {
+    /// Entries that are not carried over
+    pub drop: BTreeSet<TagIfd>,
+    /// Entries that are reduced to the given count
+    pub truncate: BTreeMap<TagIfd, usize>,
+    /// IFDs whose next IFD is not carried over
+    pub cut_next: BTreeSet<IfdId>,
+}
+
+impl Repairs {
+    fn new(findings: &[Finding]) -> Self {
+        let mut repairs = Self::default();
+
+        for finding in findings.iter().filter(|x| x.repairable) {
+            let Some(tag) = finding.tag else {
+                if matches!(
+                    finding.kind,
+                    FindingKind::IfdLoop { .. } | FindingKind::UnexpectedNextIfd { .. }
+                ) {
+                    repairs.cut_next.insert(finding.ifd);
+                }
+                continue;
+            };
+
+            let tag_ifd = TagIfd::new(tag, finding.ifd);
+
+            match finding.kind {
+                FindingKind::DataOutOfBounds { .. } | FindingKind::DataOverlapsStructure { .. } => {
+                    repairs.drop_entry(tag_ifd);
+                }
+                FindingKind::CountMismatch { .. } if tag.is_data_offset() => {
+                    repairs.drop_entry(tag_ifd);
+                }
+                FindingKind::CountMismatch {
+                    expected: Count::Fixed(count),
+                    ..
+                } => {
+                    repairs.truncate.insert(tag_ifd, count);
+                }
+                _ => {}
+            }
+        }
+
+        repairs
+    }
+
+    /// Drop entry including the byte counts of image data
+    fn drop_entry(&mut self, tag_ifd: TagIfd) {
+        self.drop.insert(tag_ifd);
+
+        if let Some(length_tag) = tag_ifd.tag.data_length_tag() {
+            self.drop.insert(TagIfd::new(length_tag, tag_ifd.ifd));
+        }
+    }
+}
+
+/// Tags that are mandatory in an IFD according to Exif 3.0
+fn mandatory_tags(ifd: IfdId) -> Vec<TagIfd> {
+    match ifd {
+        IfdId::Primary => vec![
+            field::XResolution.into(),
+            field::YResolution.into(),
+            field::ResolutionUnit.into(),
+            field::ExifIFDPointer.into(),
+        ],
+        IfdId::Exif => vec![
+            field::ExifVersion.into(),
+            field::ComponentsConfiguration.into(),
+            field::FlashpixVersion.into(),
+            field::ColorSpace.into(),
+            field::PixelXDimension.into(),
+            field::PixelYDimension.into(),
+        ],
+        IfdId::Gps => vec![field::GPSVersionID.into()],
+        IfdId::Interoperability => vec![field::InteroperabilityIndex.into()],
+        IfdId::Thumbnail => vec![field::ThumbnailCompression.into()],
+        IfdId::MakerNote(_) | IfdId::Chain(_) | IfdId::SubIfd(_) => vec![],
+    }
+}
+
+/// Location of an entry's data
+struct DataRange {
+    range: Range<usize>,
+    tag_ifd: TagIfd,
+    offset: usize,
+}
+
+impl<'a, B: Bytes<'a>> Document<'a, B> {
+    /// Check the structure for problems
+    ///
+    /// In contrast to reading values, this does not stop at the first
+    /// problem. Entries and IFDs that can't be read at all are not part of the
+    /// document and are therefore only reported if something points to them.
+    pub fn validate(&mut self) -> Result<Vec<Finding>, Error> {
+        self.load_all_ifds()?;
+        let mut findings = Vec::new();
+
+        self.validate_into(&mut findings, 0, None)?;
+
+        if let Some(maker_note) = &mut self.maker_note {
+            let base = (cheq(maker_note.pos) + maker_note.header.as_slice().len()).check()?;
+            let vendor = maker_note.vendor;
+            maker_note
+                .document
+                .validate_into(&mut findings, base, Some(vendor))?;
+        }
+
+        findings.sort_by_key(|x| x.offset);
+
+        Ok(findings)
+    }
+
+    /// Fix all problems that can be fixed without guessing
+    ///
+    /// Returns the repaired raw Exif data and the findings that have been
+    /// fixed. The structure is laid out anew, like with
+    /// [`SerializeMode::Rebuild`](crate::structure::SerializeMode::Rebuild).
+    /// Entries with data that can't be read are dropped and counts that are
+    /// too large are truncated. Embedded MakerNotes are carried over as they
+    /// are.
+    pub fn repair(&mut self) -> Result<(Vec<u8>, Vec<Finding>), Error> {
+        let findings = self.validate()?;
+        let repaired = findings
+            .into_iter()
+            .filter(|x| x.repairable)
+            .collect::<Vec<_>>();
+
+        let raw = self.tree_repaired(&Repairs::new(&repaired))?.serialize()?;
+
+        Ok((raw, repaired))
+    }
+
+    /// Validate IFDs of this document
+    ///
+    /// For embedded MakerNotes, `base` is the position of the MakerNote's
+    /// data and `vendor` the MakerNote's vendor.
+    fn validate_into(
+        &mut self,
+        findings: &mut Vec<Finding>,
+        base: usize,
+        vendor: Option<Vendor>,
+    ) -> Result<(), Error> {
+        let embedded = vendor.is_some();
+        let len = self.len()?;
+        let mut data_ranges = Vec::new();
+
+        let mut push = |offset: usize, ifd, tag, kind: FindingKind, repairable| {
+            let severity = match kind {
+                FindingKind::UnsortedEntries
+                | FindingKind::OverlappingData { .. }
+                | FindingKind::TypeMismatch { .. }
+                | FindingKind::CountMismatch { .. }
+                | FindingKind::UnexpectedNextIfd { .. }
+                | FindingKind::MissingTag => Severity::Warning,
+                FindingKind::DuplicateEntries { .. }
+                | FindingKind::DataOutOfBounds { .. }
+                | FindingKind::DataOverlapsStructure { .. }
+                | FindingKind::IfdLoop { .. }
+                | FindingKind::UnreadableIfd { .. } => Severity::Error,
+            };
+
+            findings.push(Finding {
+                severity,
+                offset: offset.saturating_add(base),
+                ifd,
+                tag,
+                kind,
+                repairable: repairable && !embedded,
+            });
+        };
+
+        let ifd_ids = self.ifds.keys().copied().collect::<Vec<_>>();
+        let ifd_positions = self
+            .ifds
+            .iter()
+            .map(|(ifd_id, (pos, _))| (*pos, *ifd_id))
+            .collect::<BTreeMap<_, _>>();
+
+        for ifd_id in ifd_ids {
+            let public_ifd = vendor.map_or(ifd_id, IfdId::MakerNote);
+            let is_maker_note = matches!(public_ifd, IfdId::MakerNote(_));
+
+            let Some((ifd_pos, ifd)) = self.ifds.get(&ifd_id) else {
+                continue;
+            };
+            let ifd_pos = *ifd_pos;
+            let tags = ifd.tags();
+            let stored_tags = ifd.stored_tags();
+            // Position in the stored entry list, which differs from the
+            // position in `tags` if tags occur more than once
+            let entry_indices = tags
+                .iter()
+                .map(|tag| ifd.stored_entry_index(*tag))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(Error::IndexOverflow)?;
+            let stored_n_entries = ifd.stored_n_entries()?;
+            let next_ifd_offset = ifd.next_ifd_offset()?;
+
+            if !stored_tags.is_sorted() {
+                push(
+                    ifd_pos,
+                    public_ifd,
+                    None,
+                    FindingKind::UnsortedEntries,
+                    true,
+                );
+            }
+
+            if stored_n_entries != tags.len() {
+                push(
+                    ifd_pos,
+                    public_ifd,
+                    None,
+                    FindingKind::DuplicateEntries {
+                        stored: stored_n_entries,
+                        unique: tags.len(),
+                    },
+                    true,
+                );
+            }
+
+            // The chain of IFDs
+            let next_in_chain = ifd_id
+                .chain_index()
+                .and_then(|n| n.checked_add(1))
+                .map(IfdId::chain);
+            if next_ifd_offset != 0 && !is_maker_note {
+                match ifd_positions.get(&next_ifd_offset) {
+                    Some(target) if Some(*target) == next_in_chain => {}
+                    Some(target) => push(
+                        ifd_pos,
+                        public_ifd,
+                        None,
+                        FindingKind::IfdLoop { target: *target },
+                        true,
+                    ),
+                    None => push(
+                        ifd_pos,
+                        public_ifd,
+                        None,
+                        match next_in_chain {
+                            Some(target) => FindingKind::UnreadableIfd { target },
+                            None => FindingKind::UnexpectedNextIfd {
+                                offset: next_ifd_offset,
+                            },
+                        },
+                        true,
+                    ),
+                }
+            }
+
+            if !embedded {
+                let mut mandatory_tags = mandatory_tags(ifd_id);
+                if ifd_id == IfdId::Thumbnail
+                    && self
+                        .lookup_short(field::ThumbnailCompression.into())
+                        .ok()
+                        .flatten()
+                        == Some(COMPRESSION_JPEG)
+                {
+                    mandatory_tags.push(field::JPEGInterchangeFormat.into());
+                    mandatory_tags.push(field::JPEGInterchangeFormatLength.into());
+                }
+
+                for tag_ifd in mandatory_tags {
+                    if !tags.contains(&tag_ifd.tag) {
+                        push(
+                            ifd_pos,
+                            public_ifd,
+                            Some(tag_ifd.tag),
+                            FindingKind::MissingTag,
+                            false,
+                        );
+                    }
+                }
+            }
+
+            for (tag, n) in tags.iter().zip(entry_indices) {
+                let tag_ifd = TagIfd::new(*tag, ifd_id);
+                let entry_pos =
+                    (cheq(ifd_pos) + self.n_entries_size + cheq(n) * self.entry_size).check()?;
+
+                let Some(entry) = self
+                    .ifds
+                    .get(&ifd_id)
+                    .map(|(_, ifd)| ifd.entry_ref(*tag))
+                    .transpose()?
+                    .flatten()
+                else {
+                    continue;
+                };
+                let type_ = entry.type_;
+                let count = entry.count;
+                let offset = match entry.value_or_offset {
+                    ValueOrOffsetRef::Offset(offset) => Some(offset),
+                    ValueOrOffsetRef::Value(_) => None,
+                };
+
+                // Definition in the tag registry
+                if let Some(info) = lookup_tag_info(TagIfd::new(*tag, public_ifd)) {
+                    let type_matches = info.types.contains(&type_);
+                    if !type_matches {
+                        push(
+                            entry_pos,
+                            public_ifd,
+                            Some(*tag),
+                            FindingKind::TypeMismatch {
+                                type_,
+                                expected: info.types,
+                            },
+                            false,
+                        );
+                    }
+
+                    if !info.count.matches(count) {
+                        let too_large = matches!(info.count, Count::Fixed(n) if count > n);
+                        push(
+                            entry_pos,
+                            public_ifd,
+                            Some(*tag),
+                            FindingKind::CountMismatch {
+                                count,
+                                expected: info.count,
+                            },
+                            // Strings would lose their terminating NUL
+                            type_matches && too_large && type_ != Type::Ascii,
+                        );
+                    }
+                }
+
+                if is_maker_note {
+                    // MakerNotes use tags with different meanings
+                } else if *tag == Tag::SUB_IFDS && ifd_id.is_image() {
+                    let n_sub_ifds = self.lookup_offsets(tag_ifd)?.map_or(0, |x| x.len());
+                    for index in 0..n_sub_ifds {
+                        let Some(target) = ifd_id.sub_ifd(u16::try_from(index)?) else {
+                            break;
+                        };
+                        if !self.ifds.contains_key(&target) {
+                            push(
+                                entry_pos,
+                                public_ifd,
+                                Some(*tag),
+                                FindingKind::UnreadableIfd { target },
+                                true,
+                            );
+                        }
+                    }
+                } else if let Some(target) = tag.exif_specific_ifd() {
+                    if !self.ifds.contains_key(&target) {
+                        push(
+                            entry_pos,
+                            public_ifd,
+                            Some(*tag),
+                            FindingKind::UnreadableIfd { target },
+                            true,
+                        );
+                    }
+                    continue;
+                } else if ifd_id == IfdId::Exif
+                    && *tag == Tag::MAKER_NOTE
+                    && (self.maker_note.is_some() || self.sub_ifd(tag_ifd).is_some())
+                {
+                    // The MakerNote's data contain its IFD
+                    continue;
+                } else if let Some(length_tag) = tag.data_length_tag() {
+                    let offsets = self.lookup_offsets(tag_ifd)?.unwrap_or_default();
+                    let lengths = self
+                        .lookup_offsets(TagIfd::new(length_tag, ifd_id))?
+                        .unwrap_or_default();
+
+                    if offsets.len() != lengths.len() {
+                        push(
+                            entry_pos,
+                            public_ifd,
+                            Some(*tag),
+                            FindingKind::CountMismatch {
+                                count: offsets.len(),
+                                expected: Count::Fixed(lengths.len()),
+                            },
+                            true,
+                        );
+                        continue;
+                    }
+
+                    for (offset, length) in offsets.into_iter().zip(lengths) {
+                        let range = offset..(cheq(offset) + length).check()?;
+                        data_ranges.push(DataRange {
+                            range,
+                            tag_ifd: TagIfd::new(*tag, public_ifd),
+                            offset: entry_pos,
+                        });
+                    }
+                }
+
+                if let Some(offset) = offset {
+                    let range = offset..(cheq(offset) + cheq(count) * type_.size()).check()?;
+                    data_ranges.push(DataRange {
+                        range,
+                        tag_ifd: TagIfd::new(*tag, public_ifd),
+                        offset: entry_pos,
+                    });
+                }
+            }
+        }
+
+        // Check that data are readable and don't overlap
+        data_ranges.retain(|x| !x.range.is_empty());
+        data_ranges.sort_by_key(|x| x.range.start);
+
+        let mut previous: Option<(usize, TagIfd)> = None;
+        for data_range in data_ranges {
+            let DataRange {
+                range,
+                tag_ifd,
+                offset,
+            } = data_range;
+
+            if range.end > len {
+                push(
+                    offset,
+                    tag_ifd.ifd,
+                    Some(tag_ifd.tag),
+                    FindingKind::DataOutOfBounds {
+                        range: range.clone(),
+                        len,
+                    },
+                    true,
+                );
+                continue;
+            }
+
+            if self.data_ref(range.clone()).is_none() {
+                push(
+                    offset,
+                    tag_ifd.ifd,
+                    Some(tag_ifd.tag),
+                    FindingKind::DataOverlapsStructure {
+                        range: range.clone(),
+                    },
+                    true,
+                );
+                continue;
+            }
+
+            match previous {
+                Some((end, other)) if range.start < end => {
+                    push(
+                        offset,
+                        tag_ifd.ifd,
+                        Some(tag_ifd.tag),
+                        FindingKind::OverlappingData { other },
+                        // Which entry's data are correct would be a guess
+                        false,
+                    );
+                    if range.end > end {
+                        previous = Some((range.end, tag_ifd));
+                    }
+                }
+                _ => previous = Some((range.end, tag_ifd)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Length of the raw data
+    fn len(&self) -> Result<usize, Error> {
+        let mut len = self
+            .data
+            .iter()
+            .map(|(pos, data)| cheq(*pos) + data.as_slice().len())
+            .try_fold(0, |len, end| end.check().map(|end| len.max(end)))?;
+
+        for (pos, ifd) in self.ifds.values() {
+            let ifd_len = cheq(self.n_entries_size)
+                + cheq(ifd.stored_n_entries()?) * self.entry_size
+                + self.index_size;
+            len = len.max((ifd_len + *pos).check()?);
+        }
+
+        if let Some(maker_note) = &self.maker_note {
+            let end = cheq(maker_note.pos)
+                + maker_note.header.as_slice().len()
+                + maker_note.document.len()?;
+            len = len.max(end.check()?);
+        }
+
+        Ok(len)
+    }
+}
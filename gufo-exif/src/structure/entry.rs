@@ -3,7 +3,7 @@ use gufo_common::math::cheq;
 use zerocopy::{BE, BigEndian, ByteOrder, FromBytes, IntoBytes, LE, LittleEndian, U16, U32, U64};
 
 use super::type_::Type;
-use super::util::IndexType;
+use super::util::{Endieness, IndexType};
 use crate::Error;
 
 #[derive(Debug)]
@@ -23,7 +23,7 @@ impl<'a> Entry<'a> {
         crate::forall_formats_self!(self, entry, entry.value_or_offset())
     }
 
-    pub fn ifd_pointer(&mut self) -> Result<u32, Error> {
+    pub fn ifd_pointer(&mut self) -> Result<usize, Error> {
         crate::forall_formats_self!(self, entry, entry.ifd_pointer())
     }
 
@@ -62,6 +62,37 @@ impl<'a> Entry<'a> {
         self.update(tag, type_, count, data)
     }
 
+    /// Point the entry to a new location of its data
+    pub fn set_offset(&mut self, offset: usize) -> Result<(), Error> {
+        let tag = self.tag();
+        let type_ = self.type_();
+        let count = self.count()?;
+
+        self.update_offset(tag, type_, count, offset)
+    }
+
+    /// Point an IFD pointer entry to a new IFD location
+    ///
+    /// The pointer keeps its size, see [`EntryGeneric::ifd_pointer`].
+    pub fn set_ifd_pointer(&mut self, offset: usize) -> Result<(), Error> {
+        let endieness = match self {
+            Self::Be32(_) | Self::Be64(_) => Endieness::Big,
+            Self::Le32(_) | Self::Le64(_) => Endieness::Litte,
+        };
+        let pointer_size = crate::forall_formats_self!(self, entry, entry.ifd_pointer_size())?;
+        let data = endieness.index_to_bytes(offset, pointer_size)?;
+
+        let tag = self.tag();
+        let type_ = self.type_();
+        let count = self.count()?;
+
+        self.update(tag, type_, count, data)
+    }
+
+    pub fn set_count(&mut self, count: usize) -> Result<(), Error> {
+        crate::forall_formats_self!(self, entry, entry.set_count(count))
+    }
+
     pub fn type_(&self) -> Type {
         crate::forall_formats_self!(self, entry, entry.type_())
     }
@@ -130,29 +161,65 @@ impl<T: IndexType + zerocopy::Immutable, O: ByteOrder> EntryGeneric<T, O> {
     }
 
     pub fn value_or_offset(&mut self) -> Result<ValueOrOffset<'_>, Error> {
-        let data_size = (cheq(self.type_().size()) * self.count.try_to_usize()?).check()?;
-        if data_size > std::mem::size_of::<T>() {
+        if self.has_offset()? {
             Ok(ValueOrOffset::Offset(self.value_or_offset.try_to_usize()?))
         } else {
             Ok(ValueOrOffset::Value(self.value_or_offset.as_mut_bytes()))
         }
     }
 
-    pub fn ifd_pointer(&mut self) -> Result<u32, Error> {
+    pub(crate) fn value_or_offset_ref(&self) -> Result<ValueOrOffsetRef<'_>, Error> {
+        if self.has_offset()? {
+            Ok(ValueOrOffsetRef::Offset(
+                self.value_or_offset.try_to_usize()?,
+            ))
+        } else {
+            Ok(ValueOrOffsetRef::Value(self.value_or_offset.as_bytes()))
+        }
+    }
+
+    /// Returns `true` if the data don't fit into the entry
+    fn has_offset(&self) -> Result<bool, Error> {
+        let data_size = (cheq(self.type_().size()) * self.count.try_to_usize()?).check()?;
+        Ok(data_size > std::mem::size_of::<T>())
+    }
+
+    /// Offset of the IFD the entry points to
+    ///
+    /// Pointers of type [`Type::Long`] and [`Type::Ifd`] have 32 bits, the
+    /// BigTIFF types [`Type::Long8`] and [`Type::Ifd8`] 64 bits. For
+    /// [`Type::Undefined`], as used by the MakerNote, the offset of the data
+    /// is returned.
+    pub fn ifd_pointer(&self) -> Result<usize, Error> {
+        let bytes = self
+            .value_or_offset
+            .as_bytes()
+            .get(..self.ifd_pointer_size()?)
+            .ok_or(Error::TryFromSlice)?;
+
+        match bytes.len() {
+            8 => Ok(U64::<O>::read_from_bytes(bytes)
+                .map_err(|_| Error::TryFromSlice)?
+                .get()
+                .try_into()?),
+            _ => Ok(U32::<O>::read_from_bytes(bytes)
+                .map_err(|_| Error::TryFromSlice)?
+                .get()
+                .try_into()?),
+        }
+    }
+
+    /// Size of the pointer in bytes
+    fn ifd_pointer_size(&self) -> Result<usize, Error> {
         let count = self.count()?;
         let type_ = self.type_();
-        if (count == 1 && type_ == Type::Long) || type_ == Type::Undefined {
-            Ok(
-                U32::<O>::read_from_prefix(self.value_or_offset.as_mut_bytes())
-                    .map_err(|_| Error::TryFromSlice)?
-                    .0
-                    .get(),
-            )
-        } else {
-            Err(Error::other(format!(
+        match type_ {
+            Type::Undefined => Ok(std::mem::size_of::<T>()),
+            Type::Long | Type::Ifd | Type::Long8 | Type::Ifd8 if count == 1 => Ok(type_.size()),
+            _ => Err(Error::other(format!(
                 "Invalid type/count {count}x{type_:?} for ifd pointer entry {}",
                 self.tag_id.get()
-            )))
+            ))),
         }
     }
 }
@@ -186,3 +253,20 @@ pub enum ValueOrOffset<'a> {
     Value(&'a mut [u8]),
     Offset(usize),
 }
+
+/// Like [`ValueOrOffset`] for data that are only read
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ValueOrOffsetRef<'a> {
+    Value(&'a [u8]),
+    Offset(usize),
+}
+
+/// Entry of an IFD without the option to change it
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct EntryRef<'a> {
+    /// Position in the entry list
+    pub n_entry: usize,
+    pub type_: Type,
+    pub count: usize,
+    pub value_or_offset: ValueOrOffsetRef<'a>,
+}
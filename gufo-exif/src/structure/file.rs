@@ -1,110 +1,196 @@
 use std::collections::BTreeMap;
 use std::marker::PhantomData;
 
-use gufo_common::exif::{Field, IfdId};
+use gufo_common::exif::{Field, IfdId, Tag, Vendor};
 use gufo_common::math::{MathError, cheq};
 use indexmap::IndexMap;
-use zerocopy::{BigEndian, ByteOrder, FromBytes, LittleEndian, U16, U32, U64};
+use zerocopy::{BigEndian, ByteOrder, FromBytes, LittleEndian, U32, U64};
 
-use super::util::{IndexType, UsizeConversion};
+use super::util::{Bytes, IndexType, UsizeConversion};
 use super::{Ifd, IfdGeneric};
 use crate::error::Error;
-use crate::structure::EntryGeneric;
+use crate::maker_note;
 use crate::structure::util::{Endieness, IterExt, handle_error_};
+use crate::structure::{EntryGeneric, EntryRef, ParseDiagnostic, ParseMode, ValueOrOffsetRef};
 
 const MAGIC_BYTES_LE_32: &[u8] = b"II*\0";
 const MAGIC_BYTES_BE_32: &[u8] = b"MM\0*";
 const MAGIC_BYTES_LE_64: &[u8] = b"II+\0";
 const MAGIC_BYTES_BE_64: &[u8] = b"MM\0+";
 
+/// Parser for shared or mutable data, see [`Bytes`]
 #[derive(Debug)]
-pub(crate) enum Parser<'a> {
-    Le32(ParserGeneric<'a, U32<LittleEndian>, LittleEndian>),
-    Be32(ParserGeneric<'a, U32<BigEndian>, BigEndian>),
-    Le64(ParserGeneric<'a, U64<LittleEndian>, LittleEndian>),
-    Be64(ParserGeneric<'a, U64<BigEndian>, BigEndian>),
+pub(crate) enum Parser<'a, B: Bytes<'a> = &'a mut [u8]> {
+    Le32(ParserGeneric<'a, U32<LittleEndian>, LittleEndian, B>),
+    Be32(ParserGeneric<'a, U32<BigEndian>, BigEndian, B>),
+    Le64(ParserGeneric<'a, U64<LittleEndian>, LittleEndian, B>),
+    Be64(ParserGeneric<'a, U64<BigEndian>, BigEndian, B>),
 }
 
-impl<'a> Parser<'a> {
-    pub fn new(data: &'a mut [u8]) -> Result<Self, Error> {
-        let magic_bytes = data.get(..4).ok_or(Error::TryFromSlice)?;
+impl<'a, B: Bytes<'a>> Parser<'a, B> {
+    pub fn new(data: B) -> Result<Self, Error> {
+        let magic_bytes = data.as_slice().get(..4).ok_or(Error::TryFromSlice)?;
 
         Ok(match magic_bytes {
-            MAGIC_BYTES_BE_32 => Self::Be32(ParserGeneric::<U32<BigEndian>, BigEndian>::new(data)),
-            MAGIC_BYTES_LE_32 => {
-                Self::Le32(ParserGeneric::<U32<LittleEndian>, LittleEndian>::new(data))
-            }
-            MAGIC_BYTES_BE_64 => {
-                let mut x = Self::Be64(ParserGeneric::<U64<BigEndian>, BigEndian>::new(data));
-                x.read_u16()?;
-                x.read_u16()?;
-                x
-            }
-            MAGIC_BYTES_LE_64 => {
-                let mut x = Self::Le64(ParserGeneric::<U64<LittleEndian>, LittleEndian>::new(data));
-                x.read_u16()?;
-                x.read_u16()?;
-                x
-            }
+            MAGIC_BYTES_BE_32 => Self::Be32(ParserGeneric::new(data)),
+            MAGIC_BYTES_LE_32 => Self::Le32(ParserGeneric::new(data)),
+            MAGIC_BYTES_BE_64 => Self::Be64(ParserGeneric::new(data)),
+            MAGIC_BYTES_LE_64 => Self::Le64(ParserGeneric::new(data)),
             _ => return Err(Error::UnknownFormat),
         })
     }
 
-    pub fn parse(&mut self) -> Result<BTreeMap<IfdId, (usize, Ifd<'a>)>, Error> {
-        // Record magic bytes into data
-        self.seek_absolute(4)?;
+    /// Parser for data without TIFF header
+    ///
+    /// Only [`parse_ifd`](Self::parse_ifd) can be used on such data.
+    pub fn new_headerless(data: B, endieness: Endieness) -> Self {
+        match endieness {
+            Endieness::Big => Self::Be32(ParserGeneric::new(data)),
+            Endieness::Litte => Self::Le32(ParserGeneric::new(data)),
+        }
+    }
+
+    /// Parser for data blocks of an already parsed document
+    ///
+    /// Used to read IFDs that have not been read while parsing. Get the blocks
+    /// back via [`data`](Self::data).
+    pub fn for_data_blocks(
+        data: Vec<(usize, B)>,
+        endieness: Endieness,
+        big_tiff: bool,
+    ) -> Result<Self, Error> {
+        Ok(match (endieness, big_tiff) {
+            (Endieness::Big, false) => Self::Be32(ParserGeneric::for_data_blocks(data)?),
+            (Endieness::Litte, false) => Self::Le32(ParserGeneric::for_data_blocks(data)?),
+            (Endieness::Big, true) => Self::Be64(ParserGeneric::for_data_blocks(data)?),
+            (Endieness::Litte, true) => Self::Le64(ParserGeneric::for_data_blocks(data)?),
+        })
+    }
+
+    /// Read a single IFD at `offset` as primary IFD
+    pub fn parse_ifd(&mut self, offset: usize) -> Result<Ifds<'a, B>, Error> {
+        self.seek_absolute(offset)?;
+        let ifd = self.read_ifd(IfdId::Primary)?;
+        self.read_remaining_data()?;
+
+        Ok(BTreeMap::from([(IfdId::Primary, (offset, ifd))]))
+    }
+
+    pub fn parse(&mut self, mode: ParseMode) -> Result<Parsed<'a, B>, Error> {
+        // Record magic bytes, and for BigTIFF the offset size, into data
+        self.seek_absolute(self.primary_ifd_offset_pos())?;
 
         let mut ifds = BTreeMap::new();
+        let mut embedded_maker_note = None;
+        let mut diagnostics = Vec::new();
+
+        // Skip IFDs that can't be read in lenient mode
+        let mut skip = |ifd, offset, error| match mode {
+            ParseMode::Strict => Err(error),
+            ParseMode::Lenient => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!("Skipping {ifd:?} at {offset}: {error}");
+                diagnostics.push(ParseDiagnostic { ifd, offset, error });
+                Ok(())
+            }
+        };
 
         // Read the initial offset in the file to the first Ifd
         let primary_ifd_offset = self.read_primary_ifd_offset()?;
         self.seek_absolute(primary_ifd_offset)?;
 
-        let mut primary_ifd = self.read_ifd(IfdId::Primary)?;
+        let primary_ifd = self.read_ifd(IfdId::Primary)?;
+
+        let make = handle_error_(primary_ifd.entry_ref(gufo_common::field::Make::TAG))
+            .flatten()
+            .and_then(|make| self.entry_value(&make));
 
         // Read Exif Ifd if available
-        if let Some(mut exif_ifd_pointer) =
-            primary_ifd.entry_by_tag(gufo_common::field::ExifIFDPointer::TAG)
-            && let Some(offset) = handle_error_(exif_ifd_pointer.ifd_pointer()).map(|x| x as usize)
+        let mut exif_ifd = None;
+        if let Some(offset) =
+            Self::ifd_pointer(&primary_ifd, gufo_common::field::ExifIFDPointer::TAG)
         {
-            self.seek_absolute(offset)?;
-            let mut exif_ifd = self.read_ifd(IfdId::Exif)?;
+            match self.read_ifd_at(IfdId::Exif, offset) {
+                Ok(ifd) => exif_ifd = Some((offset, ifd)),
+                Err(err) => skip(IfdId::Exif, offset, err)?,
+            }
+        }
 
+        if let Some((offset, exif_ifd)) = exif_ifd {
             // Read Maker Info Ifd if available
-            if let Some(mut maker_ifd_pointer) =
-                exif_ifd.entry_by_tag(gufo_common::field::MakerNote::TAG)
-                && let Some(offset) =
-                    handle_error_(maker_ifd_pointer.ifd_pointer()).map(|x| x as usize)
-                && handle_error_(self.seek_absolute(offset)).is_some()
-                && let Some(maker_info_ifd) = handle_error_(self.read_ifd(IfdId::MakerNote))
+            if let Some(offset) = Self::ifd_pointer(&exif_ifd, gufo_common::field::MakerNote::TAG)
+                && let Some(maker_note) =
+                    handle_error_(exif_ifd.entry_ref(gufo_common::field::MakerNote::TAG)).flatten()
             {
-                ifds.insert(IfdId::MakerNote, (offset, maker_info_ifd));
+                let len = maker_note.count;
+                let header = self
+                    .peek_bytes(offset, len.min(maker_note::HEADER_LEN))
+                    .unwrap_or_default();
+                let (vendor, layout) =
+                    maker_note::detect(make.as_deref(), header, self.endieness());
+
+                match layout {
+                    maker_note::Layout::Ifd => {
+                        if handle_error_(self.seek_absolute(offset)).is_some()
+                            && let Some(maker_info_ifd) =
+                                handle_error_(self.read_ifd(IfdId::MakerNote(vendor)))
+                        {
+                            ifds.insert(IfdId::MakerNote(vendor), (offset, maker_info_ifd));
+                        }
+                    }
+                    layout @ (maker_note::Layout::EmbeddedTiff { .. }
+                    | maker_note::Layout::RelativeIfd { .. }) => {
+                        // Only take the data if they are available in one piece
+                        if self.peek_bytes(offset, len).is_some()
+                            && let Some(data) = handle_error_(self.read_bytes_at(offset, len))
+                        {
+                            embedded_maker_note = Some(RawMakerNote {
+                                vendor,
+                                pos: offset,
+                                layout,
+                                data,
+                            });
+                        }
+                    }
+                }
             }
 
-            if let Some(mut interoperability_ifd) =
-                exif_ifd.entry_by_tag(gufo_common::field::InteroperabilityIfd::TAG)
-                && let Some(offset) =
-                    handle_error_(interoperability_ifd.ifd_pointer()).map(|x| x as usize)
+            if let Some(offset) =
+                Self::ifd_pointer(&exif_ifd, gufo_common::field::InteroperabilityIfd::TAG)
             {
-                self.seek_absolute(offset)?;
-                let interoperability_ifd_content = self.read_ifd(IfdId::Interoperability)?;
-                ifds.insert(
-                    IfdId::Interoperability,
-                    (offset, interoperability_ifd_content),
-                );
+                match self.read_ifd_at(IfdId::Interoperability, offset) {
+                    Ok(interoperability_ifd_content) => {
+                        ifds.insert(
+                            IfdId::Interoperability,
+                            (offset, interoperability_ifd_content),
+                        );
+                    }
+                    Err(err) => skip(IfdId::Interoperability, offset, err)?,
+                }
             }
 
             ifds.insert(IfdId::Exif, (offset, exif_ifd));
         }
 
         // Read GPS Info Ifd if available
-        if let Some(mut gps_ifd_pointer) =
-            primary_ifd.entry_by_tag(gufo_common::field::GPSInfoIFDPointer::TAG)
-            && let Some(offset) = handle_error_(gps_ifd_pointer.ifd_pointer()).map(|x| x as usize)
+        if let Some(offset) =
+            Self::ifd_pointer(&primary_ifd, gufo_common::field::GPSInfoIFDPointer::TAG)
+        {
+            match self.read_ifd_at(IfdId::Gps, offset) {
+                Ok(gps_info_ifd) => {
+                    ifds.insert(IfdId::Gps, (offset, gps_info_ifd));
+                }
+                Err(err) => skip(IfdId::Gps, offset, err)?,
+            }
+        }
+
+        // Read Thumbnail Ifd if available, it's the next Ifd after the primary Ifd
+        if let Some(offset) = handle_error_(primary_ifd.next_ifd_offset())
+            && offset != 0
+            && handle_error_(self.seek_absolute(offset)).is_some()
+            && let Some(thumbnail_ifd) = handle_error_(self.read_ifd(IfdId::Thumbnail))
         {
-            self.seek_absolute(offset)?;
-            let gps_info_ifd = self.read_ifd(IfdId::Gps)?;
-            ifds.insert(IfdId::Gps, (offset, gps_info_ifd));
+            ifds.insert(IfdId::Thumbnail, (offset, thumbnail_ifd));
         }
 
         ifds.insert(IfdId::Primary, (primary_ifd_offset, primary_ifd));
@@ -113,7 +199,29 @@ impl<'a> Parser<'a> {
         // entrties
         self.read_remaining_data()?;
 
-        Ok(ifds)
+        Ok(Parsed {
+            ifds,
+            maker_note: embedded_maker_note,
+            diagnostics,
+        })
+    }
+
+    /// Offset in an entry that points to an IFD
+    ///
+    /// Returns `None` if the entry doesn't exist or isn't a valid pointer.
+    fn ifd_pointer(ifd: &Ifd<'a, B>, tag: Tag) -> Option<usize> {
+        handle_error_(ifd.ifd_pointer(tag)).flatten()
+    }
+
+    /// Seek to `offset` and read the IFD there
+    pub fn read_ifd_at(&mut self, ifd: IfdId, offset: usize) -> Result<Ifd<'a, B>, Error> {
+        self.seek_absolute(offset)?;
+        self.read_ifd(ifd)
+    }
+
+    /// Position of the offset to the primary IFD in the header
+    pub fn primary_ifd_offset_pos(&self) -> usize {
+        if self.index_size() == 8 { 8 } else { 4 }
     }
 
     pub fn n_entries_size(&self) -> usize {
@@ -132,15 +240,19 @@ impl<'a> Parser<'a> {
         crate::forall_formats_self!(self, file, file.read_primary_ifd_offset())
     }
 
-    pub fn read_u16(&mut self) -> Result<u16, Error> {
-        crate::forall_formats_self!(self, file, Ok(file.read_u16()?.get()))
-    }
-
     pub fn seek_absolute(&mut self, abs_pos: usize) -> Result<(), Error> {
         crate::forall_formats_self!(self, file, file.seek_absolute(abs_pos))
     }
 
-    pub fn read_ifd(&mut self, ifd: IfdId) -> Result<Ifd<'a>, Error> {
+    /// Take `n_bytes` at `pos` out of the data
+    pub fn read_bytes_at(&mut self, pos: usize, n_bytes: usize) -> Result<B, Error> {
+        crate::forall_formats_self!(self, file, {
+            file.seek_absolute(pos)?;
+            file.read_bytes(n_bytes)
+        })
+    }
+
+    pub fn read_ifd(&mut self, ifd: IfdId) -> Result<Ifd<'a, B>, Error> {
         Ok(match self {
             Parser::Be32(x) => Ifd::Be32(x.read_ifd(ifd)?),
             Parser::Le32(x) => Ifd::Le32(x.read_ifd(ifd)?),
@@ -149,7 +261,21 @@ impl<'a> Parser<'a> {
         })
     }
 
-    pub fn data(self) -> (&'a mut [u8], Vec<(usize, &'a mut [u8])>) {
+    /// Raw value of an entry without consuming the data
+    pub fn entry_value(&self, entry: &EntryRef) -> Option<Vec<u8>> {
+        let len = handle_error_((cheq(entry.count) * entry.type_.size()).check())?;
+
+        match entry.value_or_offset {
+            ValueOrOffsetRef::Value(value) => value.get(..len).map(|x| x.to_vec()),
+            ValueOrOffsetRef::Offset(offset) => self.peek_bytes(offset, len).map(|x| x.to_vec()),
+        }
+    }
+
+    pub fn peek_bytes(&self, pos: usize, n_bytes: usize) -> Option<&[u8]> {
+        crate::forall_formats_self!(self, file, file.peek_bytes(pos, n_bytes))
+    }
+
+    pub fn data(self) -> (B, Vec<(usize, B)>) {
         crate::forall_formats_self!(self, file, (file.primary_ifd_offset, file.data))
     }
 
@@ -165,34 +291,79 @@ impl<'a> Parser<'a> {
     }
 }
 
+/// IFDs with their position
+pub(crate) type Ifds<'a, B = &'a mut [u8]> = BTreeMap<IfdId, (usize, Ifd<'a, B>)>;
+
+/// Result of parsing a complete Exif structure
+#[derive(Debug)]
+pub(crate) struct Parsed<'a, B: Bytes<'a>> {
+    pub ifds: Ifds<'a, B>,
+    pub maker_note: Option<RawMakerNote<B>>,
+    /// IFDs that have been skipped in [`ParseMode::Lenient`]
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+/// MakerNote with its own TIFF structure
+///
+/// The data are not part of the document's data blocks.
+#[derive(Debug)]
+pub(crate) struct RawMakerNote<B> {
+    pub vendor: Vendor,
+    /// Position of the MakerNote in the Exif data
+    pub pos: usize,
+    pub layout: maker_note::Layout,
+    pub data: B,
+}
+
 #[derive(Debug)]
-pub(crate) struct ParserGeneric<'a, T, O> {
-    remaining_data: &'a mut [u8],
+pub(crate) struct ParserGeneric<'a, T, O, B: Bytes<'a>> {
+    remaining_data: B,
     remaining_data_pos: usize,
     pos: usize,
     // Data sections where either data referenced from Ifds lives or Ifds that haven't been parsed
     // yet live
-    data: Vec<(usize, &'a mut [u8])>,
+    data: Vec<(usize, B)>,
     pointer_type: PhantomData<T>,
     endieness: PhantomData<O>,
-    primary_ifd_offset: &'a mut [u8],
+    lifetime: PhantomData<&'a ()>,
+    primary_ifd_offset: B,
 }
 
-impl<'a, T: IndexType, O: ByteOrder> ParserGeneric<'a, T, O> {
-    fn new(remaining_data: &'a mut [u8]) -> Self {
+impl<'a, T: IndexType, O: ByteOrder + 'static, B: Bytes<'a>> ParserGeneric<'a, T, O, B> {
+    fn new(remaining_data: B) -> Self {
         Self {
             remaining_data,
             pos: 0,
             remaining_data_pos: 0,
             pointer_type: Default::default(),
             endieness: Default::default(),
+            lifetime: Default::default(),
             primary_ifd_offset: Default::default(),
             data: Default::default(),
         }
     }
 
+    fn for_data_blocks(data: Vec<(usize, B)>) -> Result<Self, Error> {
+        // Nothing remains to be read behind the blocks
+        let end = data
+            .iter()
+            .map(|(pos, data)| cheq(*pos) + data.as_slice().len())
+            .try_fold(0, |len, end| end.check().map(|end| len.max(end)))?;
+
+        Ok(Self {
+            remaining_data: Default::default(),
+            pos: end,
+            remaining_data_pos: end,
+            pointer_type: Default::default(),
+            endieness: Default::default(),
+            lifetime: Default::default(),
+            primary_ifd_offset: Default::default(),
+            data,
+        })
+    }
+
     /// Read specified number of bytes
-    fn read_bytes(&mut self, n_bytes: usize) -> Result<&'a mut [u8], Error> {
+    fn read_bytes(&mut self, n_bytes: usize) -> Result<B, Error> {
         if self.remaining_data_pos == self.pos {
             #[cfg(feature = "tracing")]
             tracing::trace!(
@@ -200,10 +371,14 @@ impl<'a, T: IndexType, O: ByteOrder> ParserGeneric<'a, T, O> {
                 self.pos
             );
 
+            if n_bytes > self.remaining_data.as_slice().len() {
+                return Err(Error::IndexOverflow);
+            }
+
             let current_data = std::mem::take(&mut self.remaining_data);
 
             let (read, remaining_data) = current_data
-                .split_at_mut_checked(n_bytes)
+                .split_at_checked(n_bytes)
                 .ok_or(Error::IndexOverflow)?;
 
             self.remaining_data = remaining_data;
@@ -219,15 +394,22 @@ impl<'a, T: IndexType, O: ByteOrder> ParserGeneric<'a, T, O> {
                 self.pos
             );
 
-            let data_index = self
+            let (data_index, (block_pos, block)) = self
                 .data
                 .iter()
                 .enumerate()
                 .try_find_(|(_, (pos, x))| {
-                    Ok::<_, MathError>((*pos..(cheq(*pos) + x.len()).check()?).contains(&self.pos))
+                    Ok::<_, MathError>(
+                        (*pos..(cheq(*pos) + x.as_slice().len()).check()?).contains(&self.pos),
+                    )
                 })?
-                .ok_or(Error::IndexUsed)?
-                .0;
+                .ok_or(Error::IndexUsed)?;
+
+            if (cheq(self.pos) + n_bytes).check()?
+                > (cheq(*block_pos) + block.as_slice().len()).check()?
+            {
+                return Err(Error::IndexOverflow);
+            }
 
             let (pos, data) = self.data.swap_remove(data_index);
 
@@ -242,7 +424,7 @@ impl<'a, T: IndexType, O: ByteOrder> ParserGeneric<'a, T, O> {
                 );
 
                 let (new_data_chunk, data) = data
-                    .split_at_mut_checked(remaining)
+                    .split_at_checked(remaining)
                     .ok_or(Error::IndexOverflow)?;
 
                 self.data.push((pos, new_data_chunk));
@@ -251,10 +433,11 @@ impl<'a, T: IndexType, O: ByteOrder> ParserGeneric<'a, T, O> {
                 data
             };
 
-            let (read, remaining_data) = data.split_at_mut_checked(n_bytes).unwrap();
-            let new_pos = (cheq(self.pos) + read.len()).check()?;
+            let (read, remaining_data) =
+                data.split_at_checked(n_bytes).ok_or(Error::IndexOverflow)?;
+            let new_pos = (cheq(self.pos) + read.as_slice().len()).check()?;
 
-            if !remaining_data.is_empty() {
+            if !remaining_data.as_slice().is_empty() {
                 self.data.push((new_pos, remaining_data));
             }
 
@@ -264,45 +447,58 @@ impl<'a, T: IndexType, O: ByteOrder> ParserGeneric<'a, T, O> {
         }
     }
 
-    /// Read index value, 32 bit for TIFF, 64 bit for BigTIFF
-    fn read_index(&mut self) -> Result<&'a mut T, Error> {
-        let bytes = self.read_bytes(std::mem::size_of::<T>())?;
+    /// Data at `pos` without consuming them
+    ///
+    /// Returns `None` if the data are not available in one piece.
+    fn peek_bytes(&self, pos: usize, n_bytes: usize) -> Option<&[u8]> {
+        let end = pos.checked_add(n_bytes)?;
+
+        if let Some(relative_pos) = pos.checked_sub(self.remaining_data_pos) {
+            let relative_end = end.checked_sub(self.remaining_data_pos)?;
+            return self
+                .remaining_data
+                .as_slice()
+                .get(relative_pos..relative_end);
+        }
 
-        T::mut_from_bytes(bytes).map_err(Into::into)
+        self.data.iter().find_map(|(data_pos, data)| {
+            let relative_pos = pos.checked_sub(*data_pos)?;
+            let relative_end = end.checked_sub(*data_pos)?;
+            data.as_slice().get(relative_pos..relative_end)
+        })
     }
 
-    fn read_primary_ifd_offset(&mut self) -> Result<usize, Error> {
-        let index = self.read_index()?;
-        let index_usize = index.try_to_usize()?;
-        self.primary_ifd_offset = index.as_mut_bytes();
-
-        Ok(index_usize)
+    /// Read index value, 32 bit for TIFF, 64 bit for BigTIFF
+    fn read_index(&mut self) -> Result<B::Ref<T>, Error> {
+        self.read_bytes(std::mem::size_of::<T>())?.cast()
     }
 
-    fn read_u16(&mut self) -> Result<&'a mut U16<O>, Error> {
-        let bytes = self.read_bytes(2)?;
+    fn read_primary_ifd_offset(&mut self) -> Result<usize, Error> {
+        let bytes = self.read_bytes(std::mem::size_of::<T>())?;
+        let index_usize = T::ref_from_bytes(bytes.as_slice())?.try_to_usize()?;
+        self.primary_ifd_offset = bytes;
 
-        U16::<O>::mut_from_bytes(bytes).map_err(Into::into)
+        Ok(index_usize)
     }
 
     fn seek_absolute(&mut self, abs_pos: usize) -> Result<(), Error> {
-        let pos = self.pos;
-
         #[cfg(feature = "tracing")]
-        tracing::trace!("Seeking absolute to {abs_pos} from current {pos}");
+        tracing::trace!("Seeking absolute to {abs_pos} from current {}", self.pos);
 
-        if let Some(relative_position) = abs_pos.checked_sub(self.pos) {
-            // Seeking forward
+        if let Some(relative_position) = abs_pos.checked_sub(self.remaining_data_pos) {
+            // Seeking into the data that haven't been read yet
+            let pos = self.remaining_data_pos;
+            self.pos = pos;
             let bytes = self.read_bytes(relative_position)?;
 
-            if !bytes.is_empty() {
+            if !bytes.as_slice().is_empty() {
                 self.data.push((pos, bytes));
             }
 
             Ok(())
         } else {
             let pos_found = self.data.iter().try_any_(|(pos, x)| {
-                let pos_end = (cheq(*pos) + x.len()).check()?;
+                let pos_end = (cheq(*pos) + x.as_slice().len()).check()?;
                 let range = *pos..pos_end;
                 Ok::<_, MathError>(range.contains(&abs_pos))
             })?;
@@ -319,33 +515,45 @@ impl<'a, T: IndexType, O: ByteOrder> ParserGeneric<'a, T, O> {
     /// Read number of entries in an ifd
     ///
     /// This number is located directly before the list of entries.
-    fn read_n_entries(&mut self) -> Result<&'a mut T::NEntries, Error> {
-        let bytes = self.read_bytes(std::mem::size_of::<T::NEntries>())?;
-        T::NEntries::mut_from_bytes(bytes).map_err(Into::into)
+    fn read_n_entries(&mut self) -> Result<B::Ref<T::NEntries>, Error> {
+        self.read_bytes(std::mem::size_of::<T::NEntries>())?.cast()
     }
 
     /// Read one entry from the ifd
-    fn read_entry(&mut self) -> Result<&'a mut EntryGeneric<T, O>, Error> {
+    fn read_entry(&mut self) -> Result<B::Ref<EntryGeneric<T, O>>, Error> {
         let size = self.entry_size();
 
-        let bytes = self.read_bytes(size)?;
-
-        EntryGeneric::mut_from_bytes(bytes).map_err(Into::into)
+        self.read_bytes(size)?.cast()
     }
 
     /// Read entries from ifd
-    fn read_ifd(&mut self, ifd: IfdId) -> Result<IfdGeneric<'a, T, O>, Error> {
+    ///
+    /// No data are consumed if the IFD can't be read.
+    fn read_ifd(&mut self, ifd: IfdId) -> Result<IfdGeneric<'a, T, O, B>, Error> {
         #[cfg(feature = "tracing")]
         tracing::trace!("{ifd:?}: Reading n-entries");
 
+        // Check that the complete IFD is available before consuming anything
+        let n_entries_bytes = self
+            .peek_bytes(self.pos, self.n_entries_size())
+            .ok_or(Error::IndexOverflow)?;
+        let n_entries = T::NEntries::ref_from_bytes(n_entries_bytes)?.try_to_usize()?;
+        let ifd_len =
+            (cheq(self.n_entries_size()) + cheq(n_entries) * self.entry_size() + self.index_size())
+                .check()?;
+        self.peek_bytes(self.pos, ifd_len)
+            .ok_or(Error::IndexOverflow)?;
+
         let n_entries = self.read_n_entries()?;
 
         #[cfg(feature = "tracing")]
-        tracing::trace!("{ifd:?}: Has {n_entries} entries");
+        tracing::trace!("{ifd:?}: Has {} entries", *n_entries);
 
         let mut entries = IndexMap::new();
+        let mut stored_tags = Vec::new();
         for _ in 0..n_entries.try_to_usize()? {
             let entry = self.read_entry()?;
+            stored_tags.push(entry.tag_id.get());
             entries.insert(entry.tag_id.get(), entry);
         }
 
@@ -355,22 +563,27 @@ impl<'a, T: IndexType, O: ByteOrder> ParserGeneric<'a, T, O> {
         let ifd_offset = self.read_index()?;
 
         #[cfg(feature = "tracing")]
-        tracing::trace!("{ifd:?}: Next Ifd at {ifd_offset}");
+        tracing::trace!("{ifd:?}: Next Ifd at {}", *ifd_offset);
 
         Ok(IfdGeneric {
             namespace: ifd,
             n_entries,
             entries,
+            stored_tags,
             next_ifd_offset: ifd_offset,
         })
     }
 
     fn read_remaining_data(&mut self) -> Result<(), Error> {
         let remaining_data = std::mem::take(&mut self.remaining_data);
-        let pos = self.pos;
-        self.pos = (cheq(self.pos) + remaining_data.len()).check()?;
+        // The current position can be in front of the remaining data after seeking back
+        let pos = self.remaining_data_pos;
+        self.pos = (cheq(pos) + remaining_data.as_slice().len()).check()?;
+        self.remaining_data_pos = self.pos;
 
-        self.data.push((pos, remaining_data));
+        if !remaining_data.as_slice().is_empty() {
+            self.data.push((pos, remaining_data));
+        }
         Ok(())
     }
 
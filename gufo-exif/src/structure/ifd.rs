@@ -4,20 +4,20 @@ use gufo_common::exif::{IfdId, Tag};
 use indexmap::IndexMap;
 use zerocopy::{BigEndian, ByteOrder, IntoBytes, LittleEndian, U32, U64};
 
-use super::util::IndexType;
-use super::{Entry, EntryGeneric};
+use super::util::{Bytes, IndexType};
+use super::{Entry, EntryGeneric, EntryRef};
 use crate::error::Error;
 use crate::structure::util::UsizeConversion;
 
 #[derive(Debug)]
-pub enum Ifd<'a> {
-    Le32(IfdGeneric<'a, U32<LittleEndian>, LittleEndian>),
-    Be32(IfdGeneric<'a, U32<BigEndian>, BigEndian>),
-    Le64(IfdGeneric<'a, U64<LittleEndian>, LittleEndian>),
-    Be64(IfdGeneric<'a, U64<BigEndian>, BigEndian>),
+pub enum Ifd<'a, B: Bytes<'a> = &'a mut [u8]> {
+    Le32(IfdGeneric<'a, U32<LittleEndian>, LittleEndian, B>),
+    Be32(IfdGeneric<'a, U32<BigEndian>, BigEndian, B>),
+    Le64(IfdGeneric<'a, U64<LittleEndian>, LittleEndian, B>),
+    Be64(IfdGeneric<'a, U64<BigEndian>, BigEndian, B>),
 }
 
-impl<'a> Ifd<'a> {
+impl<'a, B: Bytes<'a>> Ifd<'a, B> {
     pub fn serialize(&self) -> Vec<u8> {
         // Ifd entry list starts with number of entries
         let mut vec = crate::forall_formats_self!(self, ifd, ifd.n_entries.as_bytes()).to_vec();
@@ -39,14 +39,6 @@ impl<'a> Ifd<'a> {
         vec
     }
 
-    pub fn set_n_entries(&mut self, n_entires: usize) -> Result<(), Error> {
-        crate::forall_formats_self!(self, ifd, ifd.set_n_entries(n_entires))
-    }
-
-    pub fn entry_by_tag(&mut self, tag: Tag) -> Option<Entry<'_>> {
-        crate::forall_formats_self!(self, ifd, Some(ifd.entries.get_mut(&tag.0)?.as_entry()))
-    }
-
     pub fn list_entry_relative_offset(&self, tag: Tag) -> Option<usize> {
         crate::forall_formats_self!(self, ifd, ifd.entries.get_index_of(&tag.0))
     }
@@ -55,6 +47,78 @@ impl<'a> Ifd<'a> {
         crate::forall_formats_self!(self, ifd, ifd.namespace)
     }
 
+    pub fn n_entries(&self) -> usize {
+        crate::forall_formats_self!(self, ifd, ifd.entries.len())
+    }
+
+    /// Number of entries as stored in front of the entry list
+    ///
+    /// Differs from [`n_entries`](Self::n_entries) if tags occur more than
+    /// once since only the last entry of a tag is kept.
+    pub fn stored_n_entries(&self) -> Result<usize, Error> {
+        crate::forall_formats_self!(self, ifd, ifd.n_entries.try_to_usize())
+    }
+
+    /// Tags of all entries in the order they are stored
+    pub fn tags(&self) -> Vec<Tag> {
+        crate::forall_formats_self!(self, ifd, ifd.entries.keys().map(|x| Tag(*x)).collect())
+    }
+
+    /// Tags of the stored entry list including tags that occur more than once
+    pub fn stored_tags(&self) -> Vec<Tag> {
+        crate::forall_formats_self!(self, ifd, ifd.stored_tags.iter().map(|x| Tag(*x)).collect())
+    }
+
+    /// Index of the entry of `tag` in the stored entry list
+    ///
+    /// For tags that occur more than once, this is the index of the last
+    /// entry, which is the one that is used.
+    pub fn stored_entry_index(&self, tag: Tag) -> Option<usize> {
+        crate::forall_formats_self!(self, ifd, ifd.stored_tags.iter().rposition(|x| *x == tag.0))
+    }
+
+    /// Offset of the next IFD in the chain
+    ///
+    /// The value is zero if this is the last IFD.
+    pub fn next_ifd_offset(&self) -> Result<usize, Error> {
+        crate::forall_formats_self!(self, ifd, ifd.next_ifd_offset.try_to_usize())
+    }
+
+    /// Type, count, and value or offset of the entry of `tag`
+    pub(crate) fn entry_ref(&self, tag: Tag) -> Result<Option<EntryRef<'_>>, Error> {
+        crate::forall_formats_self!(self, ifd, {
+            let Some((n_entry, _, entry)) = ifd.entries.get_full(&tag.0) else {
+                return Ok(None);
+            };
+
+            Ok(Some(EntryRef {
+                n_entry,
+                type_: entry.type_(),
+                count: entry.count()?,
+                value_or_offset: entry.value_or_offset_ref()?,
+            }))
+        })
+    }
+
+    /// Offset stored in the entry of `tag` that points to an IFD
+    pub(crate) fn ifd_pointer(&self, tag: Tag) -> Result<Option<usize>, Error> {
+        crate::forall_formats_self!(
+            self,
+            ifd,
+            ifd.entries.get(&tag.0).map(|x| x.ifd_pointer()).transpose()
+        )
+    }
+}
+
+impl<'a> Ifd<'a> {
+    pub fn set_n_entries(&mut self, n_entires: usize) -> Result<(), Error> {
+        crate::forall_formats_self!(self, ifd, ifd.set_n_entries(n_entires))
+    }
+
+    pub fn entry_by_tag(&mut self, tag: Tag) -> Option<Entry<'_>> {
+        crate::forall_formats_self!(self, ifd, Some(ifd.entries.get_mut(&tag.0)?.as_entry()))
+    }
+
     pub fn entries(&mut self) -> Result<BTreeMap<Tag, Entry<'_>>, Error> {
         crate::forall_formats_self!(
             self,
@@ -66,23 +130,56 @@ impl<'a> Ifd<'a> {
         )
     }
 
-    pub fn n_entries(&self) -> usize {
-        crate::forall_formats_self!(self, ifd, ifd.entries.len())
+    pub fn set_next_ifd_offset(&mut self, offset: usize) -> Result<(), Error> {
+        crate::forall_formats_self!(self, ifd, ifd.set_next_ifd_offset(offset))
+    }
+
+    /// Remove the entry of `tag` from the entry list
+    ///
+    /// The entries behind it move up when serializing. The returned bytes of
+    /// the removed entry are overwritten with zeros and can fill the space
+    /// that is freed at the end of the list.
+    pub fn remove_entry(&mut self, tag: Tag) -> Result<Option<&'a mut [u8]>, Error> {
+        crate::forall_formats_self!(self, ifd, ifd.remove_entry(tag))
     }
 }
 
 #[derive(Debug)]
-pub struct IfdGeneric<'a, T: IndexType, O: ByteOrder> {
+pub struct IfdGeneric<'a, T: IndexType, O: ByteOrder + 'static, B: Bytes<'a> = &'a mut [u8]> {
     pub namespace: IfdId,
-    pub n_entries: &'a mut T::NEntries,
-    pub entries: IndexMap<u16, &'a mut EntryGeneric<T, O>>,
-    pub next_ifd_offset: &'a mut T,
+    pub n_entries: B::Ref<T::NEntries>,
+    pub entries: IndexMap<u16, B::Ref<EntryGeneric<T, O>>>,
+    /// Tags in the order of the stored entry list
+    pub stored_tags: Vec<u16>,
+    pub next_ifd_offset: B::Ref<T>,
 }
 
-impl<'a, T: IndexType, O: ByteOrder> IfdGeneric<'a, T, O> {
+impl<'a, T: IndexType, O: ByteOrder + 'static> IfdGeneric<'a, T, O> {
     fn set_n_entries(&mut self, n_entires: usize) -> Result<(), Error> {
         *self.n_entries = T::NEntries::try_from_usize(n_entires)?;
 
         Ok(())
     }
+
+    fn set_next_ifd_offset(&mut self, offset: usize) -> Result<(), Error> {
+        *self.next_ifd_offset = T::try_from_usize(offset)?;
+
+        Ok(())
+    }
+
+    fn remove_entry(&mut self, tag: Tag) -> Result<Option<&'a mut [u8]>, Error> {
+        let Some(entry) = self.entries.shift_remove(&tag.0) else {
+            return Ok(None);
+        };
+
+        if let Some(index) = self.stored_tags.iter().rposition(|x| *x == tag.0) {
+            self.stored_tags.remove(index);
+        }
+        self.set_n_entries(self.entries.len())?;
+
+        let bytes = entry.as_mut_bytes();
+        bytes.fill(0);
+
+        Ok(Some(bytes))
+    }
 }
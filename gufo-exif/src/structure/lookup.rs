@@ -0,0 +1,647 @@
+use std::ops::Range;
+
+use gufo_common::exif::TagIfd;
+use gufo_common::types::Rational;
+use gufo_common::{capture, field, geography, hardware, orientation};
+
+use crate::Error;
+use crate::maker_note::{Vendor, apple, canon, nikon};
+use crate::structure::util::{Endieness, handle_error, handle_error_};
+use crate::structure::{CODE_ASCII, CODE_JIS, CODE_UNICODE, Type, Typed};
+
+/// Read access to the entries of an Exif structure
+///
+/// Implemented by [`Document`](super::Document) and
+/// [`ExifRef`](crate::ExifRef). All typed lookups and the convenience
+/// functions for frequently used fields are based on
+/// [`lookup`](Self::lookup). See [`Exif`](crate::Exif) for the documentation
+/// of the convenience functions.
+pub trait Lookup {
+    /// Lookup entry with arbitrary type
+    fn lookup(&mut self, tag_ifd: TagIfd) -> Result<Option<Typed>, Error>;
+
+    /// Byte order of the data
+    fn endieness(&self) -> Endieness;
+
+    /// Manufacturer that defines the structure of the MakerNote
+    fn maker_note_vendor(&self) -> Option<Vendor>;
+
+    /// Raw bytes at `range`, with positions relative to the TIFF header
+    fn raw_data(&mut self, range: Range<usize>) -> Option<&[u8]>;
+
+    /// Lookup entry with ASCII or UTF-8 entry
+    fn lookup_string_raw(&mut self, tag_ifd: TagIfd) -> Result<Option<String>, Error> {
+        let Some(typed) = self.lookup(tag_ifd)? else {
+            return Ok(None);
+        };
+
+        match typed {
+            Typed::Ascii(ascii) => Ok(Some(String::from_utf8_lossy(&ascii).to_string())),
+            Typed::Utf8(utf8) => Ok(Some(utf8)),
+            _ => Err(Error::TypeMissmatch(
+                typed.type_(),
+                &[Type::Ascii, Type::Utf8],
+            )),
+        }
+    }
+
+    fn lookup_string(&mut self, tag_ifd: TagIfd) -> Result<Option<String>, Error> {
+        let Some(mut s) = self.lookup_string_raw(tag_ifd)? else {
+            return Ok(None);
+        };
+
+        if let Some(index) = s.find('\0') {
+            s.truncate(index);
+        }
+
+        Ok(if s.is_empty() { None } else { Some(s) })
+    }
+
+    /// Lookup entry with multiple short values
+    fn lookup_shorts(&mut self, tag_ifd: TagIfd) -> Result<Option<Vec<u16>>, Error> {
+        let Some(typed) = self.lookup(tag_ifd)? else {
+            return Ok(None);
+        };
+
+        if let Typed::Short(shorts) = typed {
+            Ok(Some(shorts))
+        } else {
+            Err(Error::TypeMissmatch(typed.type_(), &[Type::Short]))
+        }
+    }
+
+    /// Lookup entry with single short entry
+    fn lookup_short(&mut self, tag_ifd: TagIfd) -> Result<Option<u16>, Error> {
+        let Some(vec) = self.lookup_shorts(tag_ifd)? else {
+            return Ok(None);
+        };
+
+        if let Some((first, rest)) = vec.split_first() {
+            if !rest.is_empty() {
+                Err(Error::ElementCountMissmatch(vec.len(), 1))
+            } else {
+                Ok(Some(*first))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Lookup entry with unsigned integers of type byte, short, or long
+    fn lookup_uints(&mut self, tag_ifd: TagIfd) -> Result<Option<Vec<u32>>, Error> {
+        let Some(typed) = self.lookup(tag_ifd)? else {
+            return Ok(None);
+        };
+
+        match typed {
+            Typed::Byte(bytes) => Ok(Some(bytes.into_iter().map(u32::from).collect())),
+            Typed::Short(shorts) => Ok(Some(shorts.into_iter().map(u32::from).collect())),
+            Typed::Long(longs) => Ok(Some(longs)),
+            _ => Err(Error::TypeMissmatch(
+                typed.type_(),
+                &[Type::Byte, Type::Short, Type::Long],
+            )),
+        }
+    }
+
+    /// Lookup entry with offsets or byte counts of type short, long, or long8
+    ///
+    /// BigTIFF uses long8 for offsets and byte counts.
+    fn lookup_offsets(&mut self, tag_ifd: TagIfd) -> Result<Option<Vec<usize>>, Error> {
+        let Some(typed) = self.lookup(tag_ifd)? else {
+            return Ok(None);
+        };
+
+        let offsets = match typed {
+            Typed::Short(shorts) => shorts.into_iter().map(usize::from).collect(),
+            Typed::Long(longs) => longs
+                .into_iter()
+                .map(usize::try_from)
+                .collect::<Result<_, _>>()?,
+            Typed::Long8(long8s) => long8s
+                .into_iter()
+                .map(usize::try_from)
+                .collect::<Result<_, _>>()?,
+            _ => {
+                return Err(Error::TypeMissmatch(
+                    typed.type_(),
+                    &[Type::Short, Type::Long, Type::Long8],
+                ));
+            }
+        };
+
+        Ok(Some(offsets))
+    }
+
+    /// Lookup entry with multiple rational entries
+    fn lookup_rationals<const N: usize>(
+        &mut self,
+        tag_ifd: TagIfd,
+    ) -> Result<Option<[Rational<u32>; N]>, Error> {
+        let Some(typed) = self.lookup(tag_ifd)? else {
+            return Ok(None);
+        };
+
+        if let Typed::Rational(rationals) = typed {
+            let len = rationals.len();
+            Ok(Some(
+                rationals
+                    .try_into()
+                    .map_err(|_| Error::ElementCountMissmatch(len, N))?,
+            ))
+        } else {
+            Err(Error::TypeMissmatch(typed.type_(), &[Type::Rational]))
+        }
+    }
+
+    /// Lookup entry with single rational entry
+    fn lookup_rational(&mut self, tag_ifd: TagIfd) -> Result<Option<Rational<u32>>, Error> {
+        Ok(self.lookup_rationals::<1>(tag_ifd)?.map(|x| x[0]))
+    }
+
+    /// Lookup entry with multiple signed rational entries
+    fn lookup_srationals<const N: usize>(
+        &mut self,
+        tag_ifd: TagIfd,
+    ) -> Result<Option<[Rational<i32>; N]>, Error> {
+        let Some(typed) = self.lookup(tag_ifd)? else {
+            return Ok(None);
+        };
+
+        if let Typed::SRational(rationals) = typed {
+            let len = rationals.len();
+            Ok(Some(
+                rationals
+                    .try_into()
+                    .map_err(|_| Error::ElementCountMissmatch(len, N))?,
+            ))
+        } else {
+            Err(Error::TypeMissmatch(typed.type_(), &[Type::SRational]))
+        }
+    }
+
+    /// Lookup entry with single signed rational entry
+    fn lookup_srational(&mut self, tag_ifd: TagIfd) -> Result<Option<Rational<i32>>, Error> {
+        Ok(self.lookup_srationals::<1>(tag_ifd)?.map(|x| x[0]))
+    }
+
+    /// Lookupe entry with character identified code
+    ///
+    /// Exif 3.0: 4.6.4. Character Identifier Code
+    ///
+    /// Written via [`Typed::character_identified_code_string`].
+    fn lookup_character_identified_code_string(
+        &mut self,
+        tagifd: TagIfd,
+    ) -> Result<Option<String>, Error> {
+        let Some(data) = self.lookup(tagifd)? else {
+            return Ok(None);
+        };
+
+        // The standard only defines Undefined here, but others are used in the wild
+        let data = match data {
+            Typed::Undefined(unfedined) => unfedined,
+            Typed::Ascii(ascii) => ascii,
+            Typed::Utf8(utf8) => utf8.into_bytes(),
+            _ => {
+                return Err(Error::TypeMissmatch(data.type_(), &[Type::Undefined]));
+            }
+        };
+
+        let s = if let Some(ascii) = data.strip_prefix(CODE_ASCII) {
+            String::from_utf8_lossy(ascii).to_string()
+        } else if let Some(jis) = data.strip_prefix(CODE_JIS) {
+            // JIS X0208 is not decoded, but often only ASCII characters are used
+            String::from_utf8_lossy(jis).to_string()
+        } else if let Some(unicode) = data.strip_prefix(CODE_UNICODE) {
+            // UTF-16 in the byte order of the data, like it's written and converted
+            // between byte orders. UTF-8 can't be told apart reliably, since UTF-16
+            // code units of characters like "中" are valid UTF-8 as well.
+            let u16_vec = match self.endieness() {
+                Endieness::Big => unicode
+                    .chunks_exact(2)
+                    .map(|x| u16::from_be_bytes(x.try_into().unwrap()))
+                    .collect::<Vec<_>>(),
+                Endieness::Litte => unicode
+                    .chunks_exact(2)
+                    .map(|x| u16::from_le_bytes(x.try_into().unwrap()))
+                    .collect::<Vec<_>>(),
+            };
+
+            if let Ok(x) = String::from_utf16(&u16_vec) {
+                x
+            } else {
+                // Fallback to UTF-8 lossy for data that aren't valid UTF-16
+                String::from_utf8_lossy(unicode).to_string()
+            }
+        } else {
+            // Don't expect leading NULLs here since sometimes the content starts directly
+            String::from_utf8_lossy(&data).to_string()
+        };
+
+        // Remove potential leading NULLs and all others since some cameras fill with
+        // NULLs at the end
+        let s = s.replace('\0', "");
+
+        if s.is_empty() { Ok(None) } else { Ok(Some(s)) }
+    }
+
+    fn artist(&mut self) -> Option<String> {
+        handle_error(self.lookup_string(field::Artist.into()))
+    }
+
+    fn camera_owner_name(&mut self) -> Option<String> {
+        if let Some(s) = handle_error(self.lookup_string(field::CameraOwnerName.into())) {
+            Some(s)
+        } else {
+            handle_error(self.lookup_string(field::CanonCameraOwnerName.into()))
+        }
+    }
+
+    fn color_space(&mut self) -> Option<capture::ColorSpace> {
+        handle_error(self.lookup_short(field::ColorSpace.into())).map(capture::ColorSpace::from)
+    }
+
+    fn contrast(&mut self) -> Option<capture::Contrast> {
+        handle_error(self.lookup_short(field::Contrast.into())).map(capture::Contrast::from)
+    }
+
+    fn copyright(&mut self) -> Option<String> {
+        handle_error(self.lookup_string(field::Copyright.into()))
+    }
+
+    fn custom_rendered(&mut self) -> Option<capture::CustomRendered> {
+        handle_error(self.lookup_short(field::CustomRendered.into()))
+            .map(capture::CustomRendered::from)
+    }
+
+    fn digital_zoom_ratio(&mut self) -> Option<Rational<u32>> {
+        handle_error(self.lookup_rational(field::DigitalZoomRatio.into()))
+    }
+
+    #[cfg(feature = "chrono")]
+    fn date_time(&mut self) -> Option<gufo_common::datetime::DateTime> {
+        lookup_date_time(
+            self,
+            field::DateTime.into(),
+            field::SubSecTime.into(),
+            field::OffsetTime.into(),
+        )
+    }
+
+    #[cfg(feature = "chrono")]
+    fn date_time_digitized(&mut self) -> Option<gufo_common::datetime::DateTime> {
+        lookup_date_time(
+            self,
+            field::DateTimeDigitized.into(),
+            field::SubsecTimeDigitized.into(),
+            field::OffsetTimeDigitized.into(),
+        )
+    }
+
+    #[cfg(feature = "chrono")]
+    fn date_time_original(&mut self) -> Option<gufo_common::datetime::DateTime> {
+        lookup_date_time(
+            self,
+            field::DateTimeOriginal.into(),
+            field::SubSecTimeOriginal.into(),
+            field::OffsetTimeOriginal.into(),
+        )
+    }
+
+    fn exposure_mode(&mut self) -> Option<capture::ExposureMode> {
+        handle_error(self.lookup_short(field::ExposureMode.into())).map(capture::ExposureMode::from)
+    }
+
+    fn exposure_program(&mut self) -> Option<capture::ExposureProgram> {
+        handle_error(self.lookup_short(field::ExposureProgram.into()))
+            .map(capture::ExposureProgram::from)
+    }
+
+    fn exposure_time(&mut self) -> Option<Rational<u32>> {
+        handle_error(self.lookup_rational(field::ExposureTime.into()))
+    }
+
+    fn f_number(&mut self) -> Option<Rational<u32>> {
+        handle_error(self.lookup_rational(field::FNumber.into()))
+    }
+
+    fn flash(&mut self) -> Option<capture::Flash> {
+        handle_error(self.lookup_short(field::Flash.into())).map(capture::Flash::from)
+    }
+
+    fn focal_length(&mut self) -> Option<Rational<u32>> {
+        handle_error(self.lookup_rational(field::FocalLength.into()))
+    }
+
+    fn gain_control(&mut self) -> Option<capture::GainControl> {
+        handle_error(self.lookup_short(field::GainControl.into())).map(capture::GainControl::from)
+    }
+
+    fn gps_altitude(&mut self) -> Option<f64> {
+        let altitude = handle_error(self.lookup_rational(field::GPSAltitude.into()))?.as_f64();
+        let altitude_ref = handle_error(self.lookup_uints(field::GPSAltitudeRef.into()));
+
+        // Odd references are below sea level or ellipsoid
+        if altitude_ref
+            .and_then(|x| x.first().copied())
+            .unwrap_or_default()
+            % 2
+            == 1
+        {
+            Some(-altitude)
+        } else {
+            Some(altitude)
+        }
+    }
+
+    fn gps_area_information(&mut self) -> Option<String> {
+        handle_error(self.lookup_character_identified_code_string(field::GPSAreaInformation.into()))
+    }
+
+    #[cfg(feature = "chrono")]
+    fn gps_date_time(&mut self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let date = handle_error(self.lookup_string(field::GPSDateStamp.into()))?;
+        let time = handle_error(self.lookup_rationals::<3>(field::GPSTimeStamp.into()))?;
+
+        let date = handle_error_(chrono::NaiveDate::parse_from_str(&date, "%Y:%m:%d"))?;
+
+        let nanoseconds =
+            time.into_iter()
+                .zip([3600, 60, 1])
+                .try_fold(0_u64, |sum, (value, factor)| {
+                    let value = u64::from(value.numerator)
+                        .checked_mul(1_000_000_000)?
+                        .checked_div(u64::from(value.denominator))?;
+                    sum.checked_add(value.checked_mul(factor)?)
+                })?;
+
+        let date_time =
+            date.and_hms_opt(0, 0, 0)?
+                .checked_add_signed(chrono::TimeDelta::nanoseconds(
+                    i64::try_from(nanoseconds).ok()?,
+                ))?;
+
+        Some(date_time.and_utc())
+    }
+
+    fn gps_location(&mut self) -> Option<geography::Location> {
+        let lat_ref = handle_error_(geography::LatRef::try_from(
+            handle_error(self.lookup_string(field::GPSLatitudeRef.into()))?.as_str(),
+        ))?;
+
+        let [lat_ang, lat_min, lat_sec] =
+            handle_error(self.lookup_rationals(field::GPSLatitude.into()))?;
+
+        let lon_ref = handle_error_(geography::LonRef::try_from(
+            handle_error(self.lookup_string(field::GPSLongitudeRef.into()))?.as_str(),
+        ))?;
+
+        let [lon_ang, lon_min, lon_sec] =
+            handle_error(self.lookup_rationals(field::GPSLongitude.into()))?;
+
+        Some(geography::Location::from_ref_coord(
+            lat_ref,
+            (lat_ang.as_f64(), lat_min.as_f64(), lat_sec.as_f64()),
+            lon_ref,
+            (lon_ang.as_f64(), lon_min.as_f64(), lon_sec.as_f64()),
+        ))
+    }
+
+    fn gps_processing_method(&mut self) -> Option<String> {
+        handle_error(
+            self.lookup_character_identified_code_string(field::GPSProcessingMethod.into()),
+        )
+    }
+
+    fn iso_speed_rating(&mut self) -> Option<u16> {
+        handle_error(self.lookup_short(field::PhotographicSensitivity.into()))
+    }
+
+    fn lens_make(&mut self) -> Option<String> {
+        handle_error(self.lookup_string(field::LensMake.into()))
+    }
+
+    fn lens_model(&mut self) -> Option<String> {
+        if let Some(s) = handle_error(self.lookup_string(field::LensModel.into())) {
+            Some(s)
+        } else if let Some(s) = handle_error(self.lookup_string(field::CanonLensModel.into())) {
+            Some(s)
+        } else {
+            self.canon_camera_settings()?
+                .lens_name()
+                .map(ToString::to_string)
+        }
+    }
+
+    fn lens_specification(&mut self) -> Option<hardware::LensSpecification> {
+        let [
+            min_focal_length,
+            max_focal_length,
+            min_f_number_min_focal_length,
+            min_f_number_max_focal_length,
+        ] = if let Some(values) =
+            handle_error(self.lookup_rationals(field::LensSpecification.into()))
+        {
+            values
+        } else {
+            handle_error(self.lookup_rationals(field::NikonLens.into()))?
+        };
+
+        Some(hardware::LensSpecification {
+            min_focal_length,
+            max_focal_length,
+            min_f_number_min_focal_length,
+            min_f_number_max_focal_length,
+        })
+    }
+
+    fn light_source(&mut self) -> Option<capture::LightSource> {
+        handle_error(self.lookup_short(field::LightSource.into())).map(capture::LightSource::from)
+    }
+
+    fn make(&mut self) -> Option<String> {
+        handle_error(self.lookup_string(field::Make.into()))
+    }
+
+    fn metering_mode(&mut self) -> Option<capture::MeteringMode> {
+        handle_error(self.lookup_short(field::MeteringMode.into())).map(capture::MeteringMode::from)
+    }
+
+    fn model(&mut self) -> Option<String> {
+        handle_error(self.lookup_string(field::Model.into()))
+    }
+
+    fn orientation(&mut self) -> Option<orientation::Orientation> {
+        let orientation = handle_error(self.lookup_short(field::Orientation.into()))?;
+
+        handle_error_(orientation::Orientation::try_from(orientation))
+    }
+
+    fn saturation(&mut self) -> Option<capture::Saturation> {
+        handle_error(self.lookup_short(field::Saturation.into())).map(capture::Saturation::from)
+    }
+
+    fn scene_capture_type(&mut self) -> Option<capture::SceneCaptureType> {
+        handle_error(self.lookup_short(field::SceneCaptureType.into()))
+            .map(capture::SceneCaptureType::from)
+    }
+
+    fn sensing_method(&mut self) -> Option<capture::SensingMethod> {
+        handle_error(self.lookup_short(field::SensingMethod.into()))
+            .map(capture::SensingMethod::from)
+    }
+
+    fn serial_number(&mut self) -> Option<String> {
+        if let Some(s) = handle_error(self.lookup_string(field::BodySerialNumber.into())) {
+            Some(s)
+        } else {
+            match self.maker_note_vendor()? {
+                Vendor::Canon => {
+                    let [serial_number] =
+                        handle_error(self.lookup_uints(field::CanonSerialNumber.into()))?[..]
+                    else {
+                        return None;
+                    };
+                    Some(format!("{serial_number:010}"))
+                }
+                Vendor::Nikon => handle_error(self.lookup_string(field::NikonSerialNumber.into())),
+                _ => None,
+            }
+        }
+    }
+
+    fn sharpness(&mut self) -> Option<capture::Sharpness> {
+        handle_error(self.lookup_short(field::Sharpness.into())).map(capture::Sharpness::from)
+    }
+
+    fn software(&mut self) -> Option<String> {
+        handle_error(self.lookup_string(field::Software.into()))
+    }
+
+    fn subject_distance_range(&mut self) -> Option<capture::SubjectDistanceRange> {
+        handle_error(self.lookup_short(field::SubjectDistanceRange.into()))
+            .map(capture::SubjectDistanceRange::from)
+    }
+
+    fn thumbnail(&mut self) -> Option<Vec<u8>> {
+        let [offset] = handle_error(self.lookup_uints(field::JPEGInterchangeFormat.into()))?[..]
+        else {
+            return None;
+        };
+        let [length] =
+            handle_error(self.lookup_uints(field::JPEGInterchangeFormatLength.into()))?[..]
+        else {
+            return None;
+        };
+
+        let offset = handle_error_(usize::try_from(offset))?;
+        let end = offset.checked_add(handle_error_(usize::try_from(length))?)?;
+
+        self.raw_data(offset..end).map(|x| x.to_vec())
+    }
+
+    fn user_comment(&mut self) -> Option<String> {
+        let s =
+            handle_error(self.lookup_character_identified_code_string(field::UserComment.into()))?;
+
+        let tr = s.trim();
+        if tr.is_empty() {
+            None
+        } else {
+            Some(tr.to_string())
+        }
+    }
+
+    fn white_balance(&mut self) -> Option<capture::WhiteBalance> {
+        handle_error(self.lookup_short(field::WhiteBalance.into())).map(capture::WhiteBalance::from)
+    }
+
+    fn apple_content_identifier(&mut self) -> Option<String> {
+        handle_error(self.lookup_string(field::AppleContentIdentifier.into()))
+    }
+
+    fn apple_burst_uuid(&mut self) -> Option<String> {
+        handle_error(self.lookup_string(field::AppleBurstUUID.into()))
+    }
+
+    fn apple_hdr_headroom(&mut self) -> Option<f64> {
+        handle_error(self.lookup_srational(field::AppleHDRHeadroom.into())).map(|x| x.as_f64())
+    }
+
+    fn apple_hdr_gain(&mut self) -> Option<f64> {
+        handle_error(self.lookup_srational(field::AppleHDRGain.into())).map(|x| x.as_f64())
+    }
+
+    fn apple_acceleration_vector(&mut self) -> Option<apple::AccelerationVector> {
+        let values =
+            handle_error(self.lookup_srationals::<3>(field::AppleAccelerationVector.into()))?;
+        Some(values.map(|x| x.as_f64()).into())
+    }
+
+    fn canon_camera_settings(&mut self) -> Option<canon::CameraSettings> {
+        let values = handle_error(self.lookup_shorts(field::CanonCameraSettings.into()))?;
+        Some(canon::CameraSettings::from_shorts(&values))
+    }
+
+    fn canon_lens_type(&mut self) -> Option<canon::LensType> {
+        self.canon_camera_settings()?
+            .lens_type
+            .filter(|x| *x != canon::LensType::NOT_AVAILABLE)
+    }
+
+    fn canon_focal_length(&mut self) -> Option<canon::FocalLength> {
+        let values = handle_error(self.lookup_shorts(field::CanonFocalLength.into()))?;
+        Some(canon::FocalLength::from_shorts(&values))
+    }
+
+    fn canon_shot_info(&mut self) -> Option<canon::ShotInfo> {
+        let values = handle_error(self.lookup_shorts(field::CanonShotInfo.into()))?;
+        Some(canon::ShotInfo::from_shorts(&values))
+    }
+
+    fn canon_file_info(&mut self) -> Option<canon::FileInfo> {
+        let values = handle_error(self.lookup_shorts(field::CanonFileInfo.into()))?;
+        Some(canon::FileInfo::from_shorts(&values))
+    }
+
+    fn nikon_lens_type(&mut self) -> Option<nikon::LensType> {
+        let [lens_type] = handle_error(self.lookup_uints(field::NikonLensType.into()))?[..] else {
+            return None;
+        };
+
+        Some(nikon::LensType::from(u8::try_from(lens_type).ok()?))
+    }
+
+    fn shutter_count(&mut self) -> Option<u32> {
+        match self.maker_note_vendor()? {
+            Vendor::Canon => {
+                let model = self.model()?;
+                self.canon_file_info()?.shutter_count(&model)
+            }
+            Vendor::Nikon => {
+                let [shutter_count] =
+                    handle_error(self.lookup_uints(field::NikonShutterCount.into()))?[..]
+                else {
+                    return None;
+                };
+                Some(shutter_count)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+pub(crate) fn lookup_date_time(
+    lookup: &mut (impl Lookup + ?Sized),
+    date_time: TagIfd,
+    subsec: TagIfd,
+    offset: TagIfd,
+) -> Option<gufo_common::datetime::DateTime> {
+    let datetime = handle_error(lookup.lookup_string(date_time))?;
+    let subsec = handle_error(lookup.lookup_string(subsec));
+    let offset = handle_error(lookup.lookup_string(offset));
+
+    handle_error_(crate::structure::util::datetime(datetime, subsec, offset))
+}
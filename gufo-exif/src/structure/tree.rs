@@ -0,0 +1,460 @@
+mod byte_order;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+use gufo_common::exif::{IfdId, Tag};
+use gufo_common::math::cheq;
+
+use crate::Error;
+use crate::structure::util::Endieness;
+use crate::structure::{Type, Typed};
+
+/// Owned representation of an Exif structure
+///
+/// In contrast to [`Document`](super::Document), the tree does not know about
+/// positions in the raw data. Serializing the tree lays out all IFDs and data
+/// anew.
+#[derive(Debug, Clone)]
+pub(crate) struct Tree {
+    pub endieness: Endieness,
+    pub big_tiff: bool,
+    pub ifds: BTreeMap<IfdId, TreeIfd>,
+    /// Offset of a MakerNote that is stored as [`TreeValue::Data`]
+    ///
+    /// The structure of such MakerNotes is unknown and they might contain
+    /// absolute offsets. Therefore, they stay at this offset when serializing
+    /// and all other IFDs and data are laid out around them.
+    pub opaque_maker_note: Option<(IfdId, usize)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TreeIfd {
+    pub entries: BTreeMap<Tag, TreeValue>,
+    /// Next IFD in the chain of IFDs
+    pub next: Option<IfdId>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum TreeValue {
+    /// Data in the byte order of the tree
+    Data {
+        type_: Type,
+        count: usize,
+        data: Vec<u8>,
+    },
+    /// Pointer to a sub-IFD
+    ///
+    /// For [`Type::Undefined`], as used by the MakerNote, the count is the
+    /// size of the IFD including all its data.
+    Ifd { type_: Type, ifd: IfdId },
+    /// Pointers to multiple IFDs, like in [`Tag::SUB_IFDS`]
+    SubIfds { type_: Type, ifds: Vec<IfdId> },
+    /// Offsets to image data like strips or a JPEG thumbnail
+    ///
+    /// The byte counts are stored in a separate entry, see
+    /// [`Tag::data_length_tag`].
+    ImageData { type_: Type, chunks: Vec<Vec<u8>> },
+}
+
+impl TreeValue {
+    pub fn from_typed(value: &Typed, endieness: Endieness) -> Result<Self, Error> {
+        Ok(Self::Data {
+            type_: value.type_(),
+            count: value.count()?,
+            data: value.serialize(endieness),
+        })
+    }
+}
+
+impl Tree {
+    pub fn new(endieness: Endieness, big_tiff: bool) -> Self {
+        Self {
+            endieness,
+            big_tiff,
+            ifds: BTreeMap::new(),
+            opaque_maker_note: None,
+        }
+    }
+
+    pub fn index_size(&self) -> usize {
+        if self.big_tiff { 8 } else { 4 }
+    }
+
+    pub fn entry_size(&self) -> usize {
+        if self.big_tiff { 20 } else { 12 }
+    }
+
+    pub fn ifd_mut(&mut self, ifd: IfdId) -> Result<&mut TreeIfd, Error> {
+        self.ifds.get_mut(&ifd).ok_or(Error::IfdNotFound(ifd))
+    }
+
+    /// Add the pointers to sub-IFDs and the thumbnail IFD
+    ///
+    /// Creates the parent IFDs, like the Exif IFD for the interoperability
+    /// IFD, if they don't exist yet.
+    pub fn link_ifds(&mut self) {
+        let sub_ifds = [
+            (
+                IfdId::Interoperability,
+                IfdId::Exif,
+                Tag::INTEROPERABILITY_IFD_POINTER,
+            ),
+            (IfdId::Exif, IfdId::Primary, Tag::EXIF_IFD_POINTER),
+            (IfdId::Gps, IfdId::Primary, Tag::GPS_INFO_IFD_POINTER),
+        ];
+
+        for (ifd, parent, tag) in sub_ifds {
+            if self.ifds.contains_key(&ifd) {
+                self.ifds.entry(parent).or_default().entries.insert(
+                    tag,
+                    TreeValue::Ifd {
+                        type_: Type::Long,
+                        ifd,
+                    },
+                );
+            }
+        }
+
+        if self.ifds.contains_key(&IfdId::Thumbnail) {
+            self.ifds.entry(IfdId::Primary).or_default().next = Some(IfdId::Thumbnail);
+        }
+    }
+
+    /// Write the tree as raw Exif data
+    pub fn serialize(&self) -> Result<Vec<u8>, Error> {
+        let mut writer = Writer {
+            tree: self,
+            out: Vec::new(),
+            written: BTreeSet::new(),
+            reserved: self.reserved_maker_note()?,
+            maker_note_referenced: false,
+        };
+
+        writer.header()?;
+        let primary_ifd_offset = writer.write_ifd(IfdId::Primary)?;
+        let primary_ifd_offset_pos = if self.big_tiff { 8 } else { 4 };
+        writer.write_index_at(primary_ifd_offset_pos, primary_ifd_offset)?;
+        writer.write_maker_note()?;
+
+        Ok(writer.out)
+    }
+
+    /// Range that has to be kept free for the opaque MakerNote
+    fn reserved_maker_note(&self) -> Result<Option<ReservedMakerNote<'_>>, Error> {
+        let Some((maker_note, pos)) = self.opaque_maker_note else {
+            return Ok(None);
+        };
+
+        let Some(TreeValue::Data { data, .. }) = self
+            .ifds
+            .get(&IfdId::Exif)
+            .and_then(|x| x.entries.get(&Tag::MAKER_NOTE))
+        else {
+            return Ok(None);
+        };
+
+        if data.len() <= self.index_size() {
+            return Ok(None);
+        }
+
+        // The MakerNote can't overlap with the header
+        let header_len = if self.big_tiff { 16 } else { 8 };
+        if pos < header_len {
+            return Err(Error::MakerNoteNotMovable(maker_note));
+        }
+
+        Ok(Some(ReservedMakerNote {
+            range: pos..(cheq(pos) + data.len()).check()?,
+            data,
+        }))
+    }
+}
+
+struct Writer<'t> {
+    tree: &'t Tree,
+    out: Vec<u8>,
+    /// IFDs that have already been written to detect loops
+    written: BTreeSet<IfdId>,
+    /// Position of the opaque MakerNote where nothing else is placed
+    reserved: Option<ReservedMakerNote<'t>>,
+    /// The opaque MakerNote has to be written after all other data
+    maker_note_referenced: bool,
+}
+
+#[derive(Debug, Clone)]
+struct ReservedMakerNote<'t> {
+    range: Range<usize>,
+    data: &'t [u8],
+}
+
+impl Writer<'_> {
+    fn header(&mut self) -> Result<(), Error> {
+        let endieness = self.tree.endieness;
+
+        self.out.extend_from_slice(match endieness {
+            Endieness::Big => b"MM",
+            Endieness::Litte => b"II",
+        });
+
+        if self.tree.big_tiff {
+            self.out.extend_from_slice(&endieness.u16_to_bytes(43));
+            // Byte size of offsets
+            self.out.extend_from_slice(&endieness.u16_to_bytes(8));
+            self.out.extend_from_slice(&[0, 0]);
+        } else {
+            self.out.extend_from_slice(&endieness.u16_to_bytes(42));
+        }
+
+        // Placeholder for the offset of the primary IFD
+        self.out
+            .resize((cheq(self.out.len()) + self.tree.index_size()).check()?, 0);
+
+        Ok(())
+    }
+
+    /// Write IFD with all its data, sub-IFDs, and following IFDs
+    ///
+    /// Returns the offset of the IFD.
+    fn write_ifd(&mut self, ifd_id: IfdId) -> Result<usize, Error> {
+        let endieness = self.tree.endieness;
+        let index_size = self.tree.index_size();
+        let entry_size = self.tree.entry_size();
+
+        if !self.written.insert(ifd_id) {
+            return Err(Error::other(format!("IFD {ifd_id:?} is referenced twice")));
+        }
+
+        let empty_ifd = TreeIfd::default();
+        let ifd = match self.tree.ifds.get(&ifd_id) {
+            Some(ifd) => ifd,
+            // An Exif structure always needs a primary IFD
+            None if ifd_id == IfdId::Primary => &empty_ifd,
+            None => return Err(Error::IfdNotFound(ifd_id)),
+        };
+
+        let n_entries = ifd.entries.len();
+        let n_entries_bytes = if self.tree.big_tiff {
+            endieness.u64_to_bytes(n_entries.try_into()?).to_vec()
+        } else {
+            endieness.u16_to_bytes(n_entries.try_into()?).to_vec()
+        };
+
+        let ifd_len =
+            (cheq(n_entries_bytes.len()) + cheq(n_entries) * entry_size + index_size).check()?;
+        let pos = self.allocate(ifd_len)?;
+        self.write_at(pos, &n_entries_bytes)?;
+
+        let table_pos = (cheq(pos) + n_entries_bytes.len()).check()?;
+        let next_ifd_offset_pos = (cheq(table_pos) + cheq(n_entries) * entry_size).check()?;
+
+        let mut sub_ifds = Vec::new();
+        let mut sub_ifd_arrays = Vec::new();
+        let mut image_data = Vec::new();
+
+        for (n, (tag, value)) in ifd.entries.iter().enumerate() {
+            let entry_pos = (cheq(table_pos) + cheq(n) * entry_size).check()?;
+            let value_pos = (cheq(entry_pos) + 4 + index_size).check()?;
+
+            match value {
+                TreeValue::Data { type_, count, data } => {
+                    self.write_entry_head(entry_pos, *tag, *type_, *count)?;
+                    if data.len() <= index_size {
+                        self.write_at(value_pos, data)?;
+                    } else if ifd_id == IfdId::Exif
+                        && *tag == Tag::MAKER_NOTE
+                        && let Some(reserved) = &self.reserved
+                    {
+                        self.write_index_at(value_pos, reserved.range.start)?;
+                        self.maker_note_referenced = true;
+                    } else {
+                        let offset = self.push_data(data)?;
+                        self.write_index_at(value_pos, offset)?;
+                    }
+                }
+                TreeValue::Ifd { type_, ifd } => {
+                    sub_ifds.push((entry_pos, *tag, *type_, *ifd));
+                }
+                TreeValue::SubIfds { type_, ifds } => {
+                    let array_pos = self.write_array_head(entry_pos, *tag, *type_, ifds.len())?;
+                    sub_ifd_arrays.push((array_pos, *type_, ifds));
+                }
+                TreeValue::ImageData { type_, chunks } => {
+                    let array_pos = self.write_array_head(entry_pos, *tag, *type_, chunks.len())?;
+                    image_data.push((array_pos, *type_, chunks));
+                }
+            }
+        }
+
+        for (array_pos, type_, chunks) in image_data {
+            for (n, chunk) in chunks.iter().enumerate() {
+                let offset = self.push_data(chunk)?;
+                let element_pos = (cheq(array_pos) + cheq(n) * type_.size()).check()?;
+                self.write_offset_at(element_pos, type_, offset)?;
+            }
+        }
+
+        for (array_pos, type_, ifds) in sub_ifd_arrays {
+            for (n, sub_ifd) in ifds.iter().enumerate() {
+                let offset = self.write_ifd(*sub_ifd)?;
+                let element_pos = (cheq(array_pos) + cheq(n) * type_.size()).check()?;
+                self.write_offset_at(element_pos, type_, offset)?;
+            }
+        }
+
+        for (entry_pos, tag, type_, sub_ifd) in sub_ifds {
+            let value_pos = (cheq(entry_pos) + 4 + index_size).check()?;
+            let sub_ifd_pos = self.write_ifd(sub_ifd)?;
+
+            match type_ {
+                Type::Long => {
+                    self.write_entry_head(entry_pos, tag, type_, 1)?;
+                    self.write_at(value_pos, &endieness.u32_to_bytes(sub_ifd_pos.try_into()?))?;
+                }
+                Type::Undefined => {
+                    let len = (cheq(self.out.len()) - sub_ifd_pos).check()?;
+                    self.write_entry_head(entry_pos, tag, type_, len)?;
+                    self.write_index_at(value_pos, sub_ifd_pos)?;
+                }
+                type_ => {
+                    self.write_entry_head(entry_pos, tag, type_, 1)?;
+                    self.write_index_at(value_pos, sub_ifd_pos)?;
+                }
+            }
+        }
+
+        if let Some(next) = ifd.next {
+            let next_pos = self.write_ifd(next)?;
+            self.write_index_at(next_ifd_offset_pos, next_pos)?;
+        }
+
+        Ok(pos)
+    }
+
+    fn write_entry_head(
+        &mut self,
+        entry_pos: usize,
+        tag: Tag,
+        type_: Type,
+        count: usize,
+    ) -> Result<(), Error> {
+        let endieness = self.tree.endieness;
+
+        let mut head = Vec::new();
+        head.extend_from_slice(&endieness.u16_to_bytes(tag.0));
+        head.extend_from_slice(&endieness.u16_to_bytes(type_.u16()));
+        head.extend_from_slice(&endieness.index_to_bytes(count, self.tree.index_size())?);
+
+        self.write_at(entry_pos, &head)
+    }
+
+    /// Write the head of an entry with an array of offsets
+    ///
+    /// Returns the position of the array which is filled with zeros.
+    fn write_array_head(
+        &mut self,
+        entry_pos: usize,
+        tag: Tag,
+        type_: Type,
+        count: usize,
+    ) -> Result<usize, Error> {
+        let index_size = self.tree.index_size();
+        let value_pos = (cheq(entry_pos) + 4 + index_size).check()?;
+
+        self.write_entry_head(entry_pos, tag, type_, count)?;
+
+        let len = (cheq(count) * type_.size()).check()?;
+        if len <= index_size {
+            Ok(value_pos)
+        } else {
+            let offset = self.allocate(len)?;
+            self.write_index_at(value_pos, offset)?;
+            Ok(offset)
+        }
+    }
+
+    /// Write an element of an offset array
+    fn write_offset_at(&mut self, pos: usize, type_: Type, offset: usize) -> Result<(), Error> {
+        let endieness = self.tree.endieness;
+
+        match type_ {
+            Type::Short => self.write_at(pos, &endieness.u16_to_bytes(offset.try_into()?)),
+            Type::Long | Type::Ifd => {
+                self.write_at(pos, &endieness.u32_to_bytes(offset.try_into()?))
+            }
+            Type::Long8 | Type::Ifd8 => {
+                self.write_at(pos, &endieness.u64_to_bytes(offset.try_into()?))
+            }
+            type_ => Err(Error::TypeMissmatch(
+                type_,
+                &[Type::Short, Type::Long, Type::Ifd, Type::Long8, Type::Ifd8],
+            )),
+        }
+    }
+
+    /// Append data at a word boundary and return its offset
+    fn push_data(&mut self, data: &[u8]) -> Result<usize, Error> {
+        let offset = self.allocate(data.len())?;
+        self.write_at(offset, data)?;
+        Ok(offset)
+    }
+
+    /// Append `len` zeros at a word boundary and return their offset
+    ///
+    /// Skips the range reserved for the opaque MakerNote if the data would
+    /// overlap with it.
+    fn allocate(&mut self, len: usize) -> Result<usize, Error> {
+        self.align();
+
+        if let Some(reserved) = &self.reserved
+            && self.out.len() < reserved.range.end
+            && (cheq(self.out.len()) + len).check()? > reserved.range.start
+        {
+            let end = reserved.range.end;
+            self.out.resize(end, 0);
+            self.align();
+        }
+
+        let offset = self.out.len();
+        self.out.resize((cheq(offset) + len).check()?, 0);
+        Ok(offset)
+    }
+
+    /// Write the opaque MakerNote to its reserved range
+    fn write_maker_note(&mut self) -> Result<(), Error> {
+        if let Some(reserved) = self.reserved.clone()
+            && self.maker_note_referenced
+        {
+            if self.out.len() < reserved.range.end {
+                self.out.resize(reserved.range.end, 0);
+            }
+            self.write_at(reserved.range.start, reserved.data)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_index_at(&mut self, pos: usize, value: usize) -> Result<(), Error> {
+        let bytes = self
+            .tree
+            .endieness
+            .index_to_bytes(value, self.tree.index_size())?;
+        self.write_at(pos, &bytes)
+    }
+
+    fn write_at(&mut self, pos: usize, data: &[u8]) -> Result<(), Error> {
+        let end = (cheq(pos) + data.len()).check()?;
+        self.out
+            .get_mut(pos..end)
+            .ok_or(Error::IndexOverflow)?
+            .copy_from_slice(data);
+        Ok(())
+    }
+
+    /// Align to word boundary as required by TIFF 6.0
+    fn align(&mut self) {
+        if self.out.len() % 2 == 1 {
+            self.out.push(0);
+        }
+    }
+}
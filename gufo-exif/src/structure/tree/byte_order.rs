@@ -0,0 +1,154 @@
+use gufo_common::exif::{Field, IfdId, Tag};
+use gufo_common::field;
+use gufo_common::math::cheq;
+
+use super::{Tree, TreeValue};
+use crate::Error;
+use crate::structure::util::Endieness;
+use crate::structure::{CODE_UNICODE, Type};
+
+/// Value of the compression tag for uncompressed image data
+const COMPRESSION_NONE: u16 = 1;
+
+impl Tree {
+    /// Re-encode all values for the byte order `endieness`
+    ///
+    /// Image data are only carried over if they don't depend on the byte
+    /// order. Data of type [`Type::Undefined`] are kept as they are, except
+    /// for Exif tags with a known structure.
+    pub fn convert_byte_order(&mut self, endieness: Endieness) -> Result<(), Error> {
+        let source = self.endieness;
+        if source == endieness {
+            return Ok(());
+        }
+
+        for (ifd_id, ifd) in &mut self.ifds {
+            let has_image_data = ifd
+                .entries
+                .values()
+                .any(|x| matches!(x, TreeValue::ImageData { .. }));
+            if has_image_data && ifd_id.is_image() && multi_byte_samples(ifd, source)? {
+                return Err(Error::ImageDataByteOrder(*ifd_id));
+            }
+
+            for (tag, value) in &mut ifd.entries {
+                let TreeValue::Data { type_, data, .. } = value else {
+                    continue;
+                };
+
+                match type_ {
+                    Type::Undefined if !matches!(ifd_id, IfdId::MakerNote(_)) => {
+                        convert_undefined(*tag, *ifd_id, data, source)?
+                    }
+                    type_ => swap_units(data, unit_size(*type_)?),
+                }
+            }
+        }
+
+        self.endieness = endieness;
+
+        Ok(())
+    }
+}
+
+/// Returns true if uncompressed samples of the image use more than one byte
+fn multi_byte_samples(ifd: &super::TreeIfd, endieness: Endieness) -> Result<bool, Error> {
+    let shorts = |tag: Tag| -> Result<Vec<u16>, Error> {
+        match ifd.entries.get(&tag) {
+            Some(TreeValue::Data {
+                type_: Type::Short,
+                data,
+                ..
+            }) => data
+                .chunks_exact(2)
+                .map(|x| endieness.u16_from_bytes(x))
+                .collect(),
+            _ => Ok(Vec::new()),
+        }
+    };
+
+    let compression = shorts(field::Compression::TAG)?;
+    let bits_per_sample = shorts(field::BitsPerSample::TAG)?;
+
+    Ok(compression.first().is_none_or(|x| *x == COMPRESSION_NONE)
+        && bits_per_sample.iter().any(|x| *x > 8))
+}
+
+/// Size of the units that have to be reversed to change the byte order
+fn unit_size(type_: Type) -> Result<usize, Error> {
+    match type_ {
+        // Numerator and denominator are converted separately
+        Type::Rational | Type::SRational => Ok(4),
+        Type::Unknown(_) => Err(Error::UnknownTypeByteOrder(type_)),
+        type_ => Ok(type_.size()),
+    }
+}
+
+/// Reverse the bytes of each unit
+fn swap_units(data: &mut [u8], unit_size: usize) {
+    if unit_size > 1 {
+        data.chunks_exact_mut(unit_size).for_each(<[u8]>::reverse);
+    }
+}
+
+/// Convert Exif and GPS tags of type undefined that contain numbers
+fn convert_undefined(
+    tag: Tag,
+    ifd: IfdId,
+    data: &mut [u8],
+    source: Endieness,
+) -> Result<(), Error> {
+    match (ifd, tag) {
+        (IfdId::Exif, field::UserComment::TAG)
+        | (IfdId::Gps, field::GPSProcessingMethod::TAG | field::GPSAreaInformation::TAG) => {
+            if data.starts_with(CODE_UNICODE)
+                && let Some(text) = data.get_mut(8..)
+            {
+                swap_units(text, 2);
+            }
+        }
+        (IfdId::Exif, field::CFAPattern::TAG) => {
+            // Number of columns and rows
+            if let Some(dimensions) = data.get_mut(..4) {
+                swap_units(dimensions, 2);
+            }
+        }
+        // Number of columns and rows, and UCS-2 strings
+        (IfdId::Exif, field::DeviceSettingDescription::TAG) => swap_units(data, 2),
+        (IfdId::Exif, field::OECF::TAG | field::SpatialFrequencyResponse::TAG) => {
+            convert_function_table(data, source)?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Convert the table of OECF and SFR
+///
+/// The table consists of the number of columns and rows, the column names,
+/// and the rational values.
+fn convert_function_table(data: &mut [u8], source: Endieness) -> Result<(), Error> {
+    let columns = source.u16_from_bytes(data.get(..2).ok_or(Error::IndexOverflow)?)?;
+    let rows = source.u16_from_bytes(data.get(2..4).ok_or(Error::IndexOverflow)?)?;
+    swap_units(data.get_mut(..4).ok_or(Error::IndexOverflow)?, 2);
+
+    // Skip the NUL terminated column names
+    let mut pos = 4;
+    for _ in 0..columns {
+        let name_len = data
+            .get(pos..)
+            .and_then(|x| x.iter().position(|x| *x == 0))
+            .ok_or(Error::IndexOverflow)?;
+        pos = (cheq(pos) + name_len + 1).check()?;
+    }
+
+    let values_len = (cheq(usize::from(columns)) * usize::from(rows) * 8).check()?;
+    let values_end = (cheq(pos) + values_len).check()?;
+    swap_units(
+        data.get_mut(pos..values_end).ok_or(Error::IndexOverflow)?,
+        4,
+    );
+
+    Ok(())
+}
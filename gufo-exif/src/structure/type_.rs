@@ -1,57 +1,34 @@
 use std::fmt::Display;
 
+pub use gufo_common::exif::Type;
 use gufo_common::math::cheq;
 use gufo_common::types::Rational;
 
 use crate::Error;
 use crate::structure::util::Endieness;
 
-gufo_common::utils::convertible_enum!(
-    #[repr(u16)]
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-    /// Exif datatype
-    ///
-    /// Specifies which type an entry has
-    pub enum Type {
-        Byte = 1,
-        Ascii = 2,
-        Short = 3,
-        Long = 4,
-        Rational = 5,
-        Undefined = 7,
-        SLong = 9,
-        SRational = 10,
-        Utf8 = 129,
-    }
-);
+/// Character identifier code for ASCII text
+///
+/// Exif 3.0: 4.6.4. Character Identifier Code
+pub(crate) const CODE_ASCII: &[u8; 8] = b"ASCII\0\0\0";
+/// Character identifier code for JIS X0208-1990 text
+pub(crate) const CODE_JIS: &[u8; 8] = b"JIS\0\0\0\0\0";
+/// Character identifier code for Unicode text
+pub(crate) const CODE_UNICODE: &[u8; 8] = b"UNICODE\0";
 
-impl Type {
-    /// Size of an entry per count
-    pub const fn size(self) -> usize {
-        match self {
-            Self::Byte | Self::Ascii | Self::Undefined | Self::Utf8 | Self::Unknown(_) => 1,
-            Self::Short => 2,
-            Self::Long | Self::SLong => 4,
-            Self::Rational | Self::SRational => 8,
-        }
-    }
-
-    pub fn u16(self) -> u16 {
-        self.into()
-    }
-}
-
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Typed {
     Byte(Vec<u8>),
     Ascii(Vec<u8>),
     Short(Vec<u16>),
     Long(Vec<u32>),
+    Long8(Vec<u64>),
     Rational(Vec<Rational<u32>>),
     Undefined(Vec<u8>),
     SLong(Vec<i32>),
     SRational(Vec<Rational<i32>>),
     Utf8(String),
+    /// Raw data of types that are not decoded
     Unknown(u16, Vec<u8>),
 }
 
@@ -83,7 +60,7 @@ impl Typed {
 
                 Ok(Self::Short(vec))
             }
-            Type::Long => {
+            Type::Long | Type::Ifd => {
                 let vec = data
                     .chunks_exact(4)
                     .take(count)
@@ -92,6 +69,15 @@ impl Typed {
 
                 Ok(Self::Long(vec))
             }
+            Type::Long8 | Type::Ifd8 => {
+                let vec = data
+                    .chunks_exact(8)
+                    .take(count)
+                    .map(|x| endieness.u64_from_bytes(x))
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                Ok(Self::Long8(vec))
+            }
             Type::Rational => {
                 let vec = data
                     .chunks_exact(8)
@@ -146,10 +132,51 @@ impl Typed {
                 Ok(Self::Utf8(s))
             }
             Type::Byte => Ok(Typed::Byte(data.to_vec())),
-            Type::Unknown(type_id) => Ok(Typed::Unknown(type_id, data.to_vec())),
+            Type::SByte
+            | Type::SShort
+            | Type::Float
+            | Type::Double
+            | Type::SLong8
+            | Type::Unknown(_) => Ok(Typed::Unknown(type_.u16(), data.to_vec())),
         }
     }
 
+    /// Text with a character identifier code, like in
+    /// [`UserComment`](gufo_common::field::UserComment)
+    ///
+    /// Exif 3.0: 4.6.4. Character Identifier Code
+    ///
+    /// The text is stored as ASCII if possible. Otherwise, it is stored as
+    /// UNICODE in UTF-16 with the byte order `endieness`, which is how most
+    /// readers interpret it. JIS is never written.
+    ///
+    /// ```
+    /// use gufo_exif::Typed;
+    /// use gufo_exif::structure::Endieness;
+    ///
+    /// assert_eq!(
+    ///     Typed::character_identified_code_string("Gufo", Endieness::Big),
+    ///     Typed::Undefined(b"ASCII\0\0\0Gufo".to_vec())
+    /// );
+    /// assert_eq!(
+    ///     Typed::character_identified_code_string("Gü", Endieness::Big),
+    ///     Typed::Undefined(b"UNICODE\0\0G\0\xFC".to_vec())
+    /// );
+    /// ```
+    pub fn character_identified_code_string(s: &str, endieness: Endieness) -> Self {
+        let mut data = Vec::new();
+
+        if s.is_ascii() {
+            data.extend_from_slice(CODE_ASCII);
+            data.extend_from_slice(s.as_bytes());
+        } else {
+            data.extend_from_slice(CODE_UNICODE);
+            data.extend(s.encode_utf16().flat_map(|x| endieness.u16_to_bytes(x)));
+        }
+
+        Self::Undefined(data)
+    }
+
     pub fn serialize(&self, endieness: Endieness) -> Vec<u8> {
         match self {
             Self::Ascii(data) => {
@@ -162,6 +189,10 @@ impl Typed {
                 .iter()
                 .flat_map(|x| endieness.u32_to_bytes(*x))
                 .collect(),
+            Self::Long8(long8) => long8
+                .iter()
+                .flat_map(|x| endieness.u64_to_bytes(*x))
+                .collect(),
             Self::Rational(rational) => rational
                 .iter()
                 .flat_map(|x| {
@@ -205,12 +236,13 @@ impl Typed {
             Self::Ascii(x) => (cheq(x.len()) + 1).check()?,
             Self::Byte(x) => x.len(),
             Self::Long(x) => x.len(),
+            Self::Long8(x) => x.len(),
             Self::Rational(x) => x.len(),
             Self::SLong(x) => x.len(),
             Self::SRational(x) => x.len(),
             Self::Short(x) => x.len(),
             Self::Undefined(x) => x.len(),
-            Self::Unknown(_, x) => x.len(),
+            Self::Unknown(type_id, x) => (cheq(x.len()) / Type::from(*type_id).size()).check()?,
             Self::Utf8(x) => (cheq(x.len()) + 1).check()?,
         })
     }
@@ -221,12 +253,13 @@ impl Typed {
             Self::Ascii(_) => Type::Ascii,
             Self::Short(_) => Type::Short,
             Self::Long(_) => Type::Long,
+            Self::Long8(_) => Type::Long8,
             Self::Rational(_) => Type::Rational,
             Self::Undefined(_) => Type::Undefined,
             Self::SLong(_) => Type::SLong,
             Self::SRational(_) => Type::SRational,
             Self::Utf8(_) => Type::Utf8,
-            Self::Unknown(type_id, _) => Type::Unknown(*type_id),
+            Self::Unknown(type_id, _) => Type::from(*type_id),
         }
     }
 
@@ -236,6 +269,7 @@ impl Typed {
             Self::Ascii(data) => String::from_utf8_lossy(data).to_string(),
             Self::Short(data) => pp(data.iter()),
             Self::Long(data) => pp(data.iter()),
+            Self::Long8(data) => pp(data.iter()),
             Self::Rational(data) => pp(data.iter().map(|x| x.display())),
             Self::Undefined(data) => pp(data.iter()),
             Self::SLong(data) => pp(data.iter()),
@@ -1,12 +1,16 @@
-use std::fmt::Display;
+use std::fmt::{Debug, Display};
+use std::ops::Deref;
 
 use zerocopy::{ByteOrder, FromBytes, Immutable, IntoBytes, KnownLayout, U16, U32, U64, Unaligned};
 
 use crate::Error;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Endieness {
+    #[cfg_attr(feature = "serde", serde(rename = "big"))]
     Big,
+    #[cfg_attr(feature = "serde", serde(rename = "little"))]
     Litte,
 }
 
@@ -33,6 +37,17 @@ impl Endieness {
         }
     }
 
+    pub fn u64_from_bytes(&self, bytes: &[u8]) -> Result<u64, Error> {
+        let bytes = bytes
+            .try_into()
+            .map_err(|_| Error::InputDataWrongLength(bytes.len(), 8))?;
+
+        match self {
+            Self::Big => Ok(u64::from_be_bytes(bytes)),
+            Self::Litte => Ok(u64::from_le_bytes(bytes)),
+        }
+    }
+
     pub fn i32_from_bytes(&self, bytes: &[u8]) -> Result<i32, Error> {
         let bytes = bytes
             .try_into()
@@ -58,12 +73,35 @@ impl Endieness {
         }
     }
 
+    pub fn u64_to_bytes(&self, value: u64) -> [u8; 8] {
+        match self {
+            Self::Big => value.to_be_bytes(),
+            Self::Litte => value.to_le_bytes(),
+        }
+    }
+
     pub fn i32_to_bytes(&self, value: i32) -> [u8; 4] {
         match self {
             Self::Big => value.to_be_bytes(),
             Self::Litte => value.to_le_bytes(),
         }
     }
+
+    /// Read offset or count, 32 bit for TIFF, 64 bit for BigTIFF
+    pub fn index_from_bytes(&self, bytes: &[u8]) -> Result<usize, Error> {
+        match bytes.len() {
+            8 => Ok(self.u64_from_bytes(bytes)?.try_into()?),
+            _ => Ok(self.u32_from_bytes(bytes)?.try_into()?),
+        }
+    }
+
+    /// Write offset or count, 32 bit for TIFF, 64 bit for BigTIFF
+    pub fn index_to_bytes(&self, value: usize, index_size: usize) -> Result<Vec<u8>, Error> {
+        match index_size {
+            8 => Ok(self.u64_to_bytes(value.try_into()?).to_vec()),
+            _ => Ok(self.u32_to_bytes(value.try_into()?).to_vec()),
+        }
+    }
 }
 
 #[cfg(feature = "chrono")]
@@ -166,7 +204,15 @@ impl<O: ByteOrder> UsizeConversion for U64<O> {
 }
 
 pub trait IndexType:
-    FromBytes + IntoBytes + Immutable + KnownLayout + Unaligned + UsizeConversion + Display + 'static
+    FromBytes
+    + IntoBytes
+    + Immutable
+    + KnownLayout
+    + Unaligned
+    + UsizeConversion
+    + Display
+    + Debug
+    + 'static
 {
     type NEntries: FromBytes
         + IntoBytes
@@ -175,6 +221,7 @@ pub trait IndexType:
         + Unaligned
         + UsizeConversion
         + Display
+        + Debug
         + 'static;
 }
 
@@ -185,6 +232,63 @@ impl<O: ByteOrder + 'static> IndexType for U64<O> {
     type NEntries = U64<O>;
 }
 
+/// Byte slice the structure is read from
+///
+/// Implemented for `&[u8]`, for data that are only read, and for `&mut [u8]`,
+/// for data that are changed in place. The parser splits the data into IFDs,
+/// entries, and data blocks that borrow the data in the same way.
+pub trait Bytes<'a>: Sized + Default + Debug {
+    /// Reference to a value within the data
+    type Ref<T: Debug + 'a>: Deref<Target = T> + Debug;
+
+    fn as_slice(&self) -> &[u8];
+
+    fn split_at_checked(self, mid: usize) -> Option<(Self, Self)>;
+
+    /// Interpret the complete data as `T`
+    fn cast<T>(self) -> Result<Self::Ref<T>, Error>
+    where
+        T: FromBytes + IntoBytes + Immutable + KnownLayout + Debug + 'a;
+}
+
+impl<'a> Bytes<'a> for &'a [u8] {
+    type Ref<T: Debug + 'a> = &'a T;
+
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+
+    fn split_at_checked(self, mid: usize) -> Option<(Self, Self)> {
+        <[u8]>::split_at_checked(self, mid)
+    }
+
+    fn cast<T>(self) -> Result<Self::Ref<T>, Error>
+    where
+        T: FromBytes + IntoBytes + Immutable + KnownLayout + Debug + 'a,
+    {
+        T::ref_from_bytes(self).map_err(Into::into)
+    }
+}
+
+impl<'a> Bytes<'a> for &'a mut [u8] {
+    type Ref<T: Debug + 'a> = &'a mut T;
+
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+
+    fn split_at_checked(self, mid: usize) -> Option<(Self, Self)> {
+        self.split_at_mut_checked(mid)
+    }
+
+    fn cast<T>(self) -> Result<Self::Ref<T>, Error>
+    where
+        T: FromBytes + IntoBytes + Immutable + KnownLayout + Debug + 'a,
+    {
+        T::mut_from_bytes(self).map_err(Into::into)
+    }
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! forall_formats_self {
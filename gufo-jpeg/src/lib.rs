@@ -42,6 +42,36 @@ impl ImageMetadata for Jpeg {
     }
 }
 
+/// Metadata of a JPEG image without the image data
+///
+/// Only contains the segments in front of the first scan. In contrast to
+/// [`Jpeg`], the image can't be decoded from it.
+#[derive(Debug)]
+pub struct JpegMetadata {
+    jpeg: Jpeg,
+}
+
+impl JpegMetadata {
+    /// Read the segments in front of the image data from `reader`
+    ///
+    /// Reading stops at the first scan. The entropy-coded data and everything
+    /// behind them are not read, such that only the headers and the metadata
+    /// are kept in memory.
+    pub fn for_reader(reader: impl Read) -> Result<Self, Error> {
+        Jpeg::for_reader(reader).map(|jpeg| Self { jpeg })
+    }
+}
+
+impl ImageMetadata for JpegMetadata {
+    fn exif(&self) -> Vec<Vec<u8>> {
+        self.jpeg.exif()
+    }
+
+    fn xmp(&self) -> Vec<Vec<u8>> {
+        self.jpeg.xmp()
+    }
+}
+
 impl Jpeg {
     pub fn new(data: Vec<u8>) -> Result<Self, ErrorWithData<Error>> {
         match Self::find_segments(&data) {
@@ -50,6 +80,51 @@ impl Jpeg {
         }
     }
 
+    /// Read the segments in front of the image data from `reader`
+    ///
+    /// See [`JpegMetadata::for_reader`].
+    fn for_reader(mut reader: impl Read) -> Result<Self, Error> {
+        let mut data = vec![0; 2];
+        reader
+            .read_exact(&mut data)
+            .map_err(|_| Error::UnexpectedEof)?;
+
+        if MAGIC_BYTES.get(..2) != Some(&data) {
+            return Err(Error::InvalidMagicBytes([data[0], data[1]]));
+        }
+
+        let buf = &mut [0; 2];
+        loop {
+            reader.read_exact(buf).map_err(|_| Error::UnexpectedEof)?;
+
+            if buf[0] != MARKER_START {
+                return Err(Error::ExpectedMarkerStart(buf[0]));
+            }
+
+            let marker = Marker::from(buf[1]);
+            if matches!(marker, Marker::SOS | Marker::EOI) {
+                break;
+            }
+
+            data.extend_from_slice(buf);
+
+            if !marker.is_standalone() {
+                // The length includes the two length bytes
+                reader.read_exact(buf).map_err(|_| Error::UnexpectedEof)?;
+                data.extend_from_slice(buf);
+                let len = u16::from_be_bytes(*buf).safe_sub(2)?;
+
+                gufo_common::read::append_exact(&mut reader, &mut data, len.into())
+                    .map_err(|_| Error::UnexpectedEof)?;
+            }
+        }
+
+        data.extend_from_slice(&[MARKER_START, Marker::EOI.into()]);
+
+        let segments = Self::find_segments(&data)?;
+        Ok(Self { segments, data })
+    }
+
     pub fn into_inner(self) -> Vec<u8> {
         self.data
     }
@@ -1,5 +1,5 @@
 use std::collections::BTreeMap;
-use std::io::{Cursor, Read};
+use std::io::{Cursor, Read, Seek};
 use std::ops::Range;
 use std::slice::SliceIndex;
 
@@ -98,6 +98,43 @@ impl ImageMetadata for Png {
 
 impl ImageComplete for Png {}
 
+/// Metadata of a PNG image without the image data
+///
+/// Contains all chunks except for the `IDAT` chunks. In contrast to [`Png`],
+/// the image can't be decoded from it.
+#[derive(Debug, Clone)]
+pub struct PngMetadata {
+    png: Png,
+}
+
+impl PngMetadata {
+    /// Read all chunks except for the image data from `reader`
+    ///
+    /// The `IDAT` chunks are skipped without reading them. Chunks behind the
+    /// image data are still read since they can contain metadata.
+    pub fn for_reader(reader: impl Read + Seek) -> Result<Self, Error> {
+        Png::for_reader(reader).map(|png| Self { png })
+    }
+}
+
+impl ImageMetadata for PngMetadata {
+    fn cicp(&self) -> Option<Cicp> {
+        self.png.cicp()
+    }
+
+    fn exif(&self) -> Vec<Vec<u8>> {
+        self.png.exif()
+    }
+
+    fn xmp(&self) -> Vec<Vec<u8>> {
+        self.png.xmp()
+    }
+
+    fn key_value(&self) -> BTreeMap<String, String> {
+        self.png.key_value()
+    }
+}
+
 /// Representation of a PNG image
 ///
 /// ```
@@ -125,6 +162,65 @@ impl Png {
         }
     }
 
+    /// Read all chunks except for the image data from `reader`
+    ///
+    /// See [`PngMetadata::for_reader`].
+    fn for_reader(mut reader: impl Read + Seek) -> Result<Self, Error> {
+        let mut data = vec![0; MAGIC_BYTES.len()];
+        reader
+            .read_exact(&mut data)
+            .map_err(|_| Error::UnexpectedEof)?;
+
+        if data != MAGIC_BYTES {
+            return Err(Error::InvalidMagicBytes(data));
+        }
+
+        loop {
+            // First 4 bytes are length
+            let length_data = &mut [0; 4];
+            reader
+                .read_exact(length_data)
+                .map_err(|_| Error::UnexpectedEof)?;
+            let length = u32::from_be_bytes(*length_data);
+
+            // Next 4 bytes are chunk type
+            let chunk_type_data = &mut [0; 4];
+            reader
+                .read_exact(chunk_type_data)
+                .map_err(|_| Error::UnexpectedEof)?;
+            let chunk_type = ChunkType::from(u32::from_be_bytes(*chunk_type_data));
+
+            // Data followed by the CRC
+            let remaining = u64::from(length)
+                .checked_add(4)
+                .ok_or(Error::PositionTooLarge)?;
+
+            if chunk_type == ChunkType::IDAT {
+                let offset = i64::try_from(remaining).map_err(|_| Error::PositionTooLarge)?;
+                reader
+                    .seek_relative(offset)
+                    .map_err(|_| Error::UnexpectedEof)?;
+                continue;
+            }
+
+            data.extend_from_slice(length_data);
+            data.extend_from_slice(chunk_type_data);
+            gufo_common::read::append_exact(&mut reader, &mut data, remaining)
+                .map_err(|_| Error::UnexpectedEof)?;
+
+            if chunk_type == ChunkType::IEND {
+                break;
+            }
+        }
+
+        let chunks = Self::find_chunks(&data)?;
+        Ok(Self {
+            chunks,
+            data,
+            inflate_limit: DEFAULT_INFLATE_LIMIT,
+        })
+    }
+
     /// Convert into raw data
     pub fn into_inner(self) -> Vec<u8> {
         self.data
@@ -1,5 +1,9 @@
+mod metadata;
+
 use gufo_common::error::ErrorWithData;
 use gufo_common::image::ImageMetadata;
+use gufo_common::math::MathError;
+pub use metadata::*;
 
 const LE_MAGIC_BYTES: &[u8] = b"II*\0";
 const BE_MAGIC_BYTES: &[u8] = b"MM\0*";
@@ -31,4 +35,19 @@ impl Tiff {
 }
 
 #[derive(Debug, thiserror::Error)]
-pub enum Error {}
+pub enum Error {
+    #[error("Invalid magic bytes: {0:?}")]
+    InvalidMagicBytes([u8; 4]),
+    #[error("Unexpected end of file")]
+    UnexpectedEof,
+    #[error("Position too large")]
+    PositionTooLarge,
+    #[error("Math error: {0}")]
+    Math(#[from] MathError),
+}
+
+impl From<std::num::TryFromIntError> for Error {
+    fn from(_: std::num::TryFromIntError) -> Self {
+        Self::PositionTooLarge
+    }
+}
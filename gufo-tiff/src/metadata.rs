@@ -0,0 +1,401 @@
+use std::collections::BTreeSet;
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
+
+use gufo_common::image::ImageMetadata;
+use gufo_common::math::cheq;
+
+use crate::Error;
+
+const TAG_SUB_IFDS: u16 = 0x014A;
+const TAG_EXIF_IFD_POINTER: u16 = 0x8769;
+const TAG_GPS_INFO_IFD_POINTER: u16 = 0x8825;
+const TAG_INTEROPERABILITY_IFD_POINTER: u16 = 0xA005;
+const TAG_MAKER_NOTE: u16 = 0x927C;
+
+/// Metadata of a TIFF image without the image data
+///
+/// Contains the IFDs and the values they reference, but not the strips or
+/// tiles. In contrast to [`Tiff`](crate::Tiff), the image can't be decoded
+/// from it.
+#[derive(Debug, Clone)]
+pub struct TiffMetadata {
+    data: Vec<u8>,
+}
+
+impl ImageMetadata for TiffMetadata {
+    fn exif(&self) -> Vec<Vec<u8>> {
+        vec![self.data.clone()]
+    }
+}
+
+impl TiffMetadata {
+    /// Read the IFDs and the values they reference from `reader`
+    ///
+    /// The IFDs are found by following the IFD offsets. This includes the
+    /// chain of image IFDs, SubIFDs, and the Exif, GPS, and interoperability
+    /// IFDs. Image data are not read. IFDs other than the first one and values
+    /// that can't be read are skipped.
+    ///
+    /// The read parts are stored behind each other and all offsets to them
+    /// are adjusted. Since offsets inside a MakerNote can be relative to the
+    /// start of the file, everything up to the end of the MakerNote is kept at
+    /// its original position. Offsets to image data are not adjusted and
+    /// point to data that are not available.
+    pub fn for_reader(reader: impl Read + Seek) -> Result<Self, Error> {
+        let mut reader = TiffReader::new(reader)?;
+        let structure = reader.structure()?;
+        let layout = Layout::new(&structure)?;
+
+        let mut data = Vec::new();
+        for (range, new_start) in &layout.ranges {
+            data.resize(usize::try_from(*new_start)?, 0);
+            let len = (cheq(range.end) - range.start).check()?;
+            reader.append(range.start, len, &mut data)?;
+        }
+
+        for (pos, width) in structure.offset_fields {
+            let Some(field) = layout.map(pos) else {
+                continue;
+            };
+            let field = usize::try_from(field)?;
+            let field_end = (cheq(field) + usize::try_from(width)?).check()?;
+            let bytes = data.get_mut(field..field_end).ok_or(Error::UnexpectedEof)?;
+
+            if let Some(new) = layout.map(reader.uint(bytes)) {
+                reader.write_uint(bytes, new)?;
+            }
+        }
+
+        Ok(Self { data })
+    }
+}
+
+/// Positions of all parts that belong to the metadata
+#[derive(Debug, Default)]
+struct Structure {
+    /// IFDs that still have to be read and whether they are part of the
+    /// chain of image IFDs
+    queue: Vec<(u64, bool)>,
+    /// Header, IFDs, and values stored outside of the IFDs
+    ranges: Vec<Range<u64>>,
+    /// Position and size of all fields that contain offsets
+    offset_fields: BTreeSet<(u64, u64)>,
+    /// Data of the MakerNote
+    maker_note: Option<Range<u64>>,
+}
+
+/// New positions of the parts that are kept
+#[derive(Debug)]
+struct Layout {
+    /// Original range and its new start, ordered by the original position
+    ranges: Vec<(Range<u64>, u64)>,
+}
+
+impl Layout {
+    fn new(structure: &Structure) -> Result<Self, Error> {
+        let mut sorted = structure.ranges.clone();
+        sorted.sort_by_key(|x| x.start);
+
+        // Everything up to the end of the MakerNote keeps its position
+        let prefix_end = structure.maker_note.as_ref().map_or(0, |x| x.end);
+        let mut merged = Vec::<Range<u64>>::new();
+        merged.push(0..prefix_end);
+        for range in sorted {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        let mut ranges = Vec::new();
+        let mut pos: u64 = 0;
+        for range in merged {
+            if range.is_empty() {
+                continue;
+            }
+            // Offsets have to point to word boundaries
+            pos = (cheq(pos) + pos % 2).check()?;
+            let len = (cheq(range.end) - range.start).check()?;
+            ranges.push((range, pos));
+            pos = (cheq(pos) + len).check()?;
+        }
+
+        Ok(Self { ranges })
+    }
+
+    /// New position for the original position `pos`
+    fn map(&self, pos: u64) -> Option<u64> {
+        self.ranges
+            .iter()
+            .find(|(range, _)| range.contains(&pos))
+            .and_then(|(range, new_start)| (cheq(*new_start) + pos - range.start).check().ok())
+    }
+}
+
+impl Structure {
+    fn append(&mut self, other: Self) {
+        self.queue.extend(other.queue);
+        self.ranges.extend(other.ranges);
+        self.offset_fields.extend(other.offset_fields);
+        self.maker_note = other.maker_note.or(self.maker_note.take());
+    }
+}
+
+struct TiffReader<R> {
+    reader: R,
+    /// Position of the TIFF header in the reader
+    base: u64,
+    /// Length of the data behind the TIFF header
+    len: u64,
+    big_endian: bool,
+    big_tiff: bool,
+}
+
+impl<R: Read + Seek> TiffReader<R> {
+    fn new(mut reader: R) -> Result<Self, Error> {
+        let base = reader.stream_position().map_err(|_| Error::UnexpectedEof)?;
+        let end = reader
+            .seek(SeekFrom::End(0))
+            .map_err(|_| Error::UnexpectedEof)?;
+        let len = end.checked_sub(base).ok_or(Error::UnexpectedEof)?;
+        reader
+            .seek(SeekFrom::Start(base))
+            .map_err(|_| Error::UnexpectedEof)?;
+
+        let magic_bytes = &mut [0; 4];
+        reader
+            .read_exact(magic_bytes)
+            .map_err(|_| Error::UnexpectedEof)?;
+
+        let big_endian = match magic_bytes.get(..2) {
+            Some(b"II") => false,
+            Some(b"MM") => true,
+            _ => return Err(Error::InvalidMagicBytes(*magic_bytes)),
+        };
+
+        let mut tiff = Self {
+            reader,
+            base,
+            len,
+            big_endian,
+            big_tiff: false,
+        };
+
+        tiff.big_tiff = match tiff.uint(&magic_bytes[2..]) {
+            42 => false,
+            43 => true,
+            _ => return Err(Error::InvalidMagicBytes(*magic_bytes)),
+        };
+
+        Ok(tiff)
+    }
+
+    /// Size of offsets and of the values that are stored inside IFD entries
+    fn offset_size(&self) -> u64 {
+        if self.big_tiff { 8 } else { 4 }
+    }
+
+    /// Size of the number of entries in an IFD
+    fn n_entries_size(&self) -> u64 {
+        if self.big_tiff { 8 } else { 2 }
+    }
+
+    /// Position of the offset to the first IFD
+    fn first_ifd_field(&self) -> u64 {
+        if self.big_tiff { 8 } else { 4 }
+    }
+
+    fn entry_size(&self) -> u64 {
+        if self.big_tiff { 20 } else { 12 }
+    }
+
+    /// Find the header, all IFDs, and the values they reference
+    fn structure(&mut self) -> Result<Structure, Error> {
+        let mut structure = Structure::default();
+
+        let first_ifd_field = self.first_ifd_field();
+        let header_len = (cheq(first_ifd_field) + self.offset_size()).check()?;
+        structure.ranges.push(0..header_len);
+        structure
+            .offset_fields
+            .insert((first_ifd_field, self.offset_size()));
+
+        let first_ifd = self.read_uint(first_ifd_field, self.offset_size())?;
+        structure.queue.push((first_ifd, true));
+
+        let mut visited = BTreeSet::new();
+        while let Some((offset, is_chain)) = structure.queue.pop() {
+            if offset == 0 || !visited.insert(offset) {
+                continue;
+            }
+
+            let mut ifd = Structure::default();
+            match self.ifd(offset, is_chain, &mut ifd) {
+                Ok(()) => structure.append(ifd),
+                Err(err) if offset == first_ifd => return Err(err),
+                Err(_) => {}
+            }
+        }
+
+        Ok(structure)
+    }
+
+    /// Record the IFD at `offset` and the values it references
+    fn ifd(&mut self, offset: u64, is_chain: bool, structure: &mut Structure) -> Result<(), Error> {
+        let n_entries = self.read_uint(offset, self.n_entries_size())?;
+        let entries_start = (cheq(offset) + self.n_entries_size()).check()?;
+        let entries_len = (cheq(n_entries) * self.entry_size()).check()?;
+        let next_ifd_field = (cheq(entries_start) + entries_len).check()?;
+        let ifd_end = (cheq(next_ifd_field) + self.offset_size()).check()?;
+        structure.ranges.push(offset..ifd_end);
+
+        let mut entries = Vec::new();
+        self.append(
+            entries_start,
+            (cheq(entries_len) + self.offset_size()).check()?,
+            &mut entries,
+        )?;
+
+        let entry_size = usize::try_from(self.entry_size())?;
+        for (entry_pos, entry) in (entries_start..)
+            .step_by(entry_size)
+            .zip(entries.chunks_exact(entry_size))
+        {
+            self.entry(entry_pos, entry, structure)?;
+        }
+
+        let next_ifd = self.read_uint(next_ifd_field, self.offset_size())?;
+        if is_chain && next_ifd != 0 {
+            structure
+                .offset_fields
+                .insert((next_ifd_field, self.offset_size()));
+            structure.queue.push((next_ifd, true));
+        }
+
+        Ok(())
+    }
+
+    /// Record the value of an IFD entry and queue the IFDs it points to
+    fn entry(
+        &mut self,
+        entry_pos: u64,
+        entry: &[u8],
+        structure: &mut Structure,
+    ) -> Result<(), Error> {
+        let (tag, rest) = entry.split_at_checked(2).ok_or(Error::UnexpectedEof)?;
+        let (type_, rest) = rest.split_at_checked(2).ok_or(Error::UnexpectedEof)?;
+        let (count, _) = rest
+            .split_at_checked(usize::try_from(self.offset_size())?)
+            .ok_or(Error::UnexpectedEof)?;
+
+        let tag = u16::try_from(self.uint(tag))?;
+        let type_ = u16::try_from(self.uint(type_))?;
+        let count = self.uint(count);
+        let value_field = (cheq(entry_pos) + 4 + self.offset_size()).check()?;
+
+        // Values of unknown types can't be located
+        let Some(type_size) = type_size(type_) else {
+            return Ok(());
+        };
+        let len = (cheq(count) * type_size).check()?;
+
+        let value_pos = if len > self.offset_size() {
+            let value_offset = self.read_uint(value_field, self.offset_size())?;
+            let value_end = (cheq(value_offset) + len).check()?;
+            if value_end > self.len {
+                // Value can't be read
+                return Ok(());
+            }
+
+            structure.ranges.push(value_offset..value_end);
+            structure
+                .offset_fields
+                .insert((value_field, self.offset_size()));
+
+            if tag == TAG_MAKER_NOTE {
+                structure.maker_note = Some(value_offset..value_end);
+            }
+
+            value_offset
+        } else {
+            value_field
+        };
+
+        let is_ifd_pointer = matches!(
+            tag,
+            TAG_SUB_IFDS
+                | TAG_EXIF_IFD_POINTER
+                | TAG_GPS_INFO_IFD_POINTER
+                | TAG_INTEROPERABILITY_IFD_POINTER
+        );
+        // Only offsets can point to IFDs: LONG, IFD, LONG8, and IFD8
+        if is_ifd_pointer && matches!(type_, 4 | 13 | 16 | 18) {
+            for i in 0..count {
+                let pos = (cheq(value_pos) + cheq(i) * type_size).check()?;
+                structure.offset_fields.insert((pos, type_size));
+                structure
+                    .queue
+                    .push((self.read_uint(pos, type_size)?, false));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read an unsigned integer of `width` bytes at `pos`
+    fn read_uint(&mut self, pos: u64, width: u64) -> Result<u64, Error> {
+        let mut bytes = Vec::new();
+        self.append(pos, width, &mut bytes)?;
+        Ok(self.uint(&bytes))
+    }
+
+    /// Append `len` bytes at position `pos` to `buf`
+    fn append(&mut self, pos: u64, len: u64, buf: &mut Vec<u8>) -> Result<(), Error> {
+        let pos = (cheq(self.base) + pos).check()?;
+        self.reader
+            .seek(SeekFrom::Start(pos))
+            .map_err(|_| Error::UnexpectedEof)?;
+        gufo_common::read::append_exact(&mut self.reader, buf, len)
+            .map_err(|_| Error::UnexpectedEof)
+    }
+
+    fn uint(&self, bytes: &[u8]) -> u64 {
+        let mut buf = [0; 8];
+        if self.big_endian {
+            let start = buf.len().saturating_sub(bytes.len());
+            buf[start..].copy_from_slice(bytes);
+            u64::from_be_bytes(buf)
+        } else {
+            buf[..bytes.len()].copy_from_slice(bytes);
+            u64::from_le_bytes(buf)
+        }
+    }
+
+    fn write_uint(&self, bytes: &mut [u8], value: u64) -> Result<(), Error> {
+        match (bytes.len(), self.big_endian) {
+            (4, false) => bytes.copy_from_slice(&u32::try_from(value)?.to_le_bytes()),
+            (4, true) => bytes.copy_from_slice(&u32::try_from(value)?.to_be_bytes()),
+            (8, false) => bytes.copy_from_slice(&value.to_le_bytes()),
+            (8, true) => bytes.copy_from_slice(&value.to_be_bytes()),
+            _ => return Err(Error::PositionTooLarge),
+        }
+
+        Ok(())
+    }
+}
+
+/// Size of a single value of type `type_`
+fn type_size(type_: u16) -> Option<u64> {
+    match type_ {
+        // BYTE, ASCII, SBYTE, UNDEFINED
+        1 | 2 | 6 | 7 => Some(1),
+        // SHORT, SSHORT
+        3 | 8 => Some(2),
+        // LONG, SLONG, FLOAT, IFD
+        4 | 9 | 11 | 13 => Some(4),
+        // RATIONAL, SRATIONAL, DOUBLE, LONG8, SLONG8, IFD8
+        5 | 10 | 12 | 16 | 17 | 18 => Some(8),
+        _ => None,
+    }
+}
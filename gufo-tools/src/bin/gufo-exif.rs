@@ -61,11 +61,19 @@ pub fn output<'a, S: Storage<'a>>(exif: &mut Exif<'a, S>) -> String {
 
             if let Some(entries) = entries.get(ifd) {
                 for entry in entries.values() {
-                    let tag_name = gufo_common::exif::lookup_tag_name(entry.tag_ifd);
+                    let tag_info = gufo_common::exif::lookup_tag_info(entry.tag_ifd);
+                    let tag_name = tag_info.map_or_else(
+                        || format!("Unknown(0x{:04X})", entry.tag_ifd.tag.0),
+                        |x| x.name.to_string(),
+                    );
+                    let unexpected_type = tag_info.is_some_and(|x| !x.types.contains(&entry.type_));
                     let row = format!(
-                        "{tag:25} {count:2}×{type_:10}",
-                        tag = tag_name.unwrap_or(&format!("Unknown({})", entry.tag_ifd.tag.0)),
-                        type_ = format!("{:?}", entry.type_),
+                        "{tag_name:25} {count:2}×{type_:10}",
+                        type_ = format!(
+                            "{:?}{}",
+                            entry.type_,
+                            if unexpected_type { "!" } else { "" }
+                        ),
                         count = entry.count,
                     );
 
@@ -96,4 +104,5 @@ pub fn output<'a, S: Storage<'a>>(exif: &mut Exif<'a, S>) -> String {
 
         s
     })
+    .unwrap()
 }
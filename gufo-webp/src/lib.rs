@@ -26,6 +26,32 @@ impl ImageMetadata for WebP {
     }
 }
 
+/// Metadata of a WEBP image without the image data
+///
+/// Contains all chunks except for the image data. In contrast to [`WebP`], the
+/// image can't be decoded from it.
+#[derive(Debug, Clone)]
+pub struct WebPMetadata {
+    webp: WebP,
+}
+
+impl WebPMetadata {
+    /// Read all chunks except for the image data from `reader`
+    ///
+    /// The [`VP8`](FourCC::VP8), [`VP8L`](FourCC::VP8L),
+    /// [`ALPH`](FourCC::ALPH), and [`ANMF`](FourCC::ANMF) chunks are skipped
+    /// without reading them.
+    pub fn for_reader(reader: impl Read + Seek) -> Result<Self, Error> {
+        WebP::for_reader(reader).map(|webp| Self { webp })
+    }
+}
+
+impl ImageMetadata for WebPMetadata {
+    fn exif(&self) -> Vec<Vec<u8>> {
+        self.webp.exif()
+    }
+}
+
 /// Representation of a WEBP image
 impl WebP {
     /// Returns WEBP image representation
@@ -38,6 +64,106 @@ impl WebP {
         }
     }
 
+    /// Read all chunks except for the image data from `reader`
+    ///
+    /// See [`WebPMetadata::for_reader`].
+    fn for_reader(mut reader: impl Read + Seek) -> Result<Self, Error> {
+        // Riff magic bytes
+        let riff_magic_bytes = &mut [0; RIFF_MAGIC_BYTES.len()];
+        reader
+            .read_exact(riff_magic_bytes)
+            .map_err(|_| Error::UnexpectedEof)?;
+        if riff_magic_bytes != RIFF_MAGIC_BYTES {
+            return Err(Error::RiffMagicBytesMissing(*riff_magic_bytes));
+        }
+
+        // File length, not including the magic bytes and the length itself
+        let file_length_data = &mut [0; 4];
+        reader
+            .read_exact(file_length_data)
+            .map_err(|_| Error::UnexpectedEof)?;
+        let file_end = u64::from(u32::from_le_bytes(*file_length_data))
+            .checked_add(8)
+            .ok_or(Error::PositionTooLarge)?;
+
+        // WebP magic bytes
+        let webp_magic_bytes = &mut [0; WEBP_MAGIC_BYTES.len()];
+        reader
+            .read_exact(webp_magic_bytes)
+            .map_err(|_| Error::UnexpectedEof)?;
+        if webp_magic_bytes != WEBP_MAGIC_BYTES {
+            return Err(Error::WebpMagicBytesMissing(*webp_magic_bytes));
+        }
+
+        let mut data = [
+            riff_magic_bytes.as_slice(),
+            file_length_data,
+            webp_magic_bytes,
+        ]
+        .concat();
+        let mut position = data.len() as u64;
+        let mut chunks = Vec::new();
+        while position < file_end {
+            // Next 4 bytes are chunk FourCC (chunk type)
+            let four_cc_data = &mut [0; 4];
+            reader
+                .read_exact(four_cc_data)
+                .map_err(|_| Error::UnexpectedEof)?;
+            let four_cc = FourCC::from(u32::from_le_bytes(*four_cc_data));
+
+            // Next 4 bytes are chunk size
+            let size_data = &mut [0; 4];
+            reader
+                .read_exact(size_data)
+                .map_err(|_| Error::UnexpectedEof)?;
+            let size = u32::from_le_bytes(*size_data);
+
+            // Payload with padding to an even size
+            let padded_size = u64::from(size)
+                .checked_add(u64::from(size % 2))
+                .ok_or(Error::PositionTooLarge)?;
+            position = position
+                .checked_add(8)
+                .and_then(|x| x.checked_add(padded_size))
+                .ok_or(Error::PositionTooLarge)?;
+
+            if matches!(
+                four_cc,
+                FourCC::VP8 | FourCC::VP8L | FourCC::ALPH | FourCC::ANMF
+            ) {
+                let offset = i64::try_from(padded_size).map_err(|_| Error::PositionTooLarge)?;
+                reader
+                    .seek_relative(offset)
+                    .map_err(|_| Error::UnexpectedEof)?;
+                continue;
+            }
+
+            data.extend_from_slice(four_cc_data);
+            data.extend_from_slice(size_data);
+            let payload_start = data.len();
+            gufo_common::read::append_exact(&mut reader, &mut data, padded_size)
+                .map_err(|_| Error::UnexpectedEof)?;
+            let payload_end = payload_start
+                .checked_add(size as usize)
+                .ok_or(Error::PositionTooLarge)?;
+
+            chunks.push(RawChunk {
+                four_cc,
+                payload: payload_start..payload_end,
+            });
+        }
+
+        // Update the file length to the remaining data
+        let file_length = data
+            .len()
+            .checked_sub(8)
+            .and_then(|x| u32::try_from(x).ok())
+            .ok_or(Error::PositionTooLarge)?;
+        data.splice(4..8, file_length.to_le_bytes());
+
+        Ok(Self { data, chunks })
+    }
+
     pub fn is_filetype(data: &[u8]) -> bool {
         data.starts_with(RIFF_MAGIC_BYTES) && data.get(8..12) == Some(WEBP_MAGIC_BYTES)
     }
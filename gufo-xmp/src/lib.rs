@@ -97,6 +97,9 @@ impl Xmp {
         })
     }
 
+    /// Change the values of properties
+    ///
+    /// Properties that don't exist yet are added. Arrays are written anew.
     pub fn update(&mut self, updates: BTreeMap<Tag, Value>) -> Result<(), Error> {
         let (entries, data) = Self::lookup_and_update(&self.inner, updates)?;
         self.entries = entries;
@@ -1,10 +1,10 @@
 use std::collections::BTreeMap;
-use std::io::Cursor;
+use std::io::{Cursor, Write};
 
 use gufo_common::xmp::XML_NS_RDF;
 use xml::name::OwnedName;
 use xml::reader::XmlEvent;
-use xml::{EmitterConfig, ParserConfig, writer};
+use xml::{EmitterConfig, EventWriter, ParserConfig, writer};
 
 use super::{Error, Tag, Xmp};
 
@@ -72,15 +72,37 @@ impl Xmp {
         let mut reader_state: ReaderState = ReaderState::Nothing;
         let mut level_below_property_node = 0;
         let mut found_properties = BTreeMap::new();
+        // Depth inside of a property whose original value is replaced
+        let mut skip_depth: Option<usize> = None;
 
         for event in parser {
-            match event? {
+            let event = event?;
+
+            // Skip the original content of a replaced property
+            if let Some(depth) = &mut skip_depth {
+                match event {
+                    XmlEvent::StartElement { .. } => {
+                        *depth = depth.saturating_add(1);
+                        continue;
+                    }
+                    XmlEvent::EndElement { .. } if *depth > 0 => {
+                        *depth = depth.saturating_sub(1);
+                        continue;
+                    }
+                    // End of the replaced property
+                    XmlEvent::EndElement { .. } => skip_depth = None,
+                    _ => continue,
+                }
+            }
+
+            match event {
                 ref event @ XmlEvent::StartElement {
                     ref name,
                     ref attributes,
                     ref namespace,
                 } => {
                     let mut event = event.clone();
+                    let mut replacement = None;
 
                     match &reader_state {
                         ReaderState::Nothing => {
@@ -133,6 +155,13 @@ impl Xmp {
                         ReaderState::TypedNode => {
                             // Inside rdf:Description, hence we are entering a property
                             if let Some(tag) = Tag::from_name(name) {
+                                // Arrays are written anew instead of updating the existing items
+                                if UPDATE
+                                    && let Some(value @ (Value::Bag(_) | Value::Seq(_))) =
+                                        updates.get(&tag)
+                                {
+                                    replacement = Some((tag.clone(), value));
+                                }
                                 reader_state = ReaderState::Property(tag);
                             }
                         }
@@ -159,6 +188,12 @@ impl Xmp {
                             writer.write(event)?;
                         }
                     }
+
+                    if let Some((tag, value)) = replacement {
+                        write_value(&mut writer, value)?;
+                        found_properties.insert(tag, value.clone());
+                        skip_depth = Some(0);
+                    }
                 }
                 XmlEvent::Characters(data) => {
                     let mut data = &data;
@@ -212,6 +247,19 @@ impl Xmp {
                     match reader_state {
                         ReaderState::RdfTag => {
                             // rdf:RDF closed
+                            if UPDATE {
+                                // Add properties that don't exist yet
+                                let missing = updates
+                                    .iter()
+                                    .filter(|(tag, _)| !found_properties.contains_key(*tag))
+                                    .collect::<Vec<_>>();
+                                if !missing.is_empty() {
+                                    write_description(&mut writer, &missing)?;
+                                }
+                                for (tag, value) in missing {
+                                    found_properties.insert(tag.clone(), value.clone());
+                                }
+                            }
                             reader_state = ReaderState::Nothing;
                         }
                         ReaderState::TypedNode => {
@@ -227,8 +275,9 @@ impl Xmp {
                         }
                         ReaderState::RdfBagLi(tag) => reader_state = ReaderState::RdfBag(tag),
                         ReaderState::RdfSeqLi(tag) => reader_state = ReaderState::RdfSeq(tag),
-                        ReaderState::RdfBag(_) | ReaderState::RdfSeq(_) => {
-                            reader_state = ReaderState::RdfTag;
+                        ReaderState::RdfBag(tag) | ReaderState::RdfSeq(tag) => {
+                            level_below_property_node -= 1;
+                            reader_state = ReaderState::Property(tag);
                         }
                         _ => {}
                     }
@@ -252,3 +301,62 @@ impl Xmp {
         Ok((found_properties, output))
     }
 }
+
+/// Write a new rdf:Description with `properties`
+fn write_description<W: Write>(
+    writer: &mut EventWriter<W>,
+    properties: &[(&Tag, &Value)],
+) -> Result<(), Error> {
+    let mut prefixes = BTreeMap::new();
+    for (tag, _) in properties {
+        let n_prefix = prefixes.len();
+        prefixes.entry(tag.namespace()).or_insert_with(|| {
+            tag.namespace()
+                .prefix()
+                .map_or_else(|| format!("ns{n_prefix}"), ToString::to_string)
+        });
+    }
+
+    let mut start = writer::XmlEvent::start_element("rdf:Description").attr("rdf:about", "");
+    for (namespace, prefix) in &prefixes {
+        start = start.ns(prefix.as_str(), namespace.to_url());
+    }
+    writer.write(start)?;
+
+    for (tag, value) in properties {
+        let prefix = prefixes
+            .get(tag.namespace())
+            .ok_or_else(|| Error::other("Missing namespace prefix"))?;
+        let name = format!("{prefix}:{}", tag.name());
+
+        writer.write(writer::XmlEvent::start_element(name.as_str()))?;
+        write_value(writer, value)?;
+        writer.write(writer::XmlEvent::end_element())?;
+    }
+
+    writer.write(writer::XmlEvent::end_element())?;
+
+    Ok(())
+}
+
+/// Write the content of a property element
+fn write_value<W: Write>(writer: &mut EventWriter<W>, value: &Value) -> Result<(), Error> {
+    let (container, items) = match value {
+        Value::Generic(s) => {
+            writer.write(writer::XmlEvent::Characters(s))?;
+            return Ok(());
+        }
+        Value::Bag(items) => ("rdf:Bag", items),
+        Value::Seq(items) => ("rdf:Seq", items),
+    };
+
+    writer.write(writer::XmlEvent::start_element(container))?;
+    for item in items {
+        writer.write(writer::XmlEvent::start_element("rdf:li"))?;
+        writer.write(writer::XmlEvent::Characters(item))?;
+        writer.write(writer::XmlEvent::end_element())?;
+    }
+    writer.write(writer::XmlEvent::end_element())?;
+
+    Ok(())
+}
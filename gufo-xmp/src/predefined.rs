@@ -1,9 +1,18 @@
 use gufo_common::types::Rational;
-use gufo_common::{field, orientation};
+use gufo_common::{capture, field, orientation};
 
 use super::Xmp;
 
 impl Xmp {
+    pub fn color_space(&self) -> Option<capture::ColorSpace> {
+        self.get_u16(field::ColorSpace)
+            .map(capture::ColorSpace::from)
+    }
+
+    pub fn contrast(&self) -> Option<capture::Contrast> {
+        self.get_u16(field::Contrast).map(capture::Contrast::from)
+    }
+
     pub fn creator(&self) -> Option<String> {
         self.lookup_generic(field::Creator).map(ToString::to_string)
     }
@@ -18,6 +27,11 @@ impl Xmp {
             .map(ToString::to_string)
     }
 
+    pub fn custom_rendered(&self) -> Option<capture::CustomRendered> {
+        self.get_u16(field::CustomRendered)
+            .map(capture::CustomRendered::from)
+    }
+
     #[cfg(feature = "chrono")]
     pub fn date_time_original(&self) -> Option<gufo_common::datetime::DateTime> {
         self.get_date_time(field::DateTimeOriginal)
@@ -27,6 +41,16 @@ impl Xmp {
         self.get_frac(field::DigitalZoomRatio)
     }
 
+    pub fn exposure_mode(&self) -> Option<capture::ExposureMode> {
+        self.get_u16(field::ExposureMode)
+            .map(capture::ExposureMode::from)
+    }
+
+    pub fn exposure_program(&self) -> Option<capture::ExposureProgram> {
+        self.get_u16(field::ExposureProgram)
+            .map(capture::ExposureProgram::from)
+    }
+
     pub fn exposure_time(&self) -> Option<Rational<u32>> {
         self.get_frac(field::ExposureTime)
     }
@@ -44,6 +68,11 @@ impl Xmp {
         self.get_frac(field::FocalLength)
     }
 
+    pub fn gain_control(&self) -> Option<capture::GainControl> {
+        self.get_u16(field::GainControl)
+            .map(capture::GainControl::from)
+    }
+
     pub fn iso_speed_rating(&self) -> Option<u16> {
         self.get_u16(field::PhotographicSensitivity)
             .or_else(|| self.get_u16(field::ISOSpeedRatings))
@@ -59,17 +88,27 @@ impl Xmp {
             .map(ToString::to_string)
     }
 
+    pub fn light_source(&self) -> Option<capture::LightSource> {
+        self.get_u16(field::LightSource)
+            .map(capture::LightSource::from)
+    }
+
     pub fn make(&self) -> Option<String> {
         self.lookup_generic(field::Make).map(ToString::to_string)
     }
 
+    pub fn metering_mode(&self) -> Option<capture::MeteringMode> {
+        self.get_u16(field::MeteringMode)
+            .map(capture::MeteringMode::from)
+    }
+
     pub fn model(&self) -> Option<String> {
         self.lookup_generic(field::Model).map(ToString::to_string)
     }
 
     pub fn orientation(&self) -> Option<orientation::Orientation> {
         orientation::Orientation::try_from(
-            self.lookup_generic(field::UserComment)
+            self.lookup_generic(field::Orientation)
                 .and_then(|x| str::parse::<u16>(x).ok())?,
         )
         .ok()
@@ -84,13 +123,42 @@ impl Xmp {
             .map(ToString::to_string)
     }
 
+    pub fn saturation(&self) -> Option<capture::Saturation> {
+        self.get_u16(field::Saturation)
+            .map(capture::Saturation::from)
+    }
+
+    pub fn scene_capture_type(&self) -> Option<capture::SceneCaptureType> {
+        self.get_u16(field::SceneCaptureType)
+            .map(capture::SceneCaptureType::from)
+    }
+
+    pub fn sensing_method(&self) -> Option<capture::SensingMethod> {
+        self.get_u16(field::SensingMethod)
+            .map(capture::SensingMethod::from)
+    }
+
+    pub fn sharpness(&self) -> Option<capture::Sharpness> {
+        self.get_u16(field::Sharpness).map(capture::Sharpness::from)
+    }
+
     pub fn software(&self) -> Option<String> {
         self.lookup_generic(field::CreatorTool)
             .map(ToString::to_string)
     }
 
+    pub fn subject_distance_range(&self) -> Option<capture::SubjectDistanceRange> {
+        self.get_u16(field::SubjectDistanceRange)
+            .map(capture::SubjectDistanceRange::from)
+    }
+
     pub fn user_comment(&self) -> Option<String> {
         self.lookup_generic(field::UserComment)
             .map(ToString::to_string)
     }
+
+    pub fn white_balance(&self) -> Option<capture::WhiteBalance> {
+        self.get_u16(field::WhiteBalance)
+            .map(capture::WhiteBalance::from)
+    }
 }
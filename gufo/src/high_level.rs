@@ -1,28 +1,75 @@
 use gufo_common::types::Rational;
-use gufo_common::{geography, hardware, orientation};
+use gufo_common::{capture, field, geography, hardware, orientation};
 use gufo_exif::Exif;
 use gufo_xmp::Xmp;
 
-use crate::Metadata;
+use crate::{Metadata, ReconcilePolicy};
 
 impl Metadata {
     /// Owner of the camera used in photography
     pub fn camera_owner_name(&self) -> Option<String> {
-        self.lookup_exif_xmp(Exif::camera_owner_name, Xmp::camera_owner_name)
+        self.lookup_exif_xmp(
+            field::CameraOwnerName,
+            Exif::camera_owner_name,
+            Xmp::camera_owner_name,
+        )
+    }
+
+    /// Color space of the image data
+    pub fn color_space(&self) -> Option<capture::ColorSpace> {
+        self.lookup_exif_xmp(field::ColorSpace, Exif::color_space, Xmp::color_space)
+    }
+
+    /// Contrast processing applied by the camera
+    pub fn contrast(&self) -> Option<capture::Contrast> {
+        self.lookup_exif_xmp(field::Contrast, Exif::contrast, Xmp::contrast)
     }
 
     /// Name of the main person who created the image
     pub fn creator(&self) -> Option<String> {
-        self.lookup_exif_xmp_keyval(Exif::artist, Xmp::creator, |x| x.get("Author").cloned())
+        self.reconcile(ReconcilePolicy::default())
+            .creator()
+            .map(|x| x.value)
+    }
+
+    /// Use of special processing on the image data
+    pub fn custom_rendered(&self) -> Option<capture::CustomRendered> {
+        self.lookup_exif_xmp(
+            field::CustomRendered,
+            Exif::custom_rendered,
+            Xmp::custom_rendered,
+        )
     }
 
     #[cfg(feature = "chrono")]
     pub fn date_time_original(&self) -> Option<gufo_common::datetime::DateTime> {
-        self.lookup_exif_xmp(Exif::date_time_original, Xmp::date_time_original)
+        self.lookup_exif_xmp(
+            field::DateTimeOriginal,
+            Exif::date_time_original,
+            Xmp::date_time_original,
+        )
     }
 
     pub fn digital_zoom_ratio(&self) -> Option<Rational<u32>> {
-        self.lookup_exif_xmp(Exif::digital_zoom_ratio, Xmp::digital_zoom_ratio)
+        self.lookup_exif_xmp(
+            field::DigitalZoomRatio,
+            Exif::digital_zoom_ratio,
+            Xmp::digital_zoom_ratio,
+        )
+    }
+
+    /// Exposure mode set when the image was shot
+    pub fn exposure_mode(&self) -> Option<capture::ExposureMode> {
+        self.lookup_exif_xmp(field::ExposureMode, Exif::exposure_mode, Xmp::exposure_mode)
+    }
+
+    /// Program used by the camera to set the exposure
+    pub fn exposure_program(&self) -> Option<capture::ExposureProgram> {
+        self.lookup_exif_xmp(
+            field::ExposureProgram,
+            Exif::exposure_program,
+            Xmp::exposure_program,
+        )
     }
 
     /// Exposure time in seconds
@@ -31,17 +78,31 @@ impl Metadata {
     /// is typically one, such that the value is given in its common for like
     /// "1/60 sec".
     pub fn exposure_time(&self) -> Option<Rational<u32>> {
-        self.lookup_exif_xmp(Exif::exposure_time, Xmp::exposure_time)
+        self.lookup_exif_xmp(field::ExposureTime, Exif::exposure_time, Xmp::exposure_time)
     }
 
     /// Aperture
     pub fn f_number(&self) -> Option<f32> {
-        self.lookup_exif_xmp(|x| Exif::f_number(x).map(|x| x.as_f32()), Xmp::f_number)
+        self.lookup_exif_xmp(
+            field::FNumber,
+            |x| Exif::f_number(x).map(|x| x.as_f32()),
+            Xmp::f_number,
+        )
+    }
+
+    /// Status of the flash
+    pub fn flash(&self) -> Option<capture::Flash> {
+        self.lookup_exif(Exif::flash)
     }
 
     /// Focal length in millimeters
     pub fn focal_length(&self) -> Option<Rational<u32>> {
-        self.lookup_exif_xmp(Exif::focal_length, Xmp::focal_length)
+        self.lookup_exif_xmp(field::FocalLength, Exif::focal_length, Xmp::focal_length)
+    }
+
+    /// Degree of overall image gain adjustment
+    pub fn gain_control(&self) -> Option<capture::GainControl> {
+        self.lookup_exif_xmp(field::GainControl, Exif::gain_control, Xmp::gain_control)
     }
 
     pub fn gps_location(&self) -> Option<geography::Location> {
@@ -51,15 +112,19 @@ impl Metadata {
 
     /// ISO
     pub fn iso_speed_rating(&self) -> Option<u16> {
-        self.lookup_exif_xmp(Exif::iso_speed_rating, Xmp::iso_speed_rating)
+        self.lookup_exif_xmp(
+            field::PhotographicSensitivity,
+            Exif::iso_speed_rating,
+            Xmp::iso_speed_rating,
+        )
     }
 
     pub fn lens_make(&self) -> Option<String> {
-        self.lookup_exif_xmp(Exif::lens_make, Xmp::lens_make)
+        self.lookup_exif_xmp(field::LensMake, Exif::lens_make, Xmp::lens_make)
     }
 
     pub fn lens_model(&self) -> Option<String> {
-        self.lookup_exif_xmp(Exif::lens_model, Xmp::lens_model)
+        self.lookup_exif_xmp(field::LensModel, Exif::lens_model, Xmp::lens_model)
     }
 
     pub fn lens_specification(&self) -> Option<hardware::LensSpecification> {
@@ -67,14 +132,24 @@ impl Metadata {
         self.lookup_exif(Exif::lens_specification)
     }
 
+    /// Kind of light source
+    pub fn light_source(&self) -> Option<capture::LightSource> {
+        self.lookup_exif_xmp(field::LightSource, Exif::light_source, Xmp::light_source)
+    }
+
     /// Camera manifacturer
     pub fn make(&self) -> Option<String> {
-        self.lookup_exif_xmp(Exif::make, Xmp::make)
+        self.lookup_exif_xmp(field::Make, Exif::make, Xmp::make)
+    }
+
+    /// Metering mode
+    pub fn metering_mode(&self) -> Option<capture::MeteringMode> {
+        self.lookup_exif_xmp(field::MeteringMode, Exif::metering_mode, Xmp::metering_mode)
     }
 
     /// Camera model
     pub fn model(&self) -> Option<String> {
-        self.lookup_exif_xmp(Exif::model, Xmp::model)
+        self.lookup_exif_xmp(field::Model, Exif::model, Xmp::model)
     }
 
     /// Image orientation
@@ -82,14 +157,14 @@ impl Metadata {
     /// Rotation and mirroring that have to be applied to show the image
     /// correctly
     pub fn orientation(&self) -> Option<orientation::Orientation> {
-        self.lookup_exif_xmp(Exif::orientation, Xmp::orientation)
+        self.lookup_exif_xmp(field::Orientation, Exif::orientation, Xmp::orientation)
     }
 
     /// Copyright information
     pub fn rights(&self) -> Option<String> {
-        self.lookup_exif_xmp_keyval(Exif::copyright, Xmp::rights, |x| {
-            x.get("Copyright").cloned()
-        })
+        self.reconcile(ReconcilePolicy::default())
+            .rights()
+            .map(|x| x.value)
     }
 
     /// URL with usage rights information
@@ -97,20 +172,65 @@ impl Metadata {
         self.lookup_xmp(Xmp::rights_web_statement)
     }
 
+    /// Saturation processing applied by the camera
+    pub fn saturation(&self) -> Option<capture::Saturation> {
+        self.lookup_exif_xmp(field::Saturation, Exif::saturation, Xmp::saturation)
+    }
+
+    /// Type of scene that was shot
+    pub fn scene_capture_type(&self) -> Option<capture::SceneCaptureType> {
+        self.lookup_exif_xmp(
+            field::SceneCaptureType,
+            Exif::scene_capture_type,
+            Xmp::scene_capture_type,
+        )
+    }
+
+    /// Type of image sensor
+    pub fn sensing_method(&self) -> Option<capture::SensingMethod> {
+        self.lookup_exif_xmp(
+            field::SensingMethod,
+            Exif::sensing_method,
+            Xmp::sensing_method,
+        )
+    }
+
+    /// Sharpness processing applied by the camera
+    pub fn sharpness(&self) -> Option<capture::Sharpness> {
+        self.lookup_exif_xmp(field::Sharpness, Exif::sharpness, Xmp::sharpness)
+    }
+
     /// Name and version of software or firmware
     ///
     /// In practice, this often contains the name, version, and operating system
     /// of the image editing software used to edit an image.
     pub fn software(&self) -> Option<String> {
-        self.lookup_exif_xmp_keyval(Exif::software, Xmp::creator_tool, |x| {
+        self.lookup_exif_xmp_keyval(field::Software, Exif::software, Xmp::creator_tool, |x| {
             x.get("Software").cloned()
         })
     }
 
+    /// Distance to the subject
+    pub fn subject_distance_range(&self) -> Option<capture::SubjectDistanceRange> {
+        self.lookup_exif_xmp(
+            field::SubjectDistanceRange,
+            Exif::subject_distance_range,
+            Xmp::subject_distance_range,
+        )
+    }
+
     /// Freely write keywords or comments on the image
     pub fn user_comment(&self) -> Option<String> {
-        self.lookup_exif_xmp_keyval(Exif::user_comment, Xmp::user_comment, |x| {
-            x.get("Comment").cloned()
-        })
+        self.lookup_exif_xmp_keyval(
+            field::UserComment,
+            Exif::user_comment,
+            Xmp::user_comment,
+            |x| x.get("Comment").cloned(),
+        )
+    }
+
+    /// White balance mode
+    pub fn white_balance(&self) -> Option<capture::WhiteBalance> {
+        self.lookup_exif_xmp(field::WhiteBalance, Exif::white_balance, Xmp::white_balance)
     }
 }
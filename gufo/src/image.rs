@@ -1,9 +1,15 @@
+use std::io::{Read, Seek, SeekFrom};
+
 use gufo_common::cicp::Cicp;
 use gufo_common::error::ErrorWithData;
 use gufo_common::prelude::*;
 
 use crate::Error;
 
+/// Number of bytes needed to detect the file type
+#[cfg(any(feature = "jpeg", feature = "png", feature = "tiff", feature = "webp"))]
+const MAGIC_BYTES_LEN: u64 = 12;
+
 #[non_exhaustive]
 #[derive(Debug)]
 pub enum Image {
@@ -56,6 +62,48 @@ impl Image {
         Err(ErrorWithData::new(Error::NoSupportedFiletypeFound, data))
     }
 
+    /// Metadata from `reader` without reading the image data
+    ///
+    /// See [`RawMetadata::for_reader`](crate::RawMetadata::for_reader).
+    #[cfg(any(feature = "jpeg", feature = "png", feature = "tiff", feature = "webp"))]
+    pub(crate) fn metadata_for_reader(
+        mut reader: impl Read + Seek,
+    ) -> Result<Box<dyn ImageMetadata>, Error> {
+        let start = reader.stream_position().map_err(Error::Io)?;
+        let mut magic_bytes = Vec::new();
+        (&mut reader)
+            .take(MAGIC_BYTES_LEN)
+            .read_to_end(&mut magic_bytes)
+            .map_err(Error::Io)?;
+        reader.seek(SeekFrom::Start(start)).map_err(Error::Io)?;
+
+        #[cfg(feature = "jpeg")]
+        if gufo_jpeg::Jpeg::is_filetype(&magic_bytes) {
+            let jpeg = gufo_jpeg::JpegMetadata::for_reader(reader).map_err(Error::Jpeg)?;
+            return Ok(Box::new(jpeg));
+        }
+
+        #[cfg(feature = "png")]
+        if gufo_png::Png::is_filetype(&magic_bytes) {
+            let png = gufo_png::PngMetadata::for_reader(reader).map_err(Error::Png)?;
+            return Ok(Box::new(png));
+        }
+
+        #[cfg(feature = "tiff")]
+        if gufo_tiff::Tiff::is_filetype(&magic_bytes) {
+            let tiff = gufo_tiff::TiffMetadata::for_reader(reader).map_err(Error::Tiff)?;
+            return Ok(Box::new(tiff));
+        }
+
+        #[cfg(feature = "webp")]
+        if gufo_webp::WebP::is_filetype(&magic_bytes) {
+            let webp = gufo_webp::WebPMetadata::for_reader(reader).map_err(Error::WebP)?;
+            return Ok(Box::new(webp));
+        }
+
+        Err(Error::NoSupportedFiletypeFound)
+    }
+
     pub fn into_inner(self) -> Vec<u8> {
         match self {
             #[cfg(feature = "jpeg")]
@@ -1,10 +1,14 @@
 mod high_level;
 mod image;
+mod reconcile;
+pub mod sync;
 
 use std::collections::BTreeMap;
+use std::io::{Read, Seek};
 
 pub use gufo_common as common;
 use gufo_common::error::ErrorWithData;
+use gufo_common::exif::TagIfd;
 use gufo_common::prelude::*;
 use gufo_exif::ExifOwned;
 #[cfg(feature = "jpeg")]
@@ -17,6 +21,7 @@ pub use gufo_tiff as tiff;
 pub use gufo_webp as webp;
 use gufo_xmp::Xmp;
 pub use image::Image;
+pub use reconcile::{DigestStatus, ReconcilePolicy, Reconciled, Reconciler, Source};
 
 #[derive(Debug, Default)]
 pub struct RawMetadata {
@@ -33,6 +38,20 @@ impl RawMetadata {
         Ok((Self::load(*image.dyn_metadata()), image.into_inner()))
     }
 
+    /// Metadata from `reader` without reading the image data
+    ///
+    /// Returns the same metadata as [`for_guessed`](Self::for_guessed). Only
+    /// the container structure and the metadata of JPEG, PNG, TIFF, and WebP
+    /// images are read. The entropy-coded JPEG data, PNG `IDAT` chunks, WebP
+    /// bitstreams, and TIFF strips and tiles are skipped. Other formats return
+    /// [`Error::NoSupportedFiletypeFound`] without being read further.
+    #[cfg(any(feature = "jpeg", feature = "png", feature = "tiff", feature = "webp"))]
+    pub fn for_reader(reader: impl Read + Seek) -> Result<Self, Error> {
+        let metadata = Image::metadata_for_reader(reader)?;
+
+        Ok(Self::load(metadata.as_ref()))
+    }
+
     fn load(metadata: &dyn ImageMetadata) -> Self {
         let mut raw_metadata = Self::default();
 
@@ -105,6 +124,8 @@ pub enum Error {
     Exif(gufo_exif::Error),
     #[error("XMP: {0}")]
     Xmp(gufo_xmp::Error),
+    #[error("IO: {0}")]
+    Io(std::io::Error),
 
     #[cfg(feature = "jpeg")]
     #[error("JPEG: {0}")]
@@ -137,6 +158,14 @@ impl Metadata {
         RawMetadata::for_guessed(data).map(|x| x.0.into_metadata())
     }
 
+    /// Metadata from `reader` without reading the image data
+    ///
+    /// See [`RawMetadata::for_reader`].
+    #[cfg(any(feature = "jpeg", feature = "png", feature = "tiff", feature = "webp"))]
+    pub fn for_reader(reader: impl Read + Seek) -> Result<Self, Error> {
+        RawMetadata::for_reader(reader).map(RawMetadata::into_metadata)
+    }
+
     #[cfg(feature = "jpeg")]
     pub fn for_jpeg(jpeg: &gufo_jpeg::Jpeg) -> Self {
         RawMetadata::for_jpeg(jpeg).into_metadata()
@@ -193,23 +222,25 @@ impl Metadata {
         self.xmp.iter().find_map(xmp_op)
     }
 
+    /// Value of `field` reconciled via the [default policy](ReconcilePolicy)
     fn lookup_exif_xmp<T>(
         &self,
+        field: impl Into<TagIfd>,
         exif_op: impl Fn(&ExifOwned) -> Option<T>,
         xmp_op: impl Fn(&Xmp) -> Option<T>,
     ) -> Option<T> {
-        self.lookup_exif(exif_op)
-            .or_else(|| self.lookup_xmp(xmp_op))
+        self.lookup_exif_xmp_keyval(field, exif_op, xmp_op, |_| None)
     }
 
     fn lookup_exif_xmp_keyval<T>(
         &self,
+        field: impl Into<TagIfd>,
         exif_op: impl Fn(&ExifOwned) -> Option<T>,
         xmp_op: impl Fn(&Xmp) -> Option<T>,
         keyval_op: impl Fn(&BTreeMap<String, String>) -> Option<T>,
     ) -> Option<T> {
-        self.lookup_exif(exif_op)
-            .or_else(|| self.lookup_xmp(xmp_op))
-            .or_else(|| self.lookup_keyval(keyval_op))
+        self.reconcile(ReconcilePolicy::default())
+            .lookup_field(field.into(), exif_op, xmp_op, keyval_op)
+            .map(|x| x.value)
     }
 }
@@ -0,0 +1,288 @@
+//! Reconciliation of Exif and XMP values
+//!
+//! Some values are stored in Exif and in XMP. This module decides which value
+//! is used if both exist, following the guidelines of the Metadata Working
+//! Group (MWG) by default.
+
+use std::collections::BTreeMap;
+
+use gufo_common::exif::{IfdId, TagIfd};
+use gufo_common::field;
+use gufo_exif::{Exif, ExifOwned};
+use gufo_xmp::{Value, Xmp};
+
+use crate::Metadata;
+
+/// Separator between multiple names in [`Artist`](field::Artist)
+const ARTIST_SEPARATOR: &str = "; ";
+
+/// Which source is used if Exif and XMP both contain a value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ReconcilePolicy {
+    /// Follow the guidelines of the Metadata Working Group
+    ///
+    /// XMP is preferred if the native digest in the XMP confirms that the Exif
+    /// data didn't change since the XMP was written. In that case, differing
+    /// values can only come from an editor that updated the XMP and left the
+    /// Exif data stale. Otherwise, Exif is preferred since it has either been
+    /// changed by a tool that doesn't maintain the XMP or there is no way to
+    /// tell.
+    ///
+    /// The digest is only verified with the `mwg` feature. Without it, Exif is
+    /// always preferred.
+    #[default]
+    Mwg,
+    /// Always prefer Exif
+    PreferExif,
+    /// Always prefer XMP
+    PreferXmp,
+}
+
+/// Origin of a reconciled value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Source {
+    Exif,
+    Xmp,
+    /// Key-value metadata, like PNG text chunks
+    KeyValue,
+}
+
+/// Value with the source it has been taken from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reconciled<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+/// Comparison of a native digest stored in the XMP with the Exif data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigestStatus {
+    /// The XMP doesn't contain a digest
+    Missing,
+    /// The Exif data didn't change since the XMP was written
+    Matches,
+    /// The Exif data were changed, removed, or the digest can't be read
+    ///
+    /// Always the case for existing digests without the `mwg` feature.
+    Differs,
+}
+
+/// Access to values of [`Metadata`] that are reconciled according to a
+/// [`ReconcilePolicy`]
+///
+/// Created via [`Metadata::reconcile`].
+#[derive(Debug, Clone, Copy)]
+pub struct Reconciler<'a> {
+    metadata: &'a Metadata,
+    policy: ReconcilePolicy,
+}
+
+impl Metadata {
+    /// Values from Exif and XMP reconciled according to `policy`
+    ///
+    /// ```
+    /// use gufo::{ReconcilePolicy, Source};
+    ///
+    /// let mut metadata = gufo::Metadata::new();
+    /// metadata
+    ///     .add_raw_xmp(
+    ///         br#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+    ///           <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    ///             <rdf:Description xmlns:dc="http://purl.org/dc/elements/1.1/">
+    ///               <dc:rights>Gufo</dc:rights>
+    ///             </rdf:Description>
+    ///           </rdf:RDF>
+    ///         </x:xmpmeta>"#
+    ///             .to_vec(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let rights = metadata.reconcile(ReconcilePolicy::Mwg).rights().unwrap();
+    /// assert_eq!(rights.value, "Gufo");
+    /// assert_eq!(rights.source, Source::Xmp);
+    /// ```
+    pub fn reconcile(&self, policy: ReconcilePolicy) -> Reconciler<'_> {
+        Reconciler {
+            metadata: self,
+            policy,
+        }
+    }
+}
+
+impl Reconciler<'_> {
+    pub fn policy(&self) -> ReconcilePolicy {
+        self.policy
+    }
+
+    /// Status of `tiff:NativeDigest`, covering tags of the primary IFD
+    pub fn tiff_digest(&self) -> DigestStatus {
+        self.digest(field::TiffNativeDigest, IfdId::Primary)
+    }
+
+    /// Status of `exif:NativeDigest`, covering tags of the Exif IFD
+    pub fn exif_digest(&self) -> DigestStatus {
+        self.digest(field::ExifNativeDigest, IfdId::Exif)
+    }
+
+    /// Name of the main person who created the image
+    ///
+    /// Reconciles [`Artist`](field::Artist) and [`Creator`](field::Creator).
+    /// Multiple creators from XMP are joined with `"; "`, like in Exif.
+    pub fn creator(&self) -> Option<Reconciled<String>> {
+        self.lookup(Self::tiff_digest, Exif::artist, xmp_creator, |x| {
+            x.get("Author").cloned()
+        })
+    }
+
+    /// Copyright information
+    ///
+    /// Reconciles [`Copyright`](field::Copyright) and
+    /// [`Rights`](field::Rights).
+    pub fn rights(&self) -> Option<Reconciled<String>> {
+        self.lookup(Self::tiff_digest, Exif::copyright, Xmp::rights, |x| {
+            x.get("Copyright").cloned()
+        })
+    }
+
+    /// Date and time the file was changed
+    ///
+    /// Reconciles [`DateTime`](field::DateTime) and
+    /// [`ModifyDate`](field::ModifyDate).
+    #[cfg(feature = "chrono")]
+    pub fn modify_date(&self) -> Option<Reconciled<gufo_common::datetime::DateTime>> {
+        self.lookup(
+            Self::tiff_digest,
+            Exif::date_time,
+            |x| x.get_date_time(field::ModifyDate),
+            |_| None,
+        )
+    }
+
+    /// Value of `field` from the source preferred by the policy
+    ///
+    /// The digest is chosen by the IFD of the field. Fields outside of the
+    /// primary and the Exif IFD aren't covered by a digest.
+    pub(crate) fn lookup_field<T>(
+        &self,
+        field: TagIfd,
+        exif_op: impl Fn(&ExifOwned) -> Option<T>,
+        xmp_op: impl Fn(&Xmp) -> Option<T>,
+        keyval_op: impl Fn(&BTreeMap<String, String>) -> Option<T>,
+    ) -> Option<Reconciled<T>> {
+        let digest = |reconciler: &Self| match field.ifd {
+            IfdId::Primary => reconciler.tiff_digest(),
+            IfdId::Exif => reconciler.exif_digest(),
+            _ => DigestStatus::Missing,
+        };
+
+        self.lookup(digest, exif_op, xmp_op, keyval_op)
+    }
+
+    /// Value from the source preferred by the policy
+    ///
+    /// The digest is only checked if Exif and XMP both contain a value.
+    /// Key-value metadata are only used if neither does.
+    fn lookup<T>(
+        &self,
+        digest: impl FnOnce(&Self) -> DigestStatus,
+        exif_op: impl Fn(&ExifOwned) -> Option<T>,
+        xmp_op: impl Fn(&Xmp) -> Option<T>,
+        keyval_op: impl Fn(&BTreeMap<String, String>) -> Option<T>,
+    ) -> Option<Reconciled<T>> {
+        let exif = self.metadata.lookup_exif(exif_op).map(|value| Reconciled {
+            value,
+            source: Source::Exif,
+        });
+        let xmp = self.metadata.lookup_xmp(xmp_op).map(|value| Reconciled {
+            value,
+            source: Source::Xmp,
+        });
+
+        let reconciled = match (exif, xmp) {
+            (Some(exif), Some(xmp)) => {
+                if self.prefers_xmp(digest(self)) {
+                    Some(xmp)
+                } else {
+                    Some(exif)
+                }
+            }
+            (exif, xmp) => exif.or(xmp),
+        };
+
+        reconciled.or_else(|| {
+            self.metadata
+                .lookup_keyval(keyval_op)
+                .map(|value| Reconciled {
+                    value,
+                    source: Source::KeyValue,
+                })
+        })
+    }
+
+    fn prefers_xmp(&self, digest: DigestStatus) -> bool {
+        match self.policy {
+            ReconcilePolicy::Mwg => digest == DigestStatus::Matches,
+            ReconcilePolicy::PreferExif => false,
+            ReconcilePolicy::PreferXmp => true,
+        }
+    }
+
+    fn digest(&self, tag: impl Into<gufo_xmp::Tag>, ifd: IfdId) -> DigestStatus {
+        let tag = tag.into();
+        let Some(stored) = self
+            .metadata
+            .lookup_xmp(|x| x.lookup_generic(tag.clone()).map(ToString::to_string))
+        else {
+            return DigestStatus::Missing;
+        };
+
+        let matches = self
+            .metadata
+            .exif
+            .first()
+            .and_then(|exif| native_digest(exif, &stored, ifd))
+            .is_some_and(|x| x.eq_ignore_ascii_case(&stored));
+
+        if matches {
+            DigestStatus::Matches
+        } else {
+            DigestStatus::Differs
+        }
+    }
+}
+
+/// Digest as written by the XMP SDK
+///
+/// The format is a list of tags followed by the MD5 sum of their raw values,
+/// like `"256,257,…;0123…"`. The tags listed in `stored` are used such that
+/// the result is comparable to `stored`.
+#[cfg(feature = "mwg")]
+fn native_digest(exif: &ExifOwned, stored: &str, ifd: IfdId) -> Option<String> {
+    let (tags, _) = stored.split_once(';')?;
+    let mut context = md5::Context::new();
+
+    let exif = exif.view();
+    for tag in tags.split(',') {
+        let tag_ifd = TagIfd::new(gufo_common::exif::Tag(tag.trim().parse().ok()?), ifd);
+        if let Ok(Some(entry)) = exif.entry_data(tag_ifd) {
+            context.consume(entry.data);
+        }
+    }
+
+    Some(format!("{tags};{:X}", context.finalize()))
+}
+
+#[cfg(not(feature = "mwg"))]
+fn native_digest(_exif: &ExifOwned, _stored: &str, _ifd: IfdId) -> Option<String> {
+    None
+}
+
+/// Creators joined like in [`Artist`](field::Artist)
+fn xmp_creator(xmp: &Xmp) -> Option<String> {
+    let creator = match xmp.entries().get(&field::Creator.into())? {
+        Value::Generic(x) => x.clone(),
+        Value::Seq(x) | Value::Bag(x) => x.join(ARTIST_SEPARATOR),
+    };
+
+    (!creator.is_empty()).then_some(creator)
+}
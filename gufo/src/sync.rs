@@ -0,0 +1,489 @@
+//! Synchronization of Exif and XMP
+//!
+//! Many Exif tags have an XMP property as equivalent. The functions in this
+//! module convert changes in one format into updates for the other one, such
+//! that both don't disagree after an edit. [`Metadata::update_exif`] and
+//! [`Metadata::update_xmp`] apply the changes to both formats.
+//!
+//! Some tags are not synchronized:
+//!
+//! - GPS coordinates and time stamps, since XMP combines them with other
+//!   entries.
+//! - [`Copyright`](field::Copyright), since `dc:rights` is a language
+//!   alternative.
+//! - Binary data of type undefined, except for versions and scene information.
+//!
+//! Subseconds and offsets are part of the date in XMP. If they change without
+//! the date, [`Metadata::update_exif`] writes the XMP date anew from the
+//! updated Exif data. [`exif_to_xmp`] on its own can't do this since it only
+//! knows the changes.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use gufo_common::exif::{Count, TagIfd, Type, lookup_tag_info, lookup_xmp_namespace, tag_infos};
+use gufo_common::field;
+use gufo_common::types::Rational;
+use gufo_exif::structure::SerializeMode;
+use gufo_exif::{ExifOwned, Lookup, Typed};
+use gufo_xmp::{Tag, Value};
+
+use crate::{Error, Metadata};
+
+/// Separator between multiple names in [`Artist`](field::Artist)
+const ARTIST_SEPARATOR: char = ';';
+
+impl Metadata {
+    /// Change Exif entries and the corresponding XMP properties
+    ///
+    /// ```
+    /// use gufo_common::field;
+    /// use gufo_exif::Typed;
+    ///
+    /// let raw_exif = gufo_exif::ExifBuilder::new(gufo_exif::structure::Endieness::Big)
+    ///     .entry(field::Orientation, Typed::Short(vec![1]))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let mut metadata = gufo::Metadata::new();
+    /// metadata.add_raw_exif(raw_exif).unwrap();
+    /// metadata
+    ///     .add_raw_xmp(
+    ///         br#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+    ///           <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    ///             <rdf:Description xmlns:tiff="http://ns.adobe.com/tiff/1.0/">
+    ///               <tiff:Orientation>1</tiff:Orientation>
+    ///             </rdf:Description>
+    ///           </rdf:RDF>
+    ///         </x:xmpmeta>"#
+    ///             .to_vec(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// metadata
+    ///     .update_exif(&[(field::Orientation.into(), Typed::Short(vec![6]))])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     metadata.exif()[0].orientation(),
+    ///     metadata.xmp()[0].orientation()
+    /// );
+    /// ```
+    ///
+    /// The changes are applied to all Exif and XMP blocks. Exif data are
+    /// rebuilt via [`SerializeMode::Rebuild`] if the changes don't fit in
+    /// place. The serialize mode of the Exif data stays unchanged.
+    pub fn update_exif(&mut self, changes: &[(TagIfd, Typed)]) -> Result<(), Error> {
+        for exif in &mut self.exif {
+            for (tag_ifd, value) in changes {
+                update_entry(exif, *tag_ifd, value)?;
+            }
+        }
+
+        let mut updates = exif_to_xmp(changes);
+        if let Some(exif) = self.exif.first() {
+            updates.extend(companion_updates(exif, changes));
+        }
+        if !updates.is_empty() {
+            for xmp in &mut self.xmp {
+                xmp.update(updates.clone()).map_err(Error::Xmp)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Change XMP properties and the corresponding Exif entries
+    ///
+    /// The changes are applied to all XMP and Exif blocks. Like for
+    /// [`update_exif`](Self::update_exif), Exif data are rebuilt if needed.
+    pub fn update_xmp(&mut self, changes: BTreeMap<Tag, Value>) -> Result<(), Error> {
+        let updates = xmp_to_exif(&changes);
+
+        for xmp in &mut self.xmp {
+            xmp.update(changes.clone()).map_err(Error::Xmp)?;
+        }
+
+        for exif in &mut self.exif {
+            for (tag_ifd, value) in &updates {
+                update_entry(exif, *tag_ifd, value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Update the entry and rebuild the structure if it doesn't fit
+///
+/// The serialize mode of `exif` is restored afterwards.
+fn update_entry(exif: &mut ExifOwned, tag_ifd: TagIfd, value: &Typed) -> Result<(), Error> {
+    match exif.update_entry(tag_ifd, value.clone()) {
+        Err(gufo_exif::Error::WouldIncreaseDataStore) => {
+            let mode = exif.serialize_mode();
+            exif.set_serialize_mode(SerializeMode::Rebuild);
+            let result = exif.update_entry(tag_ifd, value.clone());
+            exif.set_serialize_mode(mode);
+            result
+        }
+        result => result,
+    }
+    .map_err(Error::Exif)
+}
+
+/// XMP property corresponding to the Exif entry `tag_ifd`
+pub fn xmp_tag(tag_ifd: TagIfd) -> Option<Tag> {
+    if let Some((_, tag)) = renamed().into_iter().find(|x| x.0 == tag_ifd) {
+        return Some(tag);
+    }
+
+    let namespace = lookup_xmp_namespace(tag_ifd)?;
+    let name = lookup_tag_info(tag_ifd)?.name;
+
+    Some(Tag::new(namespace.clone(), name.to_string()))
+}
+
+/// Exif entry corresponding to the XMP property `tag`
+pub fn exif_tag(tag: &Tag) -> Option<TagIfd> {
+    if let Some((tag_ifd, _)) = renamed().into_iter().find(|x| x.1 == *tag) {
+        return Some(tag_ifd);
+    }
+
+    tag_infos()
+        .filter(|x| x.name == tag.name())
+        .map(|x| TagIfd::new(x.tag, x.ifd))
+        .find(|x| lookup_xmp_namespace(*x) == Some(tag.namespace()))
+}
+
+/// XMP updates that correspond to the Exif `changes`
+///
+/// Dates are combined with their subseconds and offsets if these are
+/// contained in `changes` as well.
+pub fn exif_to_xmp(changes: &[(TagIfd, Typed)]) -> BTreeMap<Tag, Value> {
+    let companions = date_tags()
+        .into_iter()
+        .flat_map(|(_, subsec, offset)| [subsec, offset])
+        .collect::<BTreeSet<_>>();
+    let change = |tag_ifd: TagIfd| {
+        changes
+            .iter()
+            .find(|x| x.0 == tag_ifd)
+            .and_then(|x| text(&x.1))
+    };
+
+    let mut updates = BTreeMap::new();
+
+    for (tag_ifd, value) in changes {
+        if companions.contains(tag_ifd) || !is_synchronized(*tag_ifd) {
+            continue;
+        }
+        let Some(tag) = xmp_tag(*tag_ifd) else {
+            continue;
+        };
+
+        let value = match date_tags().into_iter().find(|x| x.0 == *tag_ifd) {
+            Some((_, subsec, offset)) => text(value)
+                .and_then(|x| exif_date_to_xmp(&x, change(subsec), change(offset)))
+                .map(Value::Generic),
+            None => exif_value_to_xmp(*tag_ifd, value),
+        };
+
+        if let Some(value) = value {
+            updates.insert(tag, value);
+        }
+    }
+
+    updates
+}
+
+/// XMP dates for changed subseconds or offsets whose date didn't change
+///
+/// The date is taken from `exif`, which already contains the changes.
+fn companion_updates(exif: &ExifOwned, changes: &[(TagIfd, Typed)]) -> BTreeMap<Tag, Value> {
+    let changed = |tag_ifd: TagIfd| changes.iter().any(|x| x.0 == tag_ifd);
+    let lookup = |tag_ifd: TagIfd| exif.view().lookup_string(tag_ifd).ok().flatten();
+
+    let mut updates = BTreeMap::new();
+
+    for (date, subsec, offset) in date_tags() {
+        if changed(date) || !(changed(subsec) || changed(offset)) {
+            continue;
+        }
+        let Some(tag) = xmp_tag(date) else {
+            continue;
+        };
+
+        if let Some(value) =
+            lookup(date).and_then(|x| exif_date_to_xmp(&x, lookup(subsec), lookup(offset)))
+        {
+            updates.insert(tag, Value::Generic(value));
+        }
+    }
+
+    updates
+}
+
+/// Exif changes that correspond to the XMP `changes`
+///
+/// Dates are split into the date, subseconds, and offset. Subseconds and
+/// offsets are only included if the XMP date contains them.
+pub fn xmp_to_exif(changes: &BTreeMap<Tag, Value>) -> Vec<(TagIfd, Typed)> {
+    let mut updates = Vec::new();
+
+    for (tag, value) in changes {
+        let Some(tag_ifd) = exif_tag(tag) else {
+            continue;
+        };
+        if !is_synchronized(tag_ifd) {
+            continue;
+        }
+
+        let items = match value {
+            Value::Generic(x) => vec![x.clone()],
+            Value::Bag(x) | Value::Seq(x) => x.clone(),
+        };
+
+        if let Some((_, subsec, offset)) = date_tags().into_iter().find(|x| x.0 == tag_ifd) {
+            let Some((date, subsec_value, offset_value)) =
+                items.first().and_then(|x| xmp_date_to_exif(x))
+            else {
+                continue;
+            };
+
+            updates.push((tag_ifd, Typed::Ascii(date.into_bytes())));
+            if let Some(subsec_value) = subsec_value {
+                updates.push((subsec, Typed::Ascii(subsec_value.into_bytes())));
+            }
+            if let Some(offset_value) = offset_value {
+                updates.push((offset, Typed::Ascii(offset_value.into_bytes())));
+            }
+        } else if let Some(value) = xmp_value_to_exif(tag_ifd, &items) {
+            updates.push((tag_ifd, value));
+        }
+    }
+
+    updates
+}
+
+/// Tags whose XMP equivalent has a different name
+fn renamed() -> [(TagIfd, Tag); 3] {
+    [
+        (field::DateTime.into(), field::ModifyDate.into()),
+        (field::Software.into(), field::CreatorTool.into()),
+        (field::Artist.into(), field::Creator.into()),
+    ]
+}
+
+/// Dates with their subseconds and offsets
+fn date_tags() -> [(TagIfd, TagIfd, TagIfd); 3] {
+    [
+        (
+            field::DateTime.into(),
+            field::SubSecTime.into(),
+            field::OffsetTime.into(),
+        ),
+        (
+            field::DateTimeOriginal.into(),
+            field::SubSecTimeOriginal.into(),
+            field::OffsetTimeOriginal.into(),
+        ),
+        (
+            field::DateTimeDigitized.into(),
+            field::SubsecTimeDigitized.into(),
+            field::OffsetTimeDigitized.into(),
+        ),
+    ]
+}
+
+/// Returns `false` for tags that XMP combines with other entries
+fn is_synchronized(tag_ifd: TagIfd) -> bool {
+    let combined: [TagIfd; 6] = [
+        field::GPSVersionID.into(),
+        field::GPSLatitude.into(),
+        field::GPSLongitude.into(),
+        field::GPSTimeStamp.into(),
+        field::GPSDestLatitude.into(),
+        field::GPSDestLongitude.into(),
+    ];
+
+    !combined.contains(&tag_ifd)
+}
+
+/// Undefined values that are stored as text, like `"0232"`
+fn is_version(tag_ifd: TagIfd) -> bool {
+    tag_ifd == field::ExifVersion.into() || tag_ifd == field::FlashpixVersion.into()
+}
+
+/// Undefined values that are stored as numbers
+fn is_undefined_numbers(tag_ifd: TagIfd) -> bool {
+    tag_ifd == field::ComponentsConfiguration.into()
+        || tag_ifd == field::FileSource.into()
+        || tag_ifd == field::SceneType.into()
+}
+
+/// Text without the terminating NUL
+fn text(value: &Typed) -> Option<String> {
+    match value {
+        Typed::Ascii(x) => String::from_utf8(x.clone()).ok(),
+        Typed::Utf8(x) => Some(x.clone()),
+        _ => None,
+    }
+    .map(|x| x.trim_end_matches('\0').to_string())
+}
+
+fn exif_value_to_xmp(tag_ifd: TagIfd, value: &Typed) -> Option<Value> {
+    fn strings<T: ToString>(values: &[T]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    let items = match value {
+        Typed::Ascii(_) | Typed::Utf8(_) => {
+            let text = text(value)?;
+            if tag_ifd == field::Artist.into() {
+                return Some(Value::Seq(
+                    text.split(ARTIST_SEPARATOR)
+                        .map(|x| x.trim().to_string())
+                        .filter(|x| !x.is_empty())
+                        .collect(),
+                ));
+            }
+            return Some(Value::Generic(text));
+        }
+        Typed::Undefined(x) if is_version(tag_ifd) => {
+            return String::from_utf8(x.clone()).ok().map(Value::Generic);
+        }
+        Typed::Undefined(x) if is_undefined_numbers(tag_ifd) => strings(x),
+        Typed::Byte(x) => strings(x),
+        Typed::Short(x) => strings(x),
+        Typed::Long(x) => strings(x),
+        Typed::Long8(x) => strings(x),
+        Typed::SLong(x) => strings(x),
+        Typed::Rational(x) => x
+            .iter()
+            .map(|x| format!("{}/{}", x.numerator, x.denominator))
+            .collect(),
+        Typed::SRational(x) => x
+            .iter()
+            .map(|x| format!("{}/{}", x.numerator, x.denominator))
+            .collect(),
+        Typed::Undefined(_) | Typed::Unknown(..) => return None,
+    };
+
+    let single = lookup_tag_info(tag_ifd).is_some_and(|x| x.count == Count::Fixed(1));
+    match items.as_slice() {
+        [item] if single => Some(Value::Generic(item.clone())),
+        _ if single => None,
+        _ => Some(Value::Seq(items)),
+    }
+}
+
+fn xmp_value_to_exif(tag_ifd: TagIfd, items: &[String]) -> Option<Typed> {
+    fn parse<T: std::str::FromStr>(items: &[String]) -> Option<Vec<T>> {
+        items.iter().map(|x| x.trim().parse().ok()).collect()
+    }
+
+    fn rationals<T: std::str::FromStr + From<u8>>(items: &[String]) -> Option<Vec<Rational<T>>> {
+        items
+            .iter()
+            .map(|x| match x.trim().split_once('/') {
+                Some((numerator, denominator)) => Some(Rational::new(
+                    numerator.parse().ok()?,
+                    denominator.parse().ok()?,
+                )),
+                None => Some(Rational::new(x.trim().parse().ok()?, T::from(1))),
+            })
+            .collect()
+    }
+
+    let types = lookup_tag_info(tag_ifd)?.types;
+
+    if tag_ifd == field::Artist.into() {
+        let separator = format!("{ARTIST_SEPARATOR} ");
+        return Some(Typed::Ascii(items.join(&separator).into_bytes()));
+    }
+
+    match types.first()? {
+        Type::Ascii | Type::Utf8 => {
+            let text = items.join(" ");
+            if !text.is_ascii() && types.contains(&Type::Utf8) {
+                Some(Typed::Utf8(text))
+            } else {
+                Some(Typed::Ascii(text.into_bytes()))
+            }
+        }
+        Type::Undefined if is_version(tag_ifd) => {
+            Some(Typed::Undefined(items.first()?.as_bytes().to_vec()))
+        }
+        Type::Undefined if is_undefined_numbers(tag_ifd) => parse(items).map(Typed::Undefined),
+        Type::Byte => parse(items).map(Typed::Byte),
+        Type::Short => parse(items).map(Typed::Short).or_else(|| {
+            types
+                .contains(&Type::Long)
+                .then(|| parse(items).map(Typed::Long))
+                .flatten()
+        }),
+        Type::Long => parse(items).map(Typed::Long),
+        Type::Long8 => parse(items).map(Typed::Long8),
+        Type::SLong => parse(items).map(Typed::SLong),
+        Type::Rational => rationals(items).map(Typed::Rational),
+        Type::SRational => rationals(items).map(Typed::SRational),
+        Type::Undefined
+        | Type::Ifd
+        | Type::Ifd8
+        | Type::SByte
+        | Type::SShort
+        | Type::Float
+        | Type::Double
+        | Type::SLong8
+        | Type::Unknown(_) => None,
+    }
+}
+
+/// Convert `YYYY:MM:DD HH:MM:SS` to ISO 8601
+fn exif_date_to_xmp(date: &str, subsec: Option<String>, offset: Option<String>) -> Option<String> {
+    let (date, time) = date.trim().split_once(' ')?;
+    if date.len() != 10 || !date.chars().all(|x| x.is_ascii_digit() || x == ':') {
+        return None;
+    }
+
+    let mut xmp = format!("{}T{time}", date.replace(':', "-"));
+    if let Some(subsec) = subsec.filter(|x| !x.trim().is_empty()) {
+        xmp.push('.');
+        xmp.push_str(subsec.trim());
+    }
+    if let Some(offset) = offset.filter(|x| !x.trim().is_empty()) {
+        xmp.push_str(offset.trim());
+    }
+
+    Some(xmp)
+}
+
+/// Convert an ISO 8601 date into the Exif date, subseconds, and offset
+fn xmp_date_to_exif(date: &str) -> Option<(String, Option<String>, Option<String>)> {
+    let (date, time) = date.trim().split_once('T').unwrap_or((date, "00:00:00"));
+    if date.len() != 10 {
+        return None;
+    }
+
+    let (time, offset) = if let Some(time) = time.strip_suffix('Z') {
+        (time, Some(String::from("+00:00")))
+    } else if let Some(pos) = time.rfind(['+', '-']) {
+        let (time, offset) = time.split_at(pos);
+        (time, Some(offset.to_string()))
+    } else {
+        (time, None)
+    };
+
+    let (time, subsec) = match time.split_once('.') {
+        Some((time, subsec)) => (time, Some(subsec.to_string())),
+        None => (time, None),
+    };
+
+    // Seconds are optional in XMP
+    let time = if time.len() == 5 {
+        format!("{time}:00")
+    } else {
+        time.to_string()
+    };
+
+    Some((format!("{} {time}", date.replace('-', ":")), subsec, offset))
+}
@@ -1,3 +1,4 @@
+use gufo_common::math::cheq;
 pub use gufo_common::orientation::Orientation;
 use gufo_common::types::Rational;
 
@@ -93,6 +94,448 @@ pub fn nikon_d5100() {
     );
 }
 
+#[test]
+pub fn reconcile_mwg() {
+    use gufo::{DigestStatus, ReconcilePolicy, Source};
+    use gufo_common::field;
+    use gufo_exif::structure::Endieness;
+    use gufo_exif::{ExifBuilder, Typed};
+
+    let raw_exif = ExifBuilder::new(Endieness::Litte)
+        .entry(
+            field::DateTime,
+            Typed::Ascii(b"2024:01:01 10:00:00".to_vec()),
+        )
+        .entry(field::Software, Typed::Ascii(b"Gufo 1.0".to_vec()))
+        .entry(field::Artist, Typed::Ascii(b"Gufo Exif".to_vec()))
+        .entry(field::Copyright, Typed::Ascii(b"Copyright Exif".to_vec()))
+        .build()
+        .unwrap();
+
+    let metadata = |digest: &str| {
+        let xmp = format!(
+            r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description
+        xmlns:dc="http://purl.org/dc/elements/1.1/"
+        xmlns:tiff="http://ns.adobe.com/tiff/1.0/"
+        xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+        {digest}>
+      <xmp:ModifyDate>2024-06-01T12:00:00+02:00</xmp:ModifyDate>
+      <xmp:CreatorTool>Gufo 2.0</xmp:CreatorTool>
+      <dc:rights>
+        <rdf:Alt>
+          <rdf:li xml:lang="x-default">Copyright XMP</rdf:li>
+        </rdf:Alt>
+      </dc:rights>
+      <dc:creator>
+        <rdf:Seq>
+          <rdf:li>Gufo XMP</rdf:li>
+          <rdf:li>Owl XMP</rdf:li>
+        </rdf:Seq>
+      </dc:creator>
+    </rdf:Description>
+  </rdf:RDF>
+</x:xmpmeta>"#
+        );
+
+        let mut metadata = gufo::Metadata::new();
+        metadata.add_raw_exif(raw_exif.clone()).unwrap();
+        metadata.add_raw_xmp(xmp.into_bytes()).unwrap();
+        metadata
+    };
+
+    // Without digest, Exif is preferred
+    let metadata_no_digest = metadata("");
+    let reconciler = metadata_no_digest.reconcile(ReconcilePolicy::Mwg);
+    assert_eq!(reconciler.tiff_digest(), DigestStatus::Missing);
+    let creator = reconciler.creator().unwrap();
+    assert_eq!(creator.value, "Gufo Exif");
+    assert_eq!(creator.source, Source::Exif);
+    assert_eq!(
+        metadata_no_digest.rights().as_deref(),
+        Some("Copyright Exif")
+    );
+    assert_eq!(metadata_no_digest.software().as_deref(), Some("Gufo 1.0"));
+
+    let reconciler = metadata_no_digest.reconcile(ReconcilePolicy::PreferXmp);
+    let creator = reconciler.creator().unwrap();
+    assert_eq!(creator.value, "Gufo XMP; Owl XMP");
+    assert_eq!(creator.source, Source::Xmp);
+
+    // Exif didn't change since the XMP was written, XMP is more recent
+    //
+    // The XMP SDK computes the digest as the MD5 sum of the raw values of the
+    // listed tags, including the terminating zero of strings. Tags that don't
+    // exist, like 270, are skipped. The expected sum is computed from the
+    // values in the order listed:
+    // `md5("2024:01:01 10:00:00\0Gufo 1.0\0Gufo Exif\0Copyright Exif\0")`
+    let metadata_matching =
+        metadata(r#"tiff:NativeDigest="306,305,315,33432,270;05D6E2282AE0A3E722680A54B43EB1D3""#);
+    let reconciler = metadata_matching.reconcile(ReconcilePolicy::Mwg);
+    assert_eq!(reconciler.tiff_digest(), DigestStatus::Matches);
+    assert_eq!(reconciler.exif_digest(), DigestStatus::Missing);
+    let rights = reconciler.rights().unwrap();
+    assert_eq!(rights.value, "Copyright XMP");
+    assert_eq!(rights.source, Source::Xmp);
+    let modify_date = reconciler.modify_date().unwrap();
+    assert_eq!(modify_date.value.to_string(), "2024-06-01 12:00:00 +02:00");
+    assert_eq!(modify_date.source, Source::Xmp);
+    assert_eq!(
+        metadata_matching.creator().as_deref(),
+        Some("Gufo XMP; Owl XMP")
+    );
+    // Other values are reconciled the same way
+    assert_eq!(metadata_matching.software().as_deref(), Some("Gufo 2.0"));
+
+    let reconciler = metadata_matching.reconcile(ReconcilePolicy::PreferExif);
+    assert_eq!(reconciler.rights().unwrap().source, Source::Exif);
+
+    // Exif was changed by a tool that doesn't update the XMP
+    let metadata_differing =
+        metadata(r#"tiff:NativeDigest="306,305,315,33432,270;00000000000000000000000000000000""#);
+    let reconciler = metadata_differing.reconcile(ReconcilePolicy::Mwg);
+    assert_eq!(reconciler.tiff_digest(), DigestStatus::Differs);
+    let modify_date = reconciler.modify_date().unwrap();
+    assert_eq!(modify_date.value.to_string(), "2024-01-01 10:00:00");
+    assert_eq!(modify_date.source, Source::Exif);
+}
+
+#[test]
+pub fn sync_exif_xmp() {
+    use std::collections::BTreeMap;
+
+    use gufo_common::field;
+    use gufo_exif::structure::{Endieness, SerializeMode};
+    use gufo_exif::{ExifBuilder, Typed};
+    use gufo_xmp::Value;
+
+    let raw_exif = ExifBuilder::new(Endieness::Big)
+        .entry(field::Orientation, Typed::Short(vec![1]))
+        .entry(field::Artist, Typed::Ascii(b"Gufo".to_vec()))
+        .entry(field::ExifVersion, Typed::Undefined(b"0232".to_vec()))
+        .build()
+        .unwrap();
+
+    let xmp = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description
+        xmlns:dc="http://purl.org/dc/elements/1.1/"
+        xmlns:tiff="http://ns.adobe.com/tiff/1.0/"
+        xmlns:xmp="http://ns.adobe.com/xap/1.0/">
+      <dc:creator>
+        <rdf:Seq>
+          <rdf:li>Gufo</rdf:li>
+        </rdf:Seq>
+      </dc:creator>
+      <tiff:Orientation>1</tiff:Orientation>
+      <xmp:CreatorTool>Gufo</xmp:CreatorTool>
+    </rdf:Description>
+  </rdf:RDF>
+</x:xmpmeta>"#;
+
+    let mut metadata = gufo::Metadata::new();
+    metadata.add_raw_exif(raw_exif).unwrap();
+    metadata.add_raw_xmp(xmp.as_bytes().to_vec()).unwrap();
+
+    assert_eq!(
+        metadata.xmp()[0].orientation(),
+        Some(Orientation::Id),
+        "Properties after an array are read"
+    );
+
+    metadata
+        .update_exif(&[
+            (field::Orientation.into(), Typed::Short(vec![6])),
+            (
+                field::DateTimeOriginal.into(),
+                Typed::Ascii(b"2021:01:02 03:04:05".to_vec()),
+            ),
+            (
+                field::SubSecTimeOriginal.into(),
+                Typed::Ascii(b"123".to_vec()),
+            ),
+            (
+                field::OffsetTimeOriginal.into(),
+                Typed::Ascii(b"+02:00".to_vec()),
+            ),
+            (
+                field::FNumber.into(),
+                Typed::Rational(vec![Rational::new(28, 10)]),
+            ),
+            (field::Artist.into(), Typed::Ascii(b"Gufo; Owl".to_vec())),
+        ])
+        .unwrap();
+
+    let exif = &metadata.exif()[0];
+    let xmp = &metadata.xmp()[0];
+    assert_eq!(exif.artist().as_deref(), Some("Gufo; Owl"));
+    assert_eq!(
+        exif.serialize_mode(),
+        SerializeMode::InPlace,
+        "Rebuilding for the longer artist keeps the serialize mode"
+    );
+    assert_eq!(xmp.orientation(), Some(Orientation::Rotation270));
+    assert_eq!(xmp.orientation(), exif.orientation());
+    assert_eq!(
+        xmp.date_time_original().unwrap().to_string(),
+        "2021-01-02 03:04:05.123 +02:00"
+    );
+    assert_eq!(
+        xmp.date_time_original().map(|x| x.to_string()),
+        exif.date_time_original().map(|x| x.to_string())
+    );
+    assert_eq!(xmp.f_number(), Some(2.8));
+    assert!(matches!(
+        xmp.entries().get(&field::Creator.into()),
+        Some(Value::Seq(x)) if x == &["Gufo", "Owl"]
+    ));
+    assert_eq!(xmp.creator_tool().as_deref(), Some("Gufo"));
+
+    // Offset without the date
+    metadata
+        .update_exif(&[(
+            field::OffsetTimeOriginal.into(),
+            Typed::Ascii(b"+05:30".to_vec()),
+        )])
+        .unwrap();
+    assert_eq!(
+        metadata.xmp()[0].date_time_original().unwrap().to_string(),
+        "2021-01-02 03:04:05.123 +05:30"
+    );
+
+    let mut changes = BTreeMap::new();
+    changes.insert(
+        field::Creator.into(),
+        Value::Seq(vec!["Kiley".into(), "Barbero".into()]),
+    );
+    changes.insert(
+        field::ModifyDate.into(),
+        Value::Generic("2025-02-03T04:05:06Z".into()),
+    );
+    metadata.update_xmp(changes).unwrap();
+
+    let exif = &metadata.exif()[0];
+    assert_eq!(exif.artist().as_deref(), Some("Kiley; Barbero"));
+    assert_eq!(
+        exif.date_time().unwrap().to_string(),
+        "2025-02-03 04:05:06 +00:00"
+    );
+
+    // GPS coordinates are combined with their reference in XMP
+    assert!(
+        gufo::sync::exif_to_xmp(&[(
+            field::GPSLatitude.into(),
+            Typed::Rational(vec![Rational::new(52, 1); 3])
+        )])
+        .is_empty()
+    );
+}
+
+#[test]
+pub fn for_reader() {
+    let jpeg = std::fs::read("../tests/exif-xmp.jpg").unwrap();
+    let exif = gufo::RawMetadata::for_guessed(jpeg.clone()).unwrap().0.exif[0].clone();
+    let image_data = vec![0; 100_001];
+
+    // Add entropy-coded data in front of the EOI marker
+    let jpeg = [
+        &jpeg[..jpeg.len() - 2],
+        &image_data,
+        &jpeg[jpeg.len() - 2..],
+    ]
+    .concat();
+
+    let png = [
+        gufo::png::MAGIC_BYTES,
+        &png_chunk(b"IHDR", &[0; 13]),
+        &png_chunk(b"IDAT", &image_data),
+        // Metadata can follow the image data
+        &png_chunk(b"eXIf", &exif),
+        &png_chunk(b"tEXt", b"Title\0Gufo"),
+        &png_chunk(b"IEND", &[]),
+    ]
+    .concat();
+
+    let chunks = [
+        webp_chunk(b"VP8X", &[0; 10]),
+        webp_chunk(b"VP8 ", &image_data),
+        webp_chunk(b"EXIF", &exif),
+    ]
+    .concat();
+    let webp = [
+        b"RIFF".as_slice(),
+        &u32::try_from(chunks.len() + 4).unwrap().to_le_bytes(),
+        b"WEBP",
+        &chunks,
+    ]
+    .concat();
+
+    for data in [jpeg, png, webp] {
+        let mut reader = CountingReader {
+            inner: std::io::Cursor::new(data.clone()),
+            n_read: 0,
+        };
+        let raw_metadata = gufo::RawMetadata::for_reader(&mut reader).unwrap();
+        let (reference, _) = gufo::RawMetadata::for_guessed(data.clone()).unwrap();
+
+        assert_eq!(raw_metadata.exif, reference.exif);
+        assert_eq!(raw_metadata.xmp, reference.xmp);
+        assert_eq!(raw_metadata.key_value, reference.key_value);
+        assert!(!raw_metadata.exif.is_empty());
+
+        // Image data have been skipped
+        assert!(reader.n_read < data.len() / 2, "{}", reader.n_read);
+    }
+
+    let metadata = gufo::Metadata::for_reader(std::io::Cursor::new(exif)).unwrap();
+    assert_eq!(metadata.model().as_deref(), Some("iPhone 6"));
+
+    // TIFF with the image data in front of the IFDs
+    let tiff = tiff(&image_data);
+    let mut reader = CountingReader {
+        inner: std::io::Cursor::new(tiff.clone()),
+        n_read: 0,
+    };
+    let metadata = gufo::Metadata::for_reader(&mut reader).unwrap();
+    let reference = gufo::Metadata::for_guessed(tiff.clone()).unwrap();
+
+    assert_eq!(metadata.model().as_deref(), Some("Gufo Camera"));
+    assert_eq!(metadata.exposure_time(), Some(Rational::new(1, 250)));
+    assert_eq!(metadata.model(), reference.model());
+    assert_eq!(metadata.exposure_time(), reference.exposure_time());
+    assert!(reader.n_read < tiff.len() / 2, "{}", reader.n_read);
+
+    // Unknown file types are not read further
+    let mut reader = CountingReader {
+        inner: std::io::Cursor::new([b"GIF89a".as_slice(), &image_data].concat()),
+        n_read: 0,
+    };
+    assert!(matches!(
+        gufo::Metadata::for_reader(&mut reader),
+        Err(gufo::Error::NoSupportedFiletypeFound)
+    ));
+    assert!(reader.n_read <= 12, "{}", reader.n_read);
+}
+
+/// Little endian TIFF with `image_data` as strip in front of the IFDs
+fn tiff(image_data: &[u8]) -> Vec<u8> {
+    let entry = |tag: u16, type_: u16, count: u32, value: u32| {
+        [
+            tag.to_le_bytes().as_slice(),
+            &type_.to_le_bytes(),
+            &count.to_le_bytes(),
+            &value.to_le_bytes(),
+        ]
+        .concat()
+    };
+
+    let len = u32::try_from(image_data.len()).unwrap();
+    let padding = vec![0; image_data.len() % 2];
+    let ifd0 = (cheq(8_u32) + len + len % 2).check().unwrap();
+    let model = (cheq(ifd0) + 2_u32 + 4_u32 * 12_u32 + 4_u32)
+        .check()
+        .unwrap();
+    let exif_ifd = (cheq(model) + 12_u32).check().unwrap();
+    let exposure_time = (cheq(exif_ifd) + 2_u32 + 12_u32 + 4_u32).check().unwrap();
+
+    [
+        b"II*\0".as_slice(),
+        &ifd0.to_le_bytes(),
+        image_data,
+        &padding,
+        // IFD0
+        &4_u16.to_le_bytes(),
+        &entry(0x0110, 2, 12, model),
+        &entry(0x0111, 4, 1, 8),
+        &entry(0x0117, 4, 1, len),
+        &entry(0x8769, 4, 1, exif_ifd),
+        &0_u32.to_le_bytes(),
+        b"Gufo Camera\0",
+        // Exif IFD
+        &1_u16.to_le_bytes(),
+        &entry(0x829A, 5, 1, exposure_time),
+        &0_u32.to_le_bytes(),
+        &1_u32.to_le_bytes(),
+        &250_u32.to_le_bytes(),
+    ]
+    .concat()
+}
+
+fn png_chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let length = u32::try_from(data.len()).unwrap().to_be_bytes();
+    // The CRC is not checked
+    [&length, chunk_type.as_slice(), data, &[0; 4]].concat()
+}
+
+fn webp_chunk(four_cc: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let size = u32::try_from(payload.len()).unwrap().to_le_bytes();
+    let padding: &[u8] = if payload.len().is_multiple_of(2) { &[] } else { &[0] };
+    [four_cc.as_slice(), &size, payload, padding].concat()
+}
+
+/// Reader that counts the bytes that have actually been read
+struct CountingReader<R> {
+    inner: R,
+    n_read: usize,
+}
+
+impl<R: std::io::Read> std::io::Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.n_read = self.n_read.saturating_add(n);
+        Ok(n)
+    }
+}
+
+impl<R: std::io::Seek> std::io::Seek for CountingReader<R> {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+pub fn xmp_orientation() {
+    let xmp = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description
+        xmlns:exif="http://ns.adobe.com/exif/1.0/"
+        xmlns:tiff="http://ns.adobe.com/tiff/1.0/"
+        exif:UserComment="1"
+        tiff:Orientation="6"/>
+  </rdf:RDF>
+</x:xmpmeta>"#;
+
+    let xmp = gufo_xmp::Xmp::new(xmp.as_bytes().to_vec()).unwrap();
+    assert_eq!(xmp.orientation(), Some(Orientation::Rotation270));
+}
+
+#[test]
+pub fn xmp_properties_after_array() {
+    let xmp = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description
+        xmlns:dc="http://purl.org/dc/elements/1.1/"
+        xmlns:xmp="http://ns.adobe.com/xap/1.0/">
+      <dc:creator>
+        <rdf:Seq>
+          <rdf:li>Gufo</rdf:li>
+          <rdf:li>Owl</rdf:li>
+        </rdf:Seq>
+      </dc:creator>
+      <xmp:CreatorTool>Gufo Tool</xmp:CreatorTool>
+    </rdf:Description>
+  </rdf:RDF>
+</x:xmpmeta>"#;
+
+    let xmp = gufo_xmp::Xmp::new(xmp.as_bytes().to_vec()).unwrap();
+    assert!(matches!(
+        xmp.entries().get(&gufo_common::field::Creator.into()),
+        Some(gufo_xmp::Value::Seq(x)) if x == &["Gufo", "Owl"]
+    ));
+    assert_eq!(xmp.creator_tool().as_deref(), Some("Gufo Tool"));
+}
+
 #[derive(Default)]
 struct Reference<'a> {
     camera_owner: Option<&'a str>,
@@ -1,3 +1,5 @@
+use gufo_exif::Lookup;
+
 #[test]
 fn exif_user_comment_gthumb() {
     let data = std::fs::read("test-images/exif/gthumb/user_comment.jpg").unwrap();
@@ -18,3 +20,2365 @@ fn exif_delete_entry() {
     let deleted = exif.delete(gufo_common::field::UserComment.into()).unwrap();
     assert_eq!(deleted, true);
 }
+
+#[test]
+fn exif_compact() {
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let lens_model = b"iPhone 6 back camera 4.15mm f/2.2";
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif.clone()).unwrap();
+    assert!(exif.delete(gufo_common::field::LensModel.into()).unwrap());
+    assert!(exif.delete(gufo_common::field::LensMake.into()).unwrap());
+
+    // Deleting does not reduce the size
+    let raw_deleted = exif.serialize().unwrap();
+    assert_eq!(raw_deleted.len(), raw_exif.len());
+
+    // Compact data without changing the stored data
+    let raw_compact = exif.serialize_compact().unwrap();
+    assert!(raw_compact.len() < raw_deleted.len());
+    assert_eq!(exif.serialize().unwrap(), raw_deleted);
+
+    exif.compact().unwrap();
+    let raw = exif.serialize().unwrap();
+    assert_eq!(raw, raw_compact);
+    assert!(!raw.windows(lens_model.len()).any(|x| x == lens_model));
+
+    // Parse again to check the serialized data are valid
+    let exif = gufo_exif::ExifOwned::for_vec(raw).unwrap();
+    assert_eq!(exif.lens_model(), None);
+    assert_eq!(exif.model().as_deref(), Some("iPhone 6"));
+    assert_eq!(
+        exif.gps_location().unwrap().iso_6709(),
+        gufo_exif::ExifOwned::for_vec(raw_exif)
+            .unwrap()
+            .gps_location()
+            .unwrap()
+            .iso_6709()
+    );
+
+    // Compacting twice does not change anything
+    assert_eq!(exif.serialize_compact().unwrap(), exif.serialize().unwrap());
+}
+
+#[test]
+fn exif_thumbnail() {
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    exif.document(|document| {
+        assert!(
+            document
+                .ifds()
+                .contains_key(&gufo_common::exif::IfdId::Thumbnail)
+        );
+    })
+    .unwrap();
+
+    let thumbnail = exif.thumbnail().unwrap();
+    assert_eq!(thumbnail.len(), 7425);
+    assert!(thumbnail.starts_with(&[0xFF, 0xD8]));
+    assert!(thumbnail.ends_with(&[0xFF, 0xD9]));
+
+    // Replace thumbnail with a larger one
+    let mut new_thumbnail = thumbnail.clone();
+    new_thumbnail.splice(2..2, [0; 1000]);
+    exif.set_thumbnail(new_thumbnail.clone()).unwrap();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+    assert_eq!(exif.thumbnail(), Some(new_thumbnail));
+    assert_eq!(exif.model().as_deref(), Some("iPhone 6"));
+    assert!(exif.gps_location().is_some());
+
+    // Remove thumbnail
+    assert!(exif.remove_thumbnail().unwrap());
+    assert!(!exif.remove_thumbnail().unwrap());
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+    assert_eq!(exif.thumbnail(), None);
+    assert_eq!(exif.model().as_deref(), Some("iPhone 6"));
+    exif.document(|document| {
+        assert!(
+            !document
+                .ifds()
+                .contains_key(&gufo_common::exif::IfdId::Thumbnail)
+        );
+    })
+    .unwrap();
+}
+
+/// Nikon type 3 MakerNote with little endian TIFF structure
+fn nikon_maker_note() -> Vec<u8> {
+    let mut data = b"Nikon\0\x02\x11\0\0".to_vec();
+    // Embedded TIFF header
+    data.extend_from_slice(b"II*\0");
+    data.extend_from_slice(&8_u32.to_le_bytes());
+
+    // IFD with four entries, data start at offset 62
+    let entries: [(u16, u16, u32, [u8; 4]); 4] = [
+        (0x1D, 2, 8, 62_u32.to_le_bytes()),
+        (0x83, 1, 1, [0x0E, 0, 0, 0]),
+        (0x84, 5, 4, 70_u32.to_le_bytes()),
+        (0xA7, 4, 1, 12345_u32.to_le_bytes()),
+    ];
+    data.extend_from_slice(&4_u16.to_le_bytes());
+    for (tag, type_, count, value) in entries {
+        data.extend_from_slice(&tag.to_le_bytes());
+        data.extend_from_slice(&type_.to_le_bytes());
+        data.extend_from_slice(&count.to_le_bytes());
+        data.extend_from_slice(&value);
+    }
+    data.extend_from_slice(&0_u32.to_le_bytes());
+
+    data.extend_from_slice(b"3012345\0");
+    for x in [18, 1, 55, 1, 35, 10, 56, 10_u32] {
+        data.extend_from_slice(&x.to_le_bytes());
+    }
+
+    data
+}
+
+#[test]
+fn exif_maker_note_canon() {
+    use gufo_common::field;
+    use gufo_exif::maker_note::Vendor;
+    use gufo_exif::maker_note::canon::LensType;
+    use gufo_exif::structure::Endieness;
+    use gufo_exif::{ExifBuilder, Typed};
+
+    let data = std::fs::read("../gufo-exif/example.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    assert_eq!(exif.maker_note_vendor(), Some(Vendor::Canon));
+    assert_eq!(exif.camera_owner_name().as_deref(), Some("Sophie Herold"));
+    assert_eq!(
+        exif.view()
+            .lookup_uints(field::CanonModelID.into())
+            .unwrap(),
+        Some(vec![0x8000_0236])
+    );
+    assert_eq!(
+        exif.view()
+            .lookup_string(field::CanonFirmwareVersion.into())
+            .unwrap()
+            .as_deref(),
+        Some("Firmware 1.1.0")
+    );
+
+    let camera_settings = exif.canon_camera_settings().unwrap();
+    assert_eq!(camera_settings.lens_type, Some(LensType(131)));
+    assert_eq!(exif.canon_lens_type(), Some(LensType(131)));
+    assert_eq!(camera_settings.focal_length_range(), Some((17., 70.)));
+    assert_eq!(
+        camera_settings.lens_name(),
+        Some("Sigma 17-70mm f/2.8-4.5 DC Macro")
+    );
+    assert_eq!(
+        exif.lens_model().as_deref(),
+        Some("Sigma 17-70mm f/2.8-4.5 DC Macro")
+    );
+
+    // Identifiers shared by several lenses are resolved by the focal length
+    assert_eq!(
+        LensType(131).name(Some((28., 80.))),
+        Some("Canon EF 28-80mm f/2.8-4L USM")
+    );
+    assert_eq!(
+        LensType(493).name(Some((24., 105.))),
+        Some("Canon EF 24-105mm f/4L IS USM")
+    );
+    assert_eq!(
+        LensType(4146).name(None),
+        Some("Canon EF-S 18-55mm f/3.5-5.6 IS STM")
+    );
+    assert_eq!(LensType::RF.name(Some((24., 105.))), None);
+
+    let focal_length = exif.canon_focal_length().unwrap();
+    assert_eq!(focal_length.focal_length, Some(17));
+
+    let shot_info = exif.canon_shot_info().unwrap();
+    assert_eq!(shot_info.camera_temperature_celsius(), Some(29));
+
+    // Data that don't look like an IFD are not read as Canon MakerNote
+    let mut maker_note = vec![0, 1, 0, 1, 0, 0];
+    maker_note.resize(18, 0);
+    let raw_exif = ExifBuilder::new(Endieness::Big)
+        .entry(field::Make, Typed::Ascii(b"Canon".to_vec()))
+        .entry(field::MakerNote, Typed::Undefined(maker_note))
+        .build()
+        .unwrap();
+    let exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    assert_eq!(exif.maker_note_vendor(), Some(Vendor::Unknown));
+}
+
+#[test]
+fn exif_maker_note_nikon() {
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let maker_note = nikon_maker_note();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    exif.update_entry(
+        gufo_common::field::MakerNote.into(),
+        gufo_exif::Typed::Undefined(maker_note.clone()),
+    )
+    .unwrap();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+
+    assert_eq!(
+        exif.maker_note_vendor(),
+        Some(gufo_exif::maker_note::Vendor::Nikon)
+    );
+    assert_eq!(exif.serial_number().as_deref(), Some("3012345"));
+    assert_eq!(exif.shutter_count(), Some(12345));
+
+    let lens_type = exif.nikon_lens_type().unwrap();
+    assert!(lens_type.d && lens_type.g && lens_type.vr);
+    assert!(!lens_type.manual_focus);
+
+    let lens = exif
+        .view()
+        .lookup_rationals::<4>(gufo_common::field::NikonLens.into())
+        .unwrap()
+        .unwrap();
+    assert_eq!(lens[1].as_f64(), 55.);
+    assert_eq!(lens[3].as_f64(), 5.6);
+
+    // MakerNote stays intact when other entries change its position
+    exif.set_serialize_mode(gufo_exif::structure::SerializeMode::Rebuild);
+    exif.update_entry(
+        gufo_common::field::Artist.into(),
+        gufo_exif::Typed::Ascii(b"Jane Doe".to_vec()),
+    )
+    .unwrap();
+    exif.update_entry(
+        gufo_common::field::Model.into(),
+        gufo_exif::Typed::Ascii(b"iPhone 6 with a longer model name".to_vec()),
+    )
+    .unwrap();
+
+    let raw = exif.serialize().unwrap();
+    assert!(raw.windows(maker_note.len()).any(|x| x == maker_note));
+
+    // MakerNote entries can be changed in place
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw).unwrap();
+    exif.update_entry(
+        gufo_common::field::NikonShutterCount.into(),
+        gufo_exif::Typed::Long(vec![54321]),
+    )
+    .unwrap();
+
+    let exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+    assert_eq!(exif.shutter_count(), Some(54321));
+    assert_eq!(exif.serial_number().as_deref(), Some("3012345"));
+    assert_eq!(exif.artist().as_deref(), Some("Jane Doe"));
+
+    // The size of the MakerNote can't change
+    let mut exif = exif;
+    assert!(matches!(
+        exif.delete(gufo_common::field::NikonSerialNumber.into()),
+        Err(gufo_exif::Error::MakerNoteNotResizable(_))
+    ));
+    assert!(matches!(
+        exif.update_entry(
+            gufo_common::field::NikonQuality.into(),
+            gufo_exif::Typed::Ascii(b"FINE".to_vec()),
+        ),
+        Err(gufo_exif::Error::MakerNoteNotResizable(_))
+    ));
+}
+
+#[test]
+fn exif_maker_note_nikon_d5100() {
+    use gufo_common::field;
+
+    let data = std::fs::read("test-images/exif/jpeg/nikon-d5100.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    assert_eq!(
+        exif.maker_note_vendor(),
+        Some(gufo_exif::maker_note::Vendor::Nikon)
+    );
+
+    // Type 3 header with the embedded TIFF structure starting at byte 10
+    let Some(gufo_exif::Typed::Undefined(maker_note)) =
+        exif.view().lookup(field::MakerNote.into()).unwrap()
+    else {
+        panic!("No MakerNote");
+    };
+    assert!(maker_note.starts_with(gufo_exif::maker_note::nikon::HEADER_MAGIC));
+    assert!(matches!(&maker_note[10..14], b"II*\0" | b"MM\0*"));
+
+    // Values are only found if offsets relative to the embedded TIFF header
+    // are resolved correctly
+    let serial_number = exif
+        .view()
+        .lookup_string(field::NikonSerialNumber.into())
+        .unwrap()
+        .unwrap();
+    assert!(!serial_number.is_empty());
+    assert!(serial_number.bytes().all(|x| x.is_ascii_digit()));
+
+    // The image was taken at 280 mm with f/5.6
+    let lens = exif
+        .view()
+        .lookup_rationals::<4>(field::NikonLens.into())
+        .unwrap()
+        .unwrap();
+    assert!(lens[0].as_f64() <= 280. && 280. <= lens[1].as_f64());
+    assert!(lens[2].as_f64() <= lens[3].as_f64() && lens[3].as_f64() <= 5.6);
+    assert!(exif.nikon_lens_type().is_some());
+
+    // The MakerNote survives rebuilding the structure
+    exif.set_serialize_mode(gufo_exif::structure::SerializeMode::Rebuild);
+    exif.update_entry(
+        field::Artist.into(),
+        gufo_exif::Typed::Ascii(b"Jane Doe".to_vec()),
+    )
+    .unwrap();
+
+    let raw = exif.serialize().unwrap();
+    assert!(raw.windows(maker_note.len()).any(|x| x == maker_note));
+    let exif = gufo_exif::ExifOwned::for_vec(raw).unwrap();
+    assert_eq!(
+        exif.view()
+            .lookup_string(field::NikonSerialNumber.into())
+            .unwrap(),
+        Some(serial_number)
+    );
+}
+
+/// Apple MakerNote with big endian IFD and offsets relative to its start
+fn apple_maker_note() -> Vec<u8> {
+    let acceleration_vector = [(-54, 100), (-987, 1000), (-15, 1000)]
+        .iter()
+        .flat_map(|(n, d): &(i32, i32)| [n.to_be_bytes(), d.to_be_bytes()].concat())
+        .collect::<Vec<_>>();
+
+    // IFD with five entries, values start at offset 80
+    let entries: [(u16, u16, u32, u32, Vec<u8>); 5] = [
+        (0x8, 10, 3, 80, acceleration_vector),
+        (
+            0xB,
+            2,
+            37,
+            104,
+            b"F3C1A2B4-5D6E-4F70-8192-A3B4C5D6E7F8\0\0".to_vec(),
+        ),
+        (
+            0x11,
+            2,
+            37,
+            142,
+            b"0A1B2C3D-4E5F-4061-7283-94A5B6C7D8E9\0\0".to_vec(),
+        ),
+        (
+            0x21,
+            10,
+            1,
+            180,
+            [1_i32, 2].iter().flat_map(|x| x.to_be_bytes()).collect(),
+        ),
+        (
+            0x30,
+            10,
+            1,
+            188,
+            [3_i32, 4].iter().flat_map(|x| x.to_be_bytes()).collect(),
+        ),
+    ];
+
+    let mut data = b"Apple iOS\0\0\x01MM".to_vec();
+    let mut values = Vec::new();
+
+    data.extend_from_slice(&5_u16.to_be_bytes());
+    for (tag, type_, count, offset, value) in entries {
+        data.extend_from_slice(&tag.to_be_bytes());
+        data.extend_from_slice(&type_.to_be_bytes());
+        data.extend_from_slice(&count.to_be_bytes());
+        data.extend_from_slice(&offset.to_be_bytes());
+        values.extend_from_slice(&value);
+    }
+    data.extend_from_slice(&0_u32.to_be_bytes());
+    data.extend_from_slice(&values);
+
+    data
+}
+
+#[test]
+fn exif_maker_note_apple() {
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let maker_note = apple_maker_note();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    exif.update_entry(
+        gufo_common::field::MakerNote.into(),
+        gufo_exif::Typed::Undefined(maker_note.clone()),
+    )
+    .unwrap();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+
+    assert_eq!(
+        exif.maker_note_vendor(),
+        Some(gufo_exif::maker_note::Vendor::Apple)
+    );
+    assert_eq!(
+        exif.apple_content_identifier().as_deref(),
+        Some("0A1B2C3D-4E5F-4061-7283-94A5B6C7D8E9")
+    );
+    assert_eq!(
+        exif.apple_burst_uuid().as_deref(),
+        Some("F3C1A2B4-5D6E-4F70-8192-A3B4C5D6E7F8")
+    );
+    assert_eq!(exif.apple_hdr_headroom(), Some(0.5));
+    assert_eq!(exif.apple_hdr_gain(), Some(0.75));
+
+    let acceleration_vector = exif.apple_acceleration_vector().unwrap();
+    assert_eq!(acceleration_vector.x, -0.54);
+    assert_eq!(acceleration_vector.y, -0.987);
+    assert_eq!(acceleration_vector.z, -0.015);
+
+    // MakerNote stays intact when the structure is rebuilt
+    exif.set_serialize_mode(gufo_exif::structure::SerializeMode::Rebuild);
+    exif.update_entry(
+        gufo_common::field::Artist.into(),
+        gufo_exif::Typed::Ascii(b"Jane Doe".to_vec()),
+    )
+    .unwrap();
+
+    let raw = exif.serialize().unwrap();
+    assert!(raw.windows(maker_note.len()).any(|x| x == maker_note));
+
+    let exif = gufo_exif::ExifOwned::for_vec(raw).unwrap();
+    assert_eq!(
+        exif.apple_content_identifier().as_deref(),
+        Some("0A1B2C3D-4E5F-4061-7283-94A5B6C7D8E9")
+    );
+}
+
+#[test]
+fn exif_maker_note_apple_iphone6() {
+    // The MakerNote has been removed from exif-xmp.jpg, so a complete file is
+    // used here
+    let data = std::fs::read("test-images/exif/jpeg/apple-iphone6.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    assert_eq!(
+        exif.maker_note_vendor(),
+        Some(gufo_exif::maker_note::Vendor::Apple)
+    );
+    let acceleration_vector = exif.apple_acceleration_vector().unwrap();
+
+    // The MakerNote survives rebuilding the structure
+    exif.set_serialize_mode(gufo_exif::structure::SerializeMode::Rebuild);
+    exif.update_entry(
+        gufo_common::field::Artist.into(),
+        gufo_exif::Typed::Ascii(b"Jane Doe".to_vec()),
+    )
+    .unwrap();
+
+    let exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+    assert_eq!(exif.apple_acceleration_vector(), Some(acceleration_vector));
+}
+
+#[test]
+fn exif_builder() {
+    use gufo_common::field;
+    use gufo_exif::structure::Endieness;
+    use gufo_exif::{ExifBuilder, Typed};
+
+    for (endieness, big_tiff) in [
+        (Endieness::Big, false),
+        (Endieness::Litte, false),
+        (Endieness::Litte, true),
+    ] {
+        let raw_exif = ExifBuilder::new(endieness)
+            .big_tiff(big_tiff)
+            .entry(field::Model, Typed::Ascii(b"Gufo Camera".to_vec()))
+            .entry(
+                field::DateTimeOriginal,
+                Typed::Ascii(b"2024:03:04 05:06:07".to_vec()),
+            )
+            .entry(field::GPSLatitudeRef, Typed::Ascii(b"N".to_vec()))
+            .entry(field::InteroperabilityIndex, Typed::Ascii(b"R98".to_vec()))
+            .entry(field::ThumbnailImageWidth, Typed::Long(vec![160]))
+            .build()
+            .unwrap();
+
+        let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+        assert_eq!(exif.model().as_deref(), Some("Gufo Camera"));
+
+        let lookup = |tag_ifd| exif.view().lookup_string(tag_ifd).unwrap();
+        assert_eq!(lookup(field::GPSLatitudeRef.into()).as_deref(), Some("N"));
+        assert_eq!(
+            lookup(field::InteroperabilityIndex.into()).as_deref(),
+            Some("R98")
+        );
+        assert_eq!(
+            exif.view()
+                .lookup_uints(field::ThumbnailImageWidth.into())
+                .unwrap(),
+            Some(vec![160])
+        );
+
+        // Output can be edited further
+        exif.update_entry(field::Model.into(), Typed::Ascii(b"Gufo".to_vec()))
+            .unwrap();
+        assert_eq!(exif.model().as_deref(), Some("Gufo"));
+    }
+
+    // Pointers are created by the builder
+    assert!(
+        ExifBuilder::new(Endieness::Big)
+            .entry(field::ExifIFDPointer, Typed::Long(vec![8]))
+            .build()
+            .is_err()
+    );
+}
+
+#[test]
+fn exif_gps() {
+    use gufo_common::geography::{Coord, Location};
+
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif.clone()).unwrap();
+    assert_eq!(
+        exif.gps_location().unwrap().iso_6709(),
+        r#"52°32'40.25"N 13°23'16.02"E"#
+    );
+
+    // Existing entries are overwritten in place, even if the data are borrowed
+    let mut raw_borrowed = raw_exif.clone();
+    let mut borrowed = gufo_exif::Exif::for_mut_slice(&mut raw_borrowed).unwrap();
+    borrowed
+        .set_gps_location(Location::new_from_coord(Coord(-33.8568), Coord(151.2153)))
+        .unwrap();
+    borrowed.set_gps_altitude(-12.5).unwrap();
+    assert_eq!(
+        borrowed.gps_location().unwrap().iso_6709(),
+        r#"33°51'24.48"S 151°12'55.08"E"#
+    );
+    assert_eq!(borrowed.gps_altitude(), Some(-12.5));
+    assert_eq!(raw_borrowed.len(), raw_exif.len());
+
+    // Replace existing GPS data
+    exif.set_gps_location(Location::new_from_coord(Coord(-33.8568), Coord(151.2153)))
+        .unwrap();
+    exif.set_gps_altitude(-12.5).unwrap();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+    assert_eq!(
+        exif.gps_location().unwrap().iso_6709(),
+        r#"33°51'24.48"S 151°12'55.08"E"#
+    );
+    assert_eq!(exif.gps_altitude(), Some(-12.5));
+    assert_eq!(exif.model().as_deref(), Some("iPhone 6"));
+
+    assert!(exif.remove_gps().unwrap());
+    assert!(exif.gps_location().is_none());
+    assert!(
+        exif.document(|x| {
+            x.entry(gufo_common::field::GPSInfoIFDPointer.into())
+                .is_none()
+        })
+        .unwrap()
+    );
+    assert!(!exif.remove_gps().unwrap());
+
+    // Create GPS IFD
+    exif.set_gps_altitude(1200.).unwrap();
+    let exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+    assert_eq!(exif.gps_altitude(), Some(1200.));
+    assert_eq!(
+        exif.view()
+            .lookup(gufo_common::field::GPSVersionID.into())
+            .unwrap(),
+        Some(gufo_exif::Typed::Byte(vec![2, 3, 0, 0]))
+    );
+}
+
+#[test]
+fn exif_gps_date_time() {
+    use chrono::TimeZone;
+    use gufo_common::field;
+    use gufo_exif::structure::Endieness;
+    use gufo_exif::{ExifBuilder, Typed};
+
+    let raw_exif = ExifBuilder::new(Endieness::Litte)
+        .entry(field::Model, Typed::Ascii(b"Gufo".to_vec()))
+        .build()
+        .unwrap();
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+
+    let date_time = chrono::Utc
+        .with_ymd_and_hms(2024, 12, 31, 23, 59, 59)
+        .unwrap()
+        .checked_add_signed(chrono::TimeDelta::microseconds(999_900))
+        .unwrap();
+    exif.set_gps_date_time(date_time).unwrap();
+
+    let exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+    assert_eq!(
+        exif.view().lookup(field::GPSTimeStamp.into()).unwrap(),
+        Some(Typed::Rational(vec![
+            gufo_common::types::Rational::new(23, 1),
+            gufo_common::types::Rational::new(59, 1),
+            gufo_common::types::Rational::new(59_999, 1000),
+        ]))
+    );
+    assert_eq!(
+        exif.gps_date_time(),
+        Some(date_time - chrono::TimeDelta::microseconds(900))
+    );
+}
+
+#[test]
+fn exif_tag_registry() {
+    use gufo_common::exif::{Count, IfdId, Tag, TagIfd, Type, lookup_tag_info, tag_infos};
+
+    let names = tag_infos()
+        .map(|x| x.name)
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(names.len(), tag_infos().count());
+
+    // TIFF 6.0, TIFF/EP, and DNG
+    for (tag, name, types, count) in [
+        (0x108, "CellWidth", &[Type::Short][..], Count::Fixed(1)),
+        (0x125, "T6Options", &[Type::Long], Count::Fixed(1)),
+        (0x151, "TargetPrinter", &[Type::Ascii], Count::Any),
+        (0x156, "TransferRange", &[Type::Short], Count::Fixed(6)),
+        (0x882A, "TimeZoneOffset", &[Type::SShort], Count::Any),
+        (0x920B, "FlashEnergy2", &[Type::Rational], Count::Any),
+        (0x9213, "ImageHistory", &[Type::Ascii], Count::Any),
+        (0x9217, "SensingMethod2", &[Type::Short], Count::Fixed(1)),
+        (
+            0xC6BF,
+            "ColorimetricReference",
+            &[Type::Short],
+            Count::Fixed(1),
+        ),
+        (
+            0xC6F9,
+            "ProfileHueSatMapDims",
+            &[Type::Long],
+            Count::Fixed(3),
+        ),
+        (0xC71A, "PreviewColorSpace", &[Type::Long], Count::Fixed(1)),
+        (0xC71B, "PreviewDateTime", &[Type::Ascii], Count::Any),
+        (0xC761, "NoiseProfile", &[Type::Double], Count::Any),
+    ] {
+        let info = lookup_tag_info(TagIfd::new(Tag(tag), IfdId::Primary)).unwrap();
+        assert_eq!((info.name, info.types, info.count), (name, types, count));
+        assert!(!info.description.is_empty());
+    }
+
+    for path in ["exif-xmp.jpg", "../gufo-exif/example.jpg"] {
+        let data = std::fs::read(path).unwrap();
+        let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+        let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+        let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+        let entries = exif.document(|x| x.entries().unwrap()).unwrap();
+
+        for (ifd, entries) in entries {
+            if matches!(ifd, IfdId::MakerNote(_)) {
+                continue;
+            }
+
+            for entry in entries.values() {
+                let info = lookup_tag_info(entry.tag_ifd).unwrap();
+                assert!(info.types.contains(&entry.type_), "{}", info.name);
+                assert!(info.count.matches(entry.count), "{}", info.name);
+            }
+        }
+    }
+}
+
+#[test]
+fn exif_capture_settings() {
+    use gufo_common::capture::{
+        ColorSpace, CustomRendered, ExposureProgram, FlashMode, MeteringMode, SensingMethod,
+        WhiteBalance,
+    };
+
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let metadata = gufo::Metadata::for_guessed(data).unwrap();
+
+    assert_eq!(metadata.exposure_program(), Some(ExposureProgram::Normal));
+    assert_eq!(metadata.metering_mode(), Some(MeteringMode::Spot));
+    assert_eq!(metadata.white_balance(), Some(WhiteBalance::Auto));
+    assert_eq!(metadata.color_space(), Some(ColorSpace::Srgb));
+    assert_eq!(
+        metadata.sensing_method().unwrap().label(),
+        Some("One-chip color area sensor")
+    );
+    assert_eq!(
+        metadata.sensing_method(),
+        Some(SensingMethod::OneChipColorArea)
+    );
+    assert_eq!(metadata.light_source(), None);
+
+    // Values not defined in the Exif specification
+    assert_eq!(metadata.custom_rendered(), Some(CustomRendered::Unknown(3)));
+    assert_eq!(
+        metadata.custom_rendered().unwrap().to_string(),
+        "Unknown (3)"
+    );
+
+    let flash = metadata.flash().unwrap();
+    assert!(!flash.fired);
+    assert!(flash.function_present);
+    assert_eq!(flash.mode, FlashMode::Off);
+    assert_eq!(u16::from(flash), 0x10);
+}
+
+#[test]
+fn exif_update_entry_into_entry() {
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    // The model is stored behind the IFD, the new value fits into the entry
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif.clone()).unwrap();
+    exif.update_entry(
+        gufo_common::field::Model.into(),
+        gufo_exif::Typed::Ascii(b"Owl".to_vec()),
+    )
+    .unwrap();
+
+    let raw = exif.serialize().unwrap();
+    assert_eq!(raw.len(), raw_exif.len());
+
+    let exif = gufo_exif::ExifOwned::for_vec(raw).unwrap();
+    assert_eq!(exif.model().as_deref(), Some("Owl"));
+    assert_eq!(exif.make().as_deref(), Some("Apple"));
+}
+
+#[test]
+fn exif_insert_entry() {
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif.clone()).unwrap();
+    let location = exif.gps_location().unwrap();
+
+    exif.update_entry(
+        gufo_common::field::Artist.into(),
+        gufo_exif::Typed::Ascii(b"Jane Doe".to_vec()),
+    )
+    .unwrap();
+    exif.update_entry(
+        gufo_common::field::Copyright.into(),
+        gufo_exif::Typed::Ascii(b"CC-BY-SA 4.0 Jane Doe".to_vec()),
+    )
+    .unwrap();
+    exif.update_entry(
+        gufo_common::field::BodySerialNumber.into(),
+        gufo_exif::Typed::Ascii(b"42".to_vec()),
+    )
+    .unwrap();
+    exif.update_entry(
+        gufo_common::field::GPSMapDatum.into(),
+        gufo_exif::Typed::Ascii(b"WGS-84".to_vec()),
+    )
+    .unwrap();
+
+    // Parse again to check the serialized data are valid
+    let mut exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+
+    assert_eq!(exif.artist().as_deref(), Some("Jane Doe"));
+    assert_eq!(exif.copyright().as_deref(), Some("CC-BY-SA 4.0 Jane Doe"));
+    assert_eq!(
+        exif.view()
+            .lookup_string(gufo_common::field::BodySerialNumber.into())
+            .unwrap()
+            .as_deref(),
+        Some("42")
+    );
+    assert_eq!(
+        exif.view()
+            .lookup_string(gufo_common::field::GPSMapDatum.into())
+            .unwrap()
+            .as_deref(),
+        Some("WGS-84")
+    );
+
+    // Existing entries are still intact
+    assert_eq!(exif.model().as_deref(), Some("iPhone 6"));
+    assert_eq!(
+        exif.lens_model().as_deref(),
+        Some("iPhone 6 back camera 4.15mm f/2.2")
+    );
+    assert_eq!(exif.gps_location().unwrap().iso_6709(), location.iso_6709());
+
+    // Entry lists stay sorted
+    exif.document(|document| {
+        for (_, ifd) in document.ifds().values() {
+            let tags = ifd.tags();
+            assert!(tags.is_sorted());
+        }
+    })
+    .unwrap();
+
+    // Borrowed data can't grow
+    let mut raw_exif = raw_exif;
+    let mut exif = gufo_exif::Exif::for_mut_slice(&mut raw_exif).unwrap();
+    assert!(matches!(
+        exif.update_entry(
+            gufo_common::field::Artist.into(),
+            gufo_exif::Typed::Ascii(b"Jane Doe".to_vec()),
+        ),
+        Err(gufo_exif::Error::StorageNotResizable)
+    ));
+}
+
+#[test]
+fn exif_insert_entry_opaque_maker_note() {
+    use gufo_common::field;
+
+    let data = opaque_maker_note();
+    let mut exif = gufo_exif::ExifOwned::for_vec(data.clone()).unwrap();
+
+    // Inserting before the MakerNote would move it
+    assert!(matches!(
+        exif.update_entry(
+            field::Model.into(),
+            gufo_exif::Typed::Ascii(b"Model".to_vec())
+        ),
+        Err(gufo_exif::Error::MakerNoteNotMovable(_))
+    ));
+    assert_eq!(exif.serialize().unwrap(), data);
+}
+
+#[test]
+fn exif_insert_entry_big_tiff_pointers() {
+    use gufo_common::exif::{Field, TagIfd};
+    use gufo_common::field;
+
+    fn big_tiff_ifd(entries: &[(u16, u16, [u8; 8])], next: u64) -> Vec<u8> {
+        let mut data = u64::try_from(entries.len()).unwrap().to_be_bytes().to_vec();
+        for (tag, type_, value) in entries {
+            data.extend_from_slice(&tag.to_be_bytes());
+            data.extend_from_slice(&type_.to_be_bytes());
+            data.extend_from_slice(&1_u64.to_be_bytes());
+            data.extend_from_slice(value);
+        }
+        data.extend_from_slice(&next.to_be_bytes());
+        data
+    }
+
+    // Big endian BigTIFF with a 32 bit Exif IFD pointer and a 64 bit GPS IFD
+    // pointer
+    let mut data = b"MM\0+\0\x08\0\0".to_vec();
+    data.extend_from_slice(&16_u64.to_be_bytes());
+    data.extend(big_tiff_ifd(
+        &[
+            (0x8769, 4, [0, 0, 0, 72, 0, 0, 0, 0]),
+            (0x8825, 18, 108_u64.to_be_bytes()),
+        ],
+        0,
+    ));
+    data.extend(big_tiff_ifd(&[(0x8827, 3, [0, 200, 0, 0, 0, 0, 0, 0])], 0));
+    data.extend(big_tiff_ifd(&[(0x0000, 1, [2, 0, 0, 0, 0, 0, 0, 0])], 0));
+
+    let gps_version = TagIfd::new(field::GPSVersionID::TAG, field::GPSVersionID::IFD);
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(data).unwrap();
+    assert_eq!(exif.iso_speed_rating(), Some(200));
+
+    exif.update_entry(field::ImageWidth.into(), gufo_exif::Typed::Long(vec![500]))
+        .unwrap();
+
+    let exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+    assert_eq!(
+        exif.view().lookup_uints(field::ImageWidth.into()).unwrap(),
+        Some(vec![500])
+    );
+    assert_eq!(exif.iso_speed_rating(), Some(200));
+    assert_eq!(
+        exif.view().lookup(gps_version).unwrap(),
+        Some(gufo_exif::Typed::Byte(vec![2]))
+    );
+}
+
+#[test]
+fn exif_insert_entry_strips() {
+    use gufo_common::field;
+    use gufo_exif::Typed;
+
+    // Two strips whose offsets and byte counts are stored behind the IFD
+    let mut raw_tiff = b"II*\0\x08\0\0\0".to_vec();
+    raw_tiff.extend(tiff_ifd(&[(0x111, 4, 2, 38), (0x117, 4, 2, 46)], 0));
+    raw_tiff.extend([54_u32, 58].iter().flat_map(|x| x.to_le_bytes()));
+    raw_tiff.extend([4_u32, 4].iter().flat_map(|x| x.to_le_bytes()));
+    raw_tiff.extend(b"AAAABBBB");
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_tiff).unwrap();
+    exif.update_entry(field::Make.into(), Typed::Ascii(b"Owl".to_vec()))
+        .unwrap();
+
+    let raw_tiff = exif.serialize().unwrap();
+    let exif = gufo_exif::ExifOwned::for_vec(raw_tiff.clone()).unwrap();
+    assert_eq!(exif.make().as_deref(), Some("Owl"));
+
+    // The offsets moved with the strips by the size of one entry
+    assert_eq!(
+        exif.view().lookup(field::StripOffsets.into()).unwrap(),
+        Some(Typed::Long(vec![66, 70]))
+    );
+    assert_eq!(
+        exif.view().lookup(field::StripByteCounts.into()).unwrap(),
+        Some(Typed::Long(vec![4, 4]))
+    );
+    assert_eq!(&raw_tiff[66..74], b"AAAABBBB");
+}
+
+#[test]
+fn exif_rebuild_grow_value() {
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let model = "iPhone 6 with a model name that is longer than before";
+    let lens_model = "iPhone 6 back camera 4.15mm f/2.2 with a much longer lens model description";
+
+    // In-place editing can't grow values
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif.clone()).unwrap();
+    let location = exif.gps_location().unwrap();
+    assert!(matches!(
+        exif.update_entry(
+            gufo_common::field::Model.into(),
+            gufo_exif::Typed::Ascii(model.as_bytes().to_vec()),
+        ),
+        Err(gufo_exif::Error::WouldIncreaseDataStore)
+    ));
+
+    exif.set_serialize_mode(gufo_exif::structure::SerializeMode::Rebuild);
+    exif.update_entry(
+        gufo_common::field::Model.into(),
+        gufo_exif::Typed::Ascii(model.as_bytes().to_vec()),
+    )
+    .unwrap();
+    exif.update_entry(
+        gufo_common::field::LensModel.into(),
+        gufo_exif::Typed::Ascii(lens_model.as_bytes().to_vec()),
+    )
+    .unwrap();
+
+    // Parse again to check the serialized data are valid
+    let mut exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+
+    assert_eq!(exif.model().as_deref(), Some(model));
+    assert_eq!(exif.lens_model().as_deref(), Some(lens_model));
+
+    // Existing entries and sub-IFDs are still intact
+    assert_eq!(exif.make().as_deref(), Some("Apple"));
+    assert_eq!(exif.gps_location().unwrap().iso_6709(), location.iso_6709());
+    exif.document(|document| {
+        assert!(
+            document
+                .ifds()
+                .contains_key(&gufo_common::exif::IfdId::Exif)
+        );
+        assert!(document.ifds().contains_key(&gufo_common::exif::IfdId::Gps));
+    })
+    .unwrap();
+}
+
+/// Exif data with a MakerNote of unknown structure that contains an absolute
+/// offset
+fn opaque_maker_note() -> Vec<u8> {
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8_u32.to_le_bytes());
+    data.extend(tiff_ifd(&[(0x8769, 4, 1, 26)], 0));
+    data.extend(tiff_ifd(&[(0x927C, 7, 16, 48)], 0));
+    data.extend_from_slice(&[0; 4]);
+    data.extend_from_slice(b"ACME");
+    data.extend_from_slice(&56_u32.to_le_bytes());
+    data.extend_from_slice(b"payload\0");
+    data
+}
+
+#[test]
+fn exif_rebuild_opaque_maker_note() {
+    use gufo_common::field;
+    use gufo_exif::Typed;
+
+    let data = opaque_maker_note();
+    let maker_note = data[48..64].to_vec();
+    let mut exif = gufo_exif::ExifOwned::for_vec(data.clone()).unwrap();
+    assert_eq!(exif.serialize().unwrap(), data);
+
+    // Other values grow and move around the MakerNote
+    exif.set_serialize_mode(gufo_exif::structure::SerializeMode::Rebuild);
+    exif.update_entry(field::Model.into(), Typed::Ascii(b"Model".to_vec()))
+        .unwrap();
+    exif.update_entry(
+        field::Copyright.into(),
+        Typed::Ascii(b"Copyright notice that is longer than the gap".to_vec()),
+    )
+    .unwrap();
+    exif.update_entry(
+        field::Copyright.into(),
+        Typed::Ascii(b"An even longer copyright notice that has to grow".to_vec()),
+    )
+    .unwrap();
+
+    let raw = exif.serialize().unwrap();
+    let exif = gufo_exif::ExifOwned::for_vec(raw.clone()).unwrap();
+    assert_eq!(exif.model().as_deref(), Some("Model"));
+    assert_eq!(
+        exif.copyright().as_deref(),
+        Some("An even longer copyright notice that has to grow")
+    );
+
+    // The MakerNote stays at its offset, so the absolute offset in it is valid
+    assert_eq!(&raw[48..64], maker_note);
+    assert_eq!(&raw[56..64], b"payload\0");
+    assert_eq!(
+        exif.view().lookup(field::MakerNote.into()).unwrap(),
+        Some(Typed::Undefined(maker_note))
+    );
+    assert!(
+        exif.validate()
+            .unwrap()
+            .iter()
+            .all(|x| x.severity == gufo_exif::structure::Severity::Warning)
+    );
+}
+
+#[test]
+fn exif_sanitize_opaque_maker_note() {
+    use gufo_common::exif::IfdId;
+    use gufo_common::field;
+    use gufo_common::geography::{Coord, Location};
+    use gufo_exif::SanitizeProfile;
+
+    let data = opaque_maker_note();
+    let maker_note = data[48..64].to_vec();
+    let mut exif = gufo_exif::ExifOwned::for_vec(data).unwrap();
+    exif.set_gps_location(Location::new_from_coord(Coord(52.5), Coord(13.4)))
+        .unwrap();
+    assert_eq!(&exif.serialize().unwrap()[48..64], maker_note);
+
+    let report = exif.sanitize(&SanitizeProfile::location()).unwrap();
+    assert_eq!(report.removed_ifds, vec![IfdId::Gps]);
+    assert!(exif.gps_location().is_none());
+    assert_eq!(&exif.serialize().unwrap()[48..64], maker_note);
+
+    // Without the MakerNote, nothing has to stay in place
+    let report = exif.sanitize(&SanitizeProfile::maker_notes()).unwrap();
+    assert!(report.removed_entries.contains(&field::MakerNote.into()));
+    let raw = exif.serialize().unwrap();
+    assert!(raw.len() < 48);
+    assert!(!raw.windows(7).any(|x| x == b"payload"));
+}
+
+#[test]
+fn exif_compact_sony_maker_note() {
+    use gufo_common::field;
+
+    let artist = b"A photographer with a rather long name\0";
+
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8_u32.to_le_bytes());
+    data.extend(tiff_ifd(
+        &[
+            (0x010F, 2, 5, 50),
+            (0x013B, 2, artist.len().try_into().unwrap(), 114),
+            (0x8769, 4, 1, 56),
+        ],
+        0,
+    ));
+    data.extend_from_slice(b"SONY\0\0");
+    data.extend(tiff_ifd(&[(0x927C, 7, 38, 76)], 0));
+    data.extend_from_slice(&[0; 2]);
+
+    // Sony MakerNote with an offset relative to the TIFF header
+    let mut maker_note = b"SONY DSC \0\0\0".to_vec();
+    maker_note.extend(tiff_ifd(&[(0x2000, 2, 8, 106)], 0));
+    maker_note.extend_from_slice(b"Sony!!!\0");
+    data.extend_from_slice(&maker_note);
+    data.extend_from_slice(artist);
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(data.clone()).unwrap();
+    assert!(exif.delete(field::Artist.into()).unwrap());
+    exif.compact().unwrap();
+
+    // The data behind the MakerNote are dropped
+    let raw = exif.serialize().unwrap();
+    assert_eq!(raw.len(), 114);
+    assert_eq!(&raw[76..114], maker_note);
+    assert_eq!(&raw[106..114], b"Sony!!!\0");
+
+    let exif = gufo_exif::ExifOwned::for_vec(raw).unwrap();
+    assert_eq!(exif.make().as_deref(), Some("SONY"));
+    assert_eq!(exif.artist(), None);
+    assert_eq!(
+        exif.view().lookup(field::MakerNote.into()).unwrap(),
+        Some(gufo_exif::Typed::Undefined(maker_note))
+    );
+}
+
+#[test]
+fn exif_validate_repair() {
+    use gufo_common::exif::Field;
+    use gufo_common::field;
+    use gufo_exif::structure::{FindingKind, Severity};
+
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8_u32.to_le_bytes());
+
+    // Unsorted entries, two orientation values, and software string outside the
+    // data
+    let entries: [(u16, u16, u32, [u8; 4]); 3] = [
+        (0x112, 3, 2, [1, 0, 1, 0]),
+        (0x10F, 2, 4, *b"Guf\0"),
+        (0x131, 2, 16, 1000_u32.to_le_bytes()),
+    ];
+    data.extend_from_slice(&3_u16.to_le_bytes());
+    for (tag, type_, count, value) in entries {
+        data.extend_from_slice(&tag.to_le_bytes());
+        data.extend_from_slice(&type_.to_le_bytes());
+        data.extend_from_slice(&count.to_le_bytes());
+        data.extend_from_slice(&value);
+    }
+    data.extend_from_slice(&0_u32.to_le_bytes());
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(data).unwrap();
+    let findings = exif.validate().unwrap();
+
+    let find = |kind: fn(&FindingKind) -> bool| {
+        findings
+            .iter()
+            .find(|x| kind(&x.kind))
+            .unwrap_or_else(|| panic!("{findings:?}"))
+    };
+
+    let unsorted = find(|x| matches!(x, FindingKind::UnsortedEntries));
+    assert_eq!(unsorted.offset, 8);
+    assert!(unsorted.repairable);
+
+    let count = find(|x| matches!(x, FindingKind::CountMismatch { count: 2, .. }));
+    assert_eq!(count.tag, Some(<field::Orientation as Field>::TAG));
+    assert_eq!(count.offset, 10);
+    assert!(count.repairable);
+
+    let out_of_bounds = find(|x| matches!(x, FindingKind::DataOutOfBounds { .. }));
+    assert_eq!(out_of_bounds.tag, Some(<field::Software as Field>::TAG));
+    assert_eq!(out_of_bounds.severity, Severity::Error);
+    assert!(out_of_bounds.repairable);
+
+    let missing = find(|x| matches!(x, FindingKind::MissingTag));
+    assert!(!missing.repairable);
+
+    let repaired = exif.repair().unwrap();
+    assert_eq!(
+        repaired.len(),
+        findings.iter().filter(|x| x.repairable).count()
+    );
+
+    let findings = exif.validate().unwrap();
+    assert!(findings.iter().all(|x| !x.repairable), "{findings:?}");
+    assert!(findings.iter().all(|x| x.severity == Severity::Warning));
+
+    assert_eq!(
+        exif.orientation(),
+        Some(gufo_common::orientation::Orientation::Id)
+    );
+    assert_eq!(exif.make().as_deref(), Some("Guf"));
+    assert_eq!(exif.software(), None);
+}
+
+#[test]
+fn exif_validate_duplicates() {
+    use gufo_common::exif::{Field, IfdId};
+    use gufo_common::field;
+    use gufo_exif::structure::FindingKind;
+
+    // Make occurs twice, the Exif IFD has a next IFD, and the data of
+    // ImageDescription and Artist overlap
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8_u32.to_le_bytes());
+    data.extend(tiff_ifd(
+        &[
+            (0x10E, 2, 8, 104),
+            (0x10F, 2, 4, u32::from_le_bytes(*b"Guf\0")),
+            (0x10F, 2, 4, u32::from_le_bytes(*b"Owl\0")),
+            (0x112, 3, 2, 0x0001_0001),
+            (0x13B, 2, 8, 108),
+            (0x8769, 4, 1, 86),
+        ],
+        0,
+    ));
+    data.extend(tiff_ifd(&[(0x9000, 7, 4, u32::from_le_bytes(*b"0232"))], 4));
+    data.extend_from_slice(b"ABCDEFGHIJK\0");
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(data).unwrap();
+    assert_eq!(exif.make().as_deref(), Some("Owl"));
+
+    let findings = exif.validate().unwrap();
+    let find = |kind: fn(&FindingKind) -> bool| {
+        findings
+            .iter()
+            .find(|x| kind(&x.kind))
+            .unwrap_or_else(|| panic!("{findings:?}"))
+    };
+
+    // Position of the fourth entry in the stored list
+    let count = find(|x| matches!(x, FindingKind::CountMismatch { count: 2, .. }));
+    assert_eq!(count.tag, Some(<field::Orientation as Field>::TAG));
+    assert_eq!(count.offset, 8 + 2 + 3 * 12);
+
+    let overlap = find(|x| matches!(x, FindingKind::OverlappingData { .. }));
+    assert_eq!(overlap.tag, Some(<field::Artist as Field>::TAG));
+    assert_eq!(overlap.offset, 8 + 2 + 4 * 12);
+    assert!(!overlap.repairable);
+
+    let next_ifd = find(|x| matches!(x, FindingKind::UnexpectedNextIfd { offset: 4 }));
+    assert_eq!(next_ifd.ifd, IfdId::Exif);
+    assert!(next_ifd.repairable);
+
+    let repaired = exif.repair().unwrap();
+    assert_eq!(
+        repaired.len(),
+        findings.iter().filter(|x| x.repairable).count()
+    );
+
+    let findings = exif.validate().unwrap();
+    assert!(findings.iter().all(|x| !x.repairable), "{findings:?}");
+    assert_eq!(exif.make().as_deref(), Some("Owl"));
+}
+
+#[test]
+fn exif_lenient_parse() {
+    use gufo_common::exif::IfdId;
+    use gufo_exif::structure::ParseMode;
+
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let mut raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let gps_pos = gufo_exif::ExifOwned::for_vec(raw_exif.clone())
+        .unwrap()
+        .document(|x| x.ifds()[&IfdId::Gps].0)
+        .unwrap();
+
+    // Number of entries that exceeds the data
+    raw_exif[gps_pos..gps_pos + 2].copy_from_slice(&[0xFF, 0xFF]);
+
+    assert!(gufo_exif::ExifOwned::for_vec(raw_exif.clone()).is_err());
+
+    let mut exif =
+        gufo_exif::ExifOwned::for_vec_with_mode(raw_exif.clone(), ParseMode::Lenient).unwrap();
+
+    let diagnostics = exif.parse_diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].ifd, IfdId::Gps);
+    assert_eq!(diagnostics[0].offset, gps_pos);
+
+    assert_eq!(exif.model().as_deref(), Some("iPhone 6"));
+    assert!(exif.gps_location().is_none());
+    exif.document(|x| assert!(x.ifds().contains_key(&IfdId::Exif)))
+        .unwrap();
+
+    // Broken data are kept as they are
+    assert_eq!(exif.serialize().unwrap(), raw_exif);
+
+    exif.update_entry(
+        gufo_common::field::Orientation.into(),
+        gufo_exif::Typed::Short(vec![6]),
+    )
+    .unwrap();
+    let raw = exif.serialize().unwrap();
+    assert_eq!(raw.len(), raw_exif.len());
+    assert_eq!(raw[gps_pos..], raw_exif[gps_pos..]);
+    assert_eq!(
+        exif.orientation(),
+        Some(gufo_common::orientation::Orientation::Rotation270)
+    );
+}
+
+/// IFD in little endian with entries of one value that fits into the entry
+fn tiff_ifd(entries: &[(u16, u16, u32, u32)], next: u32) -> Vec<u8> {
+    let mut data = u16::try_from(entries.len()).unwrap().to_le_bytes().to_vec();
+    for (tag, type_, count, value) in entries {
+        data.extend_from_slice(&tag.to_le_bytes());
+        data.extend_from_slice(&type_.to_le_bytes());
+        data.extend_from_slice(&count.to_le_bytes());
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&next.to_le_bytes());
+    data
+}
+
+#[test]
+fn exif_sub_ifds_and_chain() {
+    use gufo_common::exif::{Field, IfdId, TagIfd};
+    use gufo_common::field;
+
+    const WIDTH: u16 = 0x100;
+    const SUB_IFDS: u16 = 0x14A;
+
+    // Three IFDs in a chain, two SubIFDs in IFD0, and one nested SubIFD
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8_u32.to_le_bytes());
+    data.extend(tiff_ifd(&[(WIDTH, 4, 1, 100), (SUB_IFDS, 4, 2, 38)], 46));
+    data.extend_from_slice(&82_u32.to_le_bytes());
+    data.extend_from_slice(&112_u32.to_le_bytes());
+    data.extend(tiff_ifd(&[(WIDTH, 4, 1, 10)], 64));
+    data.extend(tiff_ifd(&[(WIDTH, 4, 1, 200)], 0));
+    data.extend(tiff_ifd(&[(WIDTH, 4, 1, 1000), (SUB_IFDS, 13, 1, 130)], 0));
+    data.extend(tiff_ifd(&[(WIDTH, 4, 1, 50)], 0));
+    data.extend(tiff_ifd(&[(WIDTH, 4, 1, 7)], 0));
+
+    let raw_subifd = IfdId::Primary.sub_ifd(0).unwrap();
+    let preview = IfdId::Primary.sub_ifd(1).unwrap();
+    let nested = raw_subifd.sub_ifd(0).unwrap();
+
+    let width = |exif: &gufo_exif::ExifOwned, ifd| {
+        exif.view()
+            .lookup_uints(TagIfd::new(field::ImageWidth::TAG, ifd))
+            .unwrap()
+    };
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(data.clone()).unwrap();
+
+    // Only read when accessed
+    exif.document(|x| assert_eq!(x.ifds().len(), 2)).unwrap();
+
+    for mut exif in [
+        exif.clone(),
+        gufo_exif::ExifOwned::for_vec(exif.serialize_compact().unwrap()).unwrap(),
+    ] {
+        assert_eq!(width(&exif, IfdId::Primary), Some(vec![100]));
+        assert_eq!(width(&exif, IfdId::Thumbnail), Some(vec![10]));
+        assert_eq!(width(&exif, IfdId::chain(2)), Some(vec![200]));
+        assert_eq!(width(&exif, IfdId::chain(3)), None);
+        assert_eq!(width(&exif, raw_subifd), Some(vec![1000]));
+        assert_eq!(width(&exif, preview), Some(vec![50]));
+        assert_eq!(width(&exif, nested), Some(vec![7]));
+        assert_eq!(width(&exif, preview.sub_ifd(0).unwrap()), None);
+
+        exif.document(|x| {
+            x.load_all_ifds().unwrap();
+            assert_eq!(x.ifds().len(), 6);
+            assert!(x.parse_diagnostics().is_empty());
+        })
+        .unwrap();
+
+        assert!(exif.validate().unwrap().iter().all(|x| !matches!(
+            x.kind,
+            gufo_exif::structure::FindingKind::UnreadableIfd { .. }
+                | gufo_exif::structure::FindingKind::IfdLoop { .. }
+        )));
+    }
+
+    // Reading IFDs lazily does not change the data
+    assert_eq!(exif.serialize().unwrap(), data);
+}
+
+#[test]
+fn exif_remove_thumbnail_chain() {
+    use gufo_common::exif::{Field, IfdId, TagIfd};
+    use gufo_common::field;
+
+    const WIDTH: u16 = 0x100;
+
+    // Four IFDs in a chain, like in a multi-page TIFF
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8_u32.to_le_bytes());
+    data.extend(tiff_ifd(&[(WIDTH, 4, 1, 100)], 26));
+    data.extend(tiff_ifd(&[(WIDTH, 4, 1, 10)], 44));
+    data.extend(tiff_ifd(&[(WIDTH, 4, 1, 200)], 62));
+    data.extend(tiff_ifd(&[(WIDTH, 4, 1, 300)], 0));
+
+    let width = |exif: &gufo_exif::ExifOwned, ifd| {
+        exif.view()
+            .lookup_uints(TagIfd::new(field::ImageWidth::TAG, ifd))
+            .unwrap()
+    };
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(data).unwrap();
+    assert_eq!(width(&exif, IfdId::Thumbnail), Some(vec![10]));
+    assert_eq!(width(&exif, IfdId::chain(3)), Some(vec![300]));
+
+    assert!(exif.remove_thumbnail().unwrap());
+
+    // The IFDs after the thumbnail move up in the chain
+    let exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+    assert_eq!(width(&exif, IfdId::Primary), Some(vec![100]));
+    assert_eq!(width(&exif, IfdId::Thumbnail), Some(vec![200]));
+    assert_eq!(width(&exif, IfdId::chain(2)), Some(vec![300]));
+    assert_eq!(width(&exif, IfdId::chain(3)), None);
+}
+
+#[test]
+fn exif_big_tiff_sub_ifds() {
+    use gufo_common::exif::{Field, IfdId, TagIfd};
+    use gufo_common::field;
+
+    fn big_tiff_ifd(entries: &[(u16, u16, u64, u64)], next: u64) -> Vec<u8> {
+        let mut data = u64::try_from(entries.len()).unwrap().to_le_bytes().to_vec();
+        for (tag, type_, count, value) in entries {
+            data.extend_from_slice(&tag.to_le_bytes());
+            data.extend_from_slice(&type_.to_le_bytes());
+            data.extend_from_slice(&count.to_le_bytes());
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&next.to_le_bytes());
+        data
+    }
+
+    // Two SubIFDs referenced via LONG8 offsets
+    let mut data = b"II+\0\x08\0\0\0".to_vec();
+    data.extend_from_slice(&16_u64.to_le_bytes());
+    data.extend(big_tiff_ifd(&[(0x100, 4, 1, 100), (0x14A, 16, 2, 72)], 0));
+    data.extend_from_slice(&88_u64.to_le_bytes());
+    data.extend_from_slice(&124_u64.to_le_bytes());
+    data.extend(big_tiff_ifd(&[(0x100, 4, 1, 1000)], 0));
+    data.extend(big_tiff_ifd(&[(0x100, 4, 1, 50)], 0));
+
+    let exif = gufo_exif::ExifOwned::for_vec(data).unwrap();
+
+    for exif in [
+        exif.clone(),
+        gufo_exif::ExifOwned::for_vec(exif.serialize_compact().unwrap()).unwrap(),
+    ] {
+        for (index, width) in [(0, 1000), (1, 50)] {
+            let sub_ifd = IfdId::Primary.sub_ifd(index).unwrap();
+            assert_eq!(
+                exif.view()
+                    .lookup_uints(TagIfd::new(field::ImageWidth::TAG, sub_ifd))
+                    .unwrap(),
+                Some(vec![width])
+            );
+        }
+        assert_eq!(
+            exif.view()
+                .lookup(TagIfd::new(field::SubIFDs::TAG, IfdId::Primary))
+                .unwrap()
+                .map(|x| x.type_()),
+            Some(gufo_exif::structure::Type::Long8)
+        );
+    }
+}
+
+#[test]
+fn exif_chain_id() {
+    use gufo_common::exif::{ChainId, IfdId};
+
+    assert_eq!(IfdId::chain(0), IfdId::Primary);
+    assert_eq!(IfdId::chain(1), IfdId::Thumbnail);
+    assert!(ChainId::try_from(1).is_err());
+    assert_eq!(
+        ChainId::try_from(2).map(IfdId::Chain).unwrap(),
+        IfdId::chain(2)
+    );
+
+    let json = serde_json::to_string(&IfdId::chain(2)).unwrap();
+    assert_eq!(
+        serde_json::from_str::<IfdId>(&json).unwrap(),
+        IfdId::chain(2)
+    );
+    assert!(serde_json::from_str::<IfdId>(&json.replace('2', "1")).is_err());
+
+    // SubIFD paths must fit the nesting depth
+    let sub_ifd = IfdId::Primary.sub_ifd(1).unwrap().sub_ifd(3).unwrap();
+    let json = serde_json::to_value(sub_ifd).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"SubIfd": {"chain": 0, "path": [1, 3]}})
+    );
+    assert_eq!(serde_json::from_value::<IfdId>(json).unwrap(), sub_ifd);
+    for path in [serde_json::json!([]), serde_json::json!([0, 1, 2, 3, 4])] {
+        let json = serde_json::json!({"SubIfd": {"chain": 0, "path": path}});
+        assert!(serde_json::from_value::<IfdId>(json).is_err());
+    }
+
+    // The thumbnail IFD points to an IFD outside of the data
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8_u32.to_le_bytes());
+    data.extend(tiff_ifd(&[(0x100, 4, 1, 100)], 26));
+    data.extend(tiff_ifd(&[(0x100, 4, 1, 10)], 1000));
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(data).unwrap();
+    exif.document(|x| {
+        assert!(x.ifd(IfdId::Thumbnail).unwrap().is_some());
+        assert!(x.ifd(IfdId::chain(2)).is_err());
+    })
+    .unwrap();
+}
+
+#[test]
+fn exif_convert_byte_order() {
+    use gufo_common::exif::{IfdId, Tag, TagIfd, Vendor};
+    use gufo_common::field;
+    use gufo_exif::structure::{Endieness, Type};
+    use gufo_exif::{ExifBuilder, Typed};
+
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    let endieness = exif.endieness();
+    let other = match endieness {
+        Endieness::Big => Endieness::Litte,
+        Endieness::Litte => Endieness::Big,
+    };
+
+    assert_eq!(
+        exif.serialize_with(endieness).unwrap(),
+        exif.serialize().unwrap()
+    );
+
+    let mut converted = gufo_exif::ExifOwned::for_vec(exif.serialize_with(other).unwrap()).unwrap();
+    assert_eq!(converted.endieness(), other);
+
+    let entries = exif.document(|x| x.entries().unwrap()).unwrap();
+    let converted_entries = converted.document(|x| x.entries().unwrap()).unwrap();
+    assert_eq!(entries.len(), converted_entries.len());
+
+    for (ifd, entries) in entries {
+        for (tag, entry) in entries {
+            let converted_entry = &converted_entries[&ifd][&tag];
+            assert_eq!(entry.type_, converted_entry.type_);
+            assert_eq!(entry.count, converted_entry.count);
+            if !tag.is_exif_specific_ifd() && !tag.is_data_offset() {
+                assert_eq!(
+                    entry.data.as_ref().ok(),
+                    converted_entry.data.as_ref().ok(),
+                    "{tag:?}"
+                );
+            }
+        }
+    }
+
+    assert_eq!(converted.model().as_deref(), Some("iPhone 6"));
+    assert_eq!(
+        converted.apple_content_identifier(),
+        exif.apple_content_identifier()
+    );
+    assert_eq!(
+        converted.gps_location().unwrap().iso_6709(),
+        exif.gps_location().unwrap().iso_6709()
+    );
+
+    // Converting back gives the same result as rebuilding
+    assert_eq!(
+        converted.serialize_with(endieness).unwrap(),
+        exif.serialize_compact().unwrap()
+    );
+
+    // Unicode user comments are stored in the byte order of the Exif data
+    let mut user_comment = b"UNICODE\0".to_vec();
+    user_comment.extend("Gufo".encode_utf16().flat_map(u16::to_be_bytes));
+    let raw_exif = ExifBuilder::new(Endieness::Big)
+        .entry(field::UserComment, Typed::Undefined(user_comment))
+        .build()
+        .unwrap();
+    let exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    let converted =
+        gufo_exif::ExifOwned::for_vec(exif.serialize_with(Endieness::Litte).unwrap()).unwrap();
+    let mut expected = b"UNICODE\0".to_vec();
+    expected.extend("Gufo".encode_utf16().flat_map(u16::to_le_bytes));
+    assert_eq!(
+        converted.view().lookup(field::UserComment.into()).unwrap(),
+        Some(Typed::Undefined(expected))
+    );
+
+    // Values of types that aren't decoded are converted by their size
+    let raw_exif = ExifBuilder::new(Endieness::Big)
+        .entry(
+            TagIfd::new(Tag(0xC000), IfdId::Primary),
+            Typed::Unknown(Type::SShort.u16(), vec![0x12, 0x34, 0x56, 0x78]),
+        )
+        .entry(
+            TagIfd::new(Tag(0xC001), IfdId::Primary),
+            Typed::Unknown(Type::Double.u16(), 1.5_f64.to_be_bytes().to_vec()),
+        )
+        .build()
+        .unwrap();
+    let exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    let mut converted =
+        gufo_exif::ExifOwned::for_vec(exif.serialize_with(Endieness::Litte).unwrap()).unwrap();
+    assert_eq!(
+        converted
+            .view()
+            .lookup(TagIfd::new(Tag(0xC000), IfdId::Primary))
+            .unwrap(),
+        Some(Typed::Unknown(
+            Type::SShort.u16(),
+            vec![0x34, 0x12, 0x78, 0x56]
+        ))
+    );
+    assert_eq!(
+        converted
+            .view()
+            .lookup(TagIfd::new(Tag(0xC001), IfdId::Primary))
+            .unwrap(),
+        Some(Typed::Unknown(
+            Type::Double.u16(),
+            1.5_f64.to_le_bytes().to_vec()
+        ))
+    );
+    converted
+        .document(|x| {
+            let entry = x.entry(TagIfd::new(Tag(0xC000), IfdId::Primary)).unwrap().1;
+            assert_eq!(entry.type_(), Type::SShort);
+            assert_eq!(entry.count().unwrap(), 2);
+        })
+        .unwrap();
+
+    // Uncompressed 16-bit samples depend on the byte order
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8_u32.to_le_bytes());
+    data.extend(tiff_ifd(
+        &[
+            (0x102, 3, 1, 16),
+            (0x103, 3, 1, 1),
+            (0x111, 4, 1, 62),
+            (0x117, 4, 1, 4),
+        ],
+        0,
+    ));
+    data.extend_from_slice(&[1, 2, 3, 4]);
+    let exif = gufo_exif::ExifOwned::for_vec(data).unwrap();
+    assert!(matches!(
+        exif.serialize_with(Endieness::Big),
+        Err(gufo_exif::Error::ImageDataByteOrder(IfdId::Primary))
+    ));
+
+    // MakerNotes with unknown structure can't be converted
+    let raw_exif = ExifBuilder::new(Endieness::Big)
+        .entry(field::Make, Typed::Ascii(b"Gufo".to_vec()))
+        .entry(field::MakerNote, Typed::Undefined(vec![0xFF; 16]))
+        .build()
+        .unwrap();
+    let exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    assert!(matches!(
+        exif.serialize_with(Endieness::Litte),
+        Err(gufo_exif::Error::MakerNoteByteOrder(IfdId::MakerNote(
+            Vendor::Unknown
+        )))
+    ));
+}
+
+#[test]
+fn exif_date_time() {
+    use gufo_common::datetime::DateTime;
+    use gufo_exif::DateTimeField;
+
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif.clone()).unwrap();
+    assert_eq!(
+        exif.date_time_original().unwrap().to_string(),
+        "2020-07-05 15:46:21.822"
+    );
+
+    // Shifting keeps all values in place
+    exif.shift_date_times(chrono::TimeDelta::minutes(90))
+        .unwrap();
+    assert_eq!(exif.serialize().unwrap().len(), raw_exif.len());
+    assert_eq!(exif.date_time().unwrap().to_string(), "2024-05-23 00:15:41");
+    assert_eq!(
+        exif.date_time_original().unwrap().to_string(),
+        "2020-07-05 17:16:21.822"
+    );
+    assert_eq!(
+        exif.date_time_digitized().unwrap().to_string(),
+        "2020-07-05 17:16:21.822"
+    );
+
+    // Existing entries can be updated by changing single bytes
+    let date_time =
+        chrono::NaiveDateTime::parse_from_str("2021-01-02 03:04:05.678901", "%Y-%m-%d %H:%M:%S%.f")
+            .unwrap();
+    let values = exif
+        .date_time_values(DateTimeField::Digitized, &DateTime::Naive(date_time))
+        .unwrap();
+    assert_eq!(values.len(), 2);
+    let mut patched = exif.serialize().unwrap();
+    for (tag_ifd, value) in values {
+        for (pos, byte) in exif.update_entry_diff(tag_ifd, value).unwrap() {
+            patched[pos] = byte;
+        }
+    }
+    let patched = gufo_exif::ExifOwned::for_vec(patched).unwrap();
+    assert_eq!(
+        patched.date_time_digitized().unwrap().to_string(),
+        "2021-01-02 03:04:05.678"
+    );
+
+    // Attach a time zone to local times
+    let cest = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+    exif.set_time_zone(cest).unwrap();
+    let mut exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+    assert_eq!(
+        exif.date_time_original().unwrap().to_string(),
+        "2020-07-05 17:16:21.822 +02:00"
+    );
+    assert_eq!(
+        exif.view()
+            .lookup(gufo_common::field::OffsetTimeOriginal.into())
+            .unwrap(),
+        Some(gufo_exif::Typed::Ascii(b"+02:00".to_vec()))
+    );
+
+    // Convert times with offsets to another time zone
+    let edt = chrono::FixedOffset::west_opt(4 * 3600).unwrap();
+    exif.set_time_zone(edt).unwrap();
+    assert_eq!(
+        exif.date_time_field(DateTimeField::Original)
+            .unwrap()
+            .to_string(),
+        "2020-07-05 11:16:21.822 -04:00"
+    );
+    assert_eq!(
+        exif.date_time().unwrap().to_string(),
+        "2024-05-22 18:15:41 -04:00"
+    );
+
+    // Years with more than four digits don't fit
+    let raw_exif = exif.serialize().unwrap();
+    let year_10000 = chrono::NaiveDate::from_ymd_opt(10000, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    assert!(
+        exif.set_date_time(DateTimeField::Original, &DateTime::Naive(year_10000))
+            .is_err()
+    );
+    assert!(
+        exif.shift_date_times(chrono::TimeDelta::days(365 * 8000))
+            .is_err()
+    );
+    assert_eq!(exif.serialize().unwrap(), raw_exif);
+
+    // Subseconds without digits keep their length
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8_u32.to_le_bytes());
+    data.extend(tiff_ifd(&[(0x8769, 4, 1, 26)], 0));
+    data.extend(tiff_ifd(&[(0x9003, 2, 20, 56), (0x9291, 2, 0, 0)], 0));
+    data.extend_from_slice(b"2020:07:05 15:46:21\0");
+    let mut exif = gufo_exif::ExifOwned::for_vec(data.clone()).unwrap();
+
+    exif.set_date_time(DateTimeField::Original, &DateTime::Naive(date_time))
+        .unwrap();
+    let raw_exif = exif.serialize().unwrap();
+    assert_eq!(raw_exif.len(), data.len());
+    assert_eq!(raw_exif[40..52], data[40..52]);
+    assert_eq!(
+        exif.date_time_original().unwrap().to_string(),
+        "2021-01-02 03:04:05"
+    );
+}
+
+#[test]
+fn exif_sanitize() {
+    use gufo_common::exif::{IfdId, TagIfd};
+    use gufo_common::field;
+    use gufo_exif::{SanitizeProfile, Typed};
+
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif.clone()).unwrap();
+    exif.update_entry(
+        field::BodySerialNumber.into(),
+        Typed::Ascii(b"SN-0123456789".to_vec()),
+    )
+    .unwrap();
+    exif.update_entry(
+        field::MakerNote.into(),
+        Typed::Undefined(nikon_maker_note()),
+    )
+    .unwrap();
+    let exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+
+    // Location
+    let mut sanitized = exif.clone();
+    let report = sanitized.sanitize(&SanitizeProfile::location()).unwrap();
+    assert_eq!(report.removed_ifds, vec![IfdId::Gps]);
+    assert!(
+        report
+            .removed_entries
+            .contains(&field::GPSInfoIFDPointer.into())
+    );
+    assert!(sanitized.gps_location().is_none());
+    assert_eq!(sanitized.model().as_deref(), Some("iPhone 6"));
+
+    // Device identity
+    let mut sanitized = exif.clone();
+    let report = sanitized
+        .sanitize(&SanitizeProfile::device_identity())
+        .unwrap();
+    assert_eq!(
+        report.removed_entries,
+        vec![
+            TagIfd::from(field::NikonSerialNumber),
+            TagIfd::from(field::BodySerialNumber)
+        ]
+    );
+    assert!(report.removed_ifds.is_empty());
+    let raw = sanitized.serialize().unwrap();
+    assert!(!raw.windows(13).any(|x| x == b"SN-0123456789"));
+    assert!(!raw.windows(7).any(|x| x == b"3012345"));
+    // The remaining MakerNote entries stay readable
+    assert_eq!(
+        sanitized
+            .view()
+            .lookup_uints(field::NikonShutterCount.into())
+            .unwrap(),
+        Some(vec![12345])
+    );
+    assert!(
+        sanitized
+            .view()
+            .lookup_string(field::NikonSerialNumber.into())
+            .unwrap()
+            .is_none()
+    );
+
+    // Device identity in Apple MakerNote
+    let mut apple = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    apple
+        .update_entry(
+            field::BodySerialNumber.into(),
+            Typed::Ascii(b"SN-0123456789".to_vec()),
+        )
+        .unwrap();
+    apple
+        .update_entry(
+            field::MakerNote.into(),
+            Typed::Undefined(apple_maker_note()),
+        )
+        .unwrap();
+    let mut apple = gufo_exif::ExifOwned::for_vec(apple.serialize().unwrap()).unwrap();
+    let report = apple.sanitize(&SanitizeProfile::device_identity()).unwrap();
+    assert_eq!(
+        report.removed_entries,
+        vec![
+            TagIfd::from(field::AppleBurstUUID),
+            TagIfd::from(field::AppleContentIdentifier),
+            TagIfd::from(field::BodySerialNumber)
+        ]
+    );
+    assert!(apple.apple_burst_uuid().is_none());
+    assert!(apple.apple_content_identifier().is_none());
+    assert_eq!(apple.apple_hdr_gain(), Some(0.75));
+    let raw = apple.serialize().unwrap();
+    assert!(!raw.windows(8).any(|x| x == b"F3C1A2B4"));
+    assert!(!raw.windows(8).any(|x| x == b"0A1B2C3D"));
+
+    // MakerNote
+    let mut sanitized = exif.clone();
+    let report = sanitized.sanitize(&SanitizeProfile::maker_notes()).unwrap();
+    assert_eq!(report.removed_entries, vec![TagIfd::from(field::MakerNote)]);
+    assert!(sanitized.maker_note_vendor().is_none());
+    let raw = sanitized.serialize().unwrap();
+    assert!(!raw.windows(5).any(|x| x == b"Nikon"));
+
+    // Everything but orientation and color space
+    let mut sanitized = exif.clone();
+    let report = sanitized
+        .sanitize(&SanitizeProfile::all_but_orientation_and_color_space())
+        .unwrap();
+    assert!(report.removed_ifds.contains(&IfdId::Thumbnail));
+    assert!(report.removed_ifds.contains(&IfdId::Gps));
+    assert_eq!(sanitized.orientation(), exif.orientation());
+    assert_eq!(sanitized.color_space(), exif.color_space());
+    assert!(sanitized.model().is_none());
+    assert!(sanitized.thumbnail().is_none());
+    let entries = sanitized.document(|x| x.entries().unwrap()).unwrap();
+    assert_eq!(
+        entries
+            .iter()
+            .flat_map(|(ifd, entries)| entries.keys().map(|tag| TagIfd::new(*tag, *ifd)))
+            .collect::<Vec<_>>(),
+        vec![
+            field::Orientation.into(),
+            field::ExifIFDPointer.into(),
+            field::ExifVersion.into(),
+            field::ColorSpace.into(),
+        ]
+    );
+
+    // Custom profile, applying it again doesn't remove anything
+    let profile = SanitizeProfile::location()
+        .with(SanitizeProfile::new().remove_entry(field::Make))
+        .remove_ifd(IfdId::Thumbnail);
+    let mut sanitized = exif.clone();
+    let report = sanitized.sanitize(&profile).unwrap();
+    assert_eq!(report.removed_ifds, vec![IfdId::Thumbnail, IfdId::Gps]);
+    assert!(report.removed_entries.contains(&field::Make.into()));
+    assert!(sanitized.make().is_none());
+    assert!(sanitized.sanitize(&profile).unwrap().is_empty());
+}
+
+#[test]
+fn exif_diff() {
+    use gufo_common::exif::{IfdId, Type};
+    use gufo_common::field;
+    use gufo_exif::structure::Endieness;
+    use gufo_exif::{EntryChange, EntryContent, StructureChange, Typed};
+
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    assert!(gufo_exif::diff(&exif, &exif.clone()).unwrap().is_empty());
+
+    // A new layout doesn't change entries
+    let mut compacted = exif.clone();
+    compacted.compact().unwrap();
+    let diff = gufo_exif::diff(&exif, &compacted).unwrap();
+    assert!(diff.entries.is_empty(), "{diff}");
+    assert!(
+        diff.structure
+            .iter()
+            .all(|x| matches!(x, StructureChange::IfdMoved { .. }))
+    );
+
+    let mut edited = exif.clone();
+    edited
+        .update_entry(field::Model.into(), Typed::Ascii(b"iPhone 7".to_vec()))
+        .unwrap();
+    edited
+        .update_entry(field::Artist.into(), Typed::Ascii(b"Gufo".to_vec()))
+        .unwrap();
+    edited
+        .update_entry(field::Orientation.into(), Typed::Long(vec![1]))
+        .unwrap();
+    edited.delete(field::Software.into()).unwrap();
+    assert!(edited.remove_gps().unwrap());
+
+    let diff = gufo_exif::diff(&exif, &edited).unwrap();
+    assert!(
+        diff.structure
+            .contains(&StructureChange::IfdRemoved(IfdId::Gps))
+    );
+
+    let Some(EntryChange::Changed { old, new }) = diff.entry(field::Model.into()) else {
+        panic!("{diff}");
+    };
+    assert_eq!(
+        old.content,
+        EntryContent::Value(Typed::Ascii(b"iPhone 6".to_vec()))
+    );
+    assert_eq!(
+        new.content,
+        EntryContent::Value(Typed::Ascii(b"iPhone 7".to_vec()))
+    );
+
+    let Some(EntryChange::Added(new)) = diff.entry(field::Artist.into()) else {
+        panic!("{diff}");
+    };
+    assert_eq!(new.type_, Type::Ascii);
+
+    let orientation = diff.entry(field::Orientation.into()).unwrap();
+    assert!(orientation.is_type_change());
+
+    assert!(matches!(
+        diff.entry(field::Software.into()),
+        Some(EntryChange::Removed(_))
+    ));
+    assert!(
+        diff.ifd(IfdId::Gps)
+            .all(|x| matches!(x.change, EntryChange::Removed(_)))
+    );
+    assert!(diff.ifd(IfdId::Primary).any(|x| x.name == Some("Model")));
+    assert!(
+        diff.to_string()
+            .contains("Primary Model: changed from Ascii iPhone 6")
+    );
+
+    // Image data are compared instead of their offsets
+    let thumbnail = exif.thumbnail().unwrap().to_vec();
+    let mut changed_thumbnail = thumbnail.clone();
+    changed_thumbnail[100] ^= 0xFF;
+    let mut old = exif.clone();
+    old.set_thumbnail(thumbnail).unwrap();
+    let mut new = exif.clone();
+    new.set_thumbnail(changed_thumbnail).unwrap();
+    let diff = gufo_exif::diff(&old, &new).unwrap();
+    assert!(diff.structure.is_empty(), "{diff}");
+    let Some(EntryChange::Changed { old, new }) = diff.entry(field::JPEGInterchangeFormat.into())
+    else {
+        panic!("{diff}");
+    };
+    assert!(matches!(old.content, EntryContent::ReferencedData(_)));
+    assert_ne!(old.content, new.content);
+    assert_eq!(diff.entries.len(), 1, "{diff}");
+
+    // Unreadable values are never considered equal
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8_u32.to_le_bytes());
+    data.extend(tiff_ifd(&[(0x10F, 2, 20, 1000)], 0));
+    let broken = gufo_exif::ExifOwned::for_vec(data).unwrap();
+    let diff = gufo_exif::diff(&broken, &broken.clone()).unwrap();
+    let Some(EntryChange::Changed { old, .. }) = diff.entry(field::Make.into()) else {
+        panic!("{diff}");
+    };
+    assert!(matches!(old.content, EntryContent::Unreadable(_)));
+
+    // Byte order
+    let endieness = match exif.endieness() {
+        Endieness::Big => Endieness::Litte,
+        Endieness::Litte => Endieness::Big,
+    };
+    let converted = gufo_exif::ExifOwned::for_vec(exif.serialize_with(endieness).unwrap()).unwrap();
+    let diff = gufo_exif::diff(&exif, &converted).unwrap();
+    assert!(diff.structure.contains(&StructureChange::Endieness {
+        old: exif.endieness(),
+        new: endieness
+    }));
+    assert!(diff.entries.is_empty(), "{diff}");
+}
+
+#[test]
+fn exif_serde_round_trip() {
+    use gufo_common::field;
+    use gufo_exif::{ExifExport, Typed, ValueExport};
+
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    exif.update_entry(
+        field::MakerNote.into(),
+        Typed::Undefined(nikon_maker_note()),
+    )
+    .unwrap();
+    let exif = gufo_exif::ExifOwned::for_vec(exif.serialize().unwrap()).unwrap();
+
+    let export = exif.export().unwrap();
+    let json = serde_json::to_value(&export).unwrap();
+
+    let model = export.entry(field::Model.into()).unwrap();
+    assert_eq!(model.value, ValueExport::Ascii("iPhone 6".into()));
+    assert_eq!(json["endieness"], "little");
+    let primary = &json["ifds"][0];
+    assert_eq!(primary["ifd"], "Primary");
+    let x_resolution = primary["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|x| x["name"] == "XResolution")
+        .unwrap();
+    assert_eq!(x_resolution["type"], "Rational");
+    assert_eq!(
+        x_resolution["value"]["rational"],
+        serde_json::json!([[72, 1]])
+    );
+    assert!(
+        export
+            .ifds
+            .iter()
+            .any(|x| x.ifd == gufo_common::exif::IfdId::Thumbnail)
+    );
+
+    // Round trip via JSON
+    let imported: ExifExport = serde_json::from_str(&json.to_string()).unwrap();
+    assert_eq!(imported, export);
+
+    let reimported = gufo_exif::ExifOwned::for_vec(imported.to_raw().unwrap()).unwrap();
+    let diff = gufo_exif::diff(&exif, &reimported).unwrap();
+    assert!(diff.entries.is_empty(), "{diff}");
+    assert_eq!(reimported.export().unwrap(), export);
+    assert_eq!(reimported.thumbnail(), exif.thumbnail());
+    assert_eq!(reimported.serial_number().as_deref(), Some("3012345"));
+    assert_eq!(reimported.shutter_count(), Some(12345));
+
+    // Edited values
+    let mut edited = export.clone();
+    let artist = gufo_exif::EntryExport {
+        tag: <field::Artist as gufo_common::exif::Field>::TAG.0,
+        name: None,
+        type_: gufo_common::exif::Type::Ascii,
+        count: 0,
+        value: ValueExport::Ascii("Gufo".into()),
+    };
+    edited.ifds[0].entries.insert(0, artist);
+    let edited = gufo_exif::ExifOwned::for_vec(edited.to_raw().unwrap()).unwrap();
+    assert_eq!(edited.artist().as_deref(), Some("Gufo"));
+}
+
+#[test]
+fn exif_ref() {
+    use std::collections::BTreeMap;
+
+    use gufo_common::exif::IfdId;
+    use gufo_common::field;
+    use gufo_exif::Typed;
+    use gufo_exif::structure::EntryTyped;
+
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    let with_maker_note = |maker_note| {
+        let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif.clone()).unwrap();
+        exif.update_entry(field::MakerNote.into(), Typed::Undefined(maker_note))
+            .unwrap();
+        exif.serialize().unwrap()
+    };
+
+    let data = std::fs::read("../gufo-exif/example.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let canon = jpeg.exif_data().next().unwrap().to_vec();
+
+    for raw_exif in [
+        canon,
+        with_maker_note(nikon_maker_note()),
+        with_maker_note(apple_maker_note()),
+    ] {
+        let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif.clone()).unwrap();
+        let mut exif_ref = gufo_exif::ExifRef::for_slice(&raw_exif).unwrap();
+
+        assert_eq!(exif_ref.maker_note_vendor(), exif.maker_note_vendor());
+        assert_eq!(exif_ref.model(), exif.model());
+        assert_eq!(exif_ref.orientation(), exif.orientation());
+        assert_eq!(
+            format!("{:?}", exif_ref.date_time_original()),
+            format!("{:?}", exif.date_time_original())
+        );
+        assert_eq!(exif_ref.serial_number(), exif.serial_number());
+        assert_eq!(exif_ref.shutter_count(), exif.shutter_count());
+        assert_eq!(exif_ref.thumbnail(), exif.thumbnail());
+        assert_eq!(
+            exif_ref.apple_acceleration_vector(),
+            exif.apple_acceleration_vector()
+        );
+
+        let entries = |entries: BTreeMap<_, BTreeMap<_, EntryTyped>>| {
+            entries
+                .into_values()
+                .flat_map(|x| x.into_values())
+                .map(|x| (x.tag_ifd, x.type_, x.count, x.data.ok()))
+                .collect::<Vec<_>>()
+        };
+        let ref_entries = entries(exif_ref.entries());
+        let doc_entries = entries(exif.document(|x| x.entries().unwrap()).unwrap());
+        assert_eq!(ref_entries.len(), doc_entries.len());
+        for (ref_entry, doc_entry) in ref_entries.into_iter().zip(doc_entries) {
+            // The document can't read MakerNote data that are also parsed as IFD
+            if doc_entry.3.is_none() {
+                assert_eq!(ref_entry.0, doc_entry.0);
+            } else {
+                assert_eq!(ref_entry, doc_entry);
+            }
+        }
+
+        // Data are borrowed from the slice
+        let model = exif_ref.entry_data(field::Model.into()).unwrap().unwrap();
+        assert!(raw_exif.as_ptr_range().contains(&model.data.as_ptr()));
+
+        // Read-only document for the same data
+        let mut document = gufo_exif::structure::Document::for_slice(&raw_exif).unwrap();
+        assert_eq!(document.model(), exif.model());
+        assert_eq!(document.serialize().unwrap(), raw_exif);
+    }
+
+    assert_eq!(
+        gufo_exif::ExifRef::for_slice(&with_maker_note(nikon_maker_note()))
+            .unwrap()
+            .maker_note_vendor(),
+        Some(gufo_exif::maker_note::Vendor::Nikon)
+    );
+
+    // Chain and SubIFDs are indexed directly
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8_u32.to_le_bytes());
+    data.extend(tiff_ifd(&[(0x100, 4, 1, 100), (0x14A, 4, 1, 56)], 38));
+    data.extend(tiff_ifd(&[(0x100, 4, 1, 10)], 0));
+    data.extend(tiff_ifd(&[(0x100, 4, 1, 50)], 0));
+
+    let mut exif_ref = gufo_exif::ExifRef::for_slice(&data).unwrap();
+    let ifds = exif_ref.ifds().map(|(ifd, _)| ifd).collect::<Vec<_>>();
+    let sub_ifd = IfdId::Primary.sub_ifd(0).unwrap();
+    assert_eq!(ifds, [IfdId::Primary, IfdId::Thumbnail, sub_ifd]);
+    assert_eq!(
+        exif_ref
+            .lookup_uints(gufo_common::exif::TagIfd::new(
+                <field::ImageWidth as gufo_common::exif::Field>::TAG,
+                sub_ifd
+            ))
+            .unwrap(),
+        Some(vec![50])
+    );
+    assert!(exif_ref.parse_diagnostics().is_empty());
+}
+
+#[test]
+fn exif_clone_and_document() {
+    use gufo_common::exif::{Field, Type};
+    use gufo_common::field;
+    use gufo_exif::Typed;
+
+    let data = std::fs::read("exif-xmp.jpg").unwrap();
+    let jpeg = gufo_jpeg::Jpeg::new(data).unwrap();
+    let mut raw_exif = jpeg.exif_data().next().unwrap().to_vec();
+
+    // Clones share the data until one of them changes
+    let exif = gufo_exif::ExifOwned::for_vec(raw_exif.clone()).unwrap();
+    let mut clone = exif.clone();
+    clone
+        .update_entry(field::Model.into(), Typed::Ascii(b"Gufo".to_vec()))
+        .unwrap();
+    assert_eq!(clone.model().as_deref(), Some("Gufo"));
+    assert_eq!(exif.model().as_deref(), Some("iPhone 6"));
+    assert_eq!(exif.serialize().unwrap(), raw_exif);
+
+    // Changes via the document are visible when reading afterwards
+    let mut exif = gufo_exif::ExifMutBorrowed::for_mut_slice(&mut raw_exif).unwrap();
+    exif.document(|x| {
+        let (_, mut entry) = x.entry(field::Orientation.into()).unwrap();
+        entry
+            .update(field::Orientation::TAG, Type::Short, 1, vec![3, 0])
+            .unwrap();
+    })
+    .unwrap();
+    assert_eq!(
+        exif.view().lookup_short(field::Orientation.into()).unwrap(),
+        Some(3)
+    );
+}
+
+#[test]
+fn exif_character_identified_code() {
+    use gufo_common::field;
+    use gufo_exif::structure::{Endieness, SerializeMode};
+    use gufo_exif::{ExifBuilder, Typed};
+
+    let raw_exif = ExifBuilder::new(Endieness::Big)
+        .entry(field::Model, Typed::Ascii(b"Gufo".to_vec()))
+        .entry(
+            field::UserComment,
+            Typed::Undefined(b"JIS\0\0\0\0\0Gufo".to_vec()),
+        )
+        .build()
+        .unwrap();
+    let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+    assert_eq!(exif.user_comment().as_deref(), Some("Gufo"));
+
+    // ASCII is used if possible
+    exif.set_user_comment("Owl").unwrap();
+    assert_eq!(
+        exif.view().lookup(field::UserComment.into()).unwrap(),
+        Some(Typed::Undefined(b"ASCII\0\0\0Owl".to_vec()))
+    );
+    assert_eq!(exif.user_comment().as_deref(), Some("Owl"));
+
+    // Longer values require rebuilding the structure
+    assert!(matches!(
+        exif.set_user_comment("Snowy owl"),
+        Err(gufo_exif::Error::WouldIncreaseDataStore)
+    ));
+    exif.set_serialize_mode(SerializeMode::Rebuild);
+
+    // UNICODE in the byte order of the data otherwise
+    exif.set_user_comment("Schneeeule ❄").unwrap();
+    let mut expected = b"UNICODE\0".to_vec();
+    expected.extend("Schneeeule ❄".encode_utf16().flat_map(u16::to_be_bytes));
+    assert_eq!(
+        exif.view().lookup(field::UserComment.into()).unwrap(),
+        Some(Typed::Undefined(expected))
+    );
+    assert_eq!(exif.user_comment().as_deref(), Some("Schneeeule ❄"));
+
+    // GPS IFD is created
+    exif.set_gps_processing_method("GPS").unwrap();
+    exif.set_gps_area_information("Köln").unwrap();
+    assert_eq!(exif.gps_processing_method().as_deref(), Some("GPS"));
+    assert_eq!(exif.gps_area_information().as_deref(), Some("Köln"));
+
+    // Text is converted to the other byte order
+    let converted =
+        gufo_exif::ExifOwned::for_vec(exif.serialize_with(Endieness::Litte).unwrap()).unwrap();
+    let mut expected = b"UNICODE\0".to_vec();
+    expected.extend("Köln".encode_utf16().flat_map(u16::to_le_bytes));
+    assert_eq!(
+        converted
+            .view()
+            .lookup(field::GPSAreaInformation.into())
+            .unwrap(),
+        Some(Typed::Undefined(expected))
+    );
+    assert_eq!(converted.gps_area_information().as_deref(), Some("Köln"));
+    assert_eq!(converted.user_comment().as_deref(), Some("Schneeeule ❄"));
+}
+
+#[test]
+fn exif_character_identified_code_round_trip() {
+    use gufo_common::field;
+    use gufo_exif::structure::{Endieness, SerializeMode};
+    use gufo_exif::{ExifBuilder, Typed};
+
+    // UTF-16 code units of these are valid UTF-8 as well
+    for text in ["中", "❄", "日本語", "€", "Eule ❄"] {
+        for endieness in [Endieness::Big, Endieness::Litte] {
+            let raw_exif = ExifBuilder::new(endieness)
+                .entry(field::Model, Typed::Ascii(b"Gufo".to_vec()))
+                .entry(field::ExifVersion, Typed::Undefined(b"0300".to_vec()))
+                .build()
+                .unwrap();
+            let mut exif = gufo_exif::ExifOwned::for_vec(raw_exif).unwrap();
+            exif.set_serialize_mode(SerializeMode::Rebuild);
+
+            exif.set_user_comment(text).unwrap();
+            exif.set_gps_processing_method(text).unwrap();
+            exif.set_gps_area_information(text).unwrap();
+
+            let other = match endieness {
+                Endieness::Big => Endieness::Litte,
+                Endieness::Litte => Endieness::Big,
+            };
+            let converted =
+                gufo_exif::ExifOwned::for_vec(exif.serialize_with(other).unwrap()).unwrap();
+
+            for exif in [&exif, &converted] {
+                assert_eq!(exif.user_comment().as_deref(), Some(text), "{endieness:?}");
+                assert_eq!(
+                    exif.gps_processing_method().as_deref(),
+                    Some(text),
+                    "{endieness:?}"
+                );
+                assert_eq!(
+                    exif.gps_area_information().as_deref(),
+                    Some(text),
+                    "{endieness:?}"
+                );
+            }
+        }
+    }
+}